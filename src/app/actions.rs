@@ -4,53 +4,105 @@ use std::path::PathBuf;
 use crate::{
     app::{AppContext, AppState, config::AppConfig},
     file_handling::{
-        Explorer, SearchResult,
+        DiskEntry, Explorer, SearchResult,
+        bulk::{BulkJob, BulkReport},
+        checksum::{Checksum, ChecksumAlgorithm},
+        content_search::LineMatch,
+        diff::FileDiff,
+        dir_compare::{DirComparison, SyncOperation},
+        disk_usage::DiskUsageCache,
+        duplicates::DuplicateResolution,
+        git::GitInfo,
+        index::IndexedRoot,
         metadata::{DirMetadata, FileMetadata},
+        search::SearchQuery,
     },
+    system::FilesystemInfo,
     ui::{Theme, search_widget::SearchMode},
 };
 
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 /// Enum that tracks all the actions that can be carried out by the App
 pub enum Action {
+    AnalyzeDiskUsage(PathBuf, bool),
     ApplyAppSettings(AppConfig),
     ApplyDropDownSelection,
     ApplySettingsInput,
+    BulkJobDone(BulkReport),
+    CancelBulkJob,
+    CancelChecksum,
+    CancelDirCompare,
+    CancelDirMetadata,
+    CancelDiskUsage,
+    CancelSearch,
+    ChecksumDone(Option<Checksum>),
+    ChecksumFailure(String),
+    ChecksumProgress(u64, u64),
     CloseMetadata,
+    CompareDirs(PathBuf, PathBuf, bool),
+    CompareDirsDone(Option<DirComparison>),
+    ComputeChecksum(PathBuf, ChecksumAlgorithm),
+    ComputeDiff(PathBuf, PathBuf),
+    DeleteDone(PathBuf),
+    DeleteFailure(String),
+    DeletePath(PathBuf),
+    DiffDone(FileDiff),
+    DiffFailure(String),
+    DiskUsageDone(Option<DiskUsageCache>),
     DropDownClosed,
     DropDownShowing,
     Error(String),
     ExportDone,
     ExportFailure(String),
+    FilesystemInfoDone(PathBuf, Option<FilesystemInfo>),
     ForcedShutdown,
+    GitStatusDone(Option<GitInfo>),
     HideOrShowSystemOverview,
+    IndexStatus(Vec<IndexedRoot>),
     Init,
     LoadDir(PathBuf, bool),
-    LoadDirDone(Explorer),
+    LoadDirChunk(Vec<DiskEntry>),
+    LoadDirDone,
+    LoadDirStarted(Explorer),
     LoadDirMetadata(String, PathBuf, bool),
     LoadDirMetadataDone(Option<DirMetadata>),
+    LoadFilesystemInfo(PathBuf),
+    LoadGitStatus(PathBuf),
     None,
+    OpenInEditor(PathBuf, Option<usize>),
     Quit,
     Render,
+    ResolveDuplicates(PathBuf, Vec<PathBuf>, DuplicateResolution),
+    ResolveDuplicatesDone(Vec<PathBuf>, Vec<String>),
     Resize(u16, u16),
     Resume,
+    RevealInExplorer(PathBuf),
+    RunBulkJob(BulkJob),
     SettingsInputCanceled,
     SettingsInputShowing,
+    SearchChunk(Vec<DiskEntry>, Vec<LineMatch>),
     SearchDone(Option<SearchResult>),
+    SearchSnapshot(Vec<DiskEntry>),
+    SelectCompareDir(PathBuf, AppContext),
+    SelectDiffFile(PathBuf, AppContext),
     SetCommandDescription(Option<String>),
     ShowAbout(AppContext),
     ShowDirMetadata(DirMetadata),
+    ShowDiskUsage(PathBuf),
     ShowFileMetadata(PathBuf, FileMetadata),
     ShowHelp(AppContext),
     ShowResultsPage(SearchResult, SearchMode),
     ShowSearchPage(PathBuf),
     ShowSettings(AppContext),
-    StartSearch(PathBuf, String, usize, bool),
+    StartSearch(SearchQuery),
     Suspend,
     SwitchAppContext(AppContext),
+    SyncDirs(Vec<SyncOperation>),
+    SyncDirsDone(usize, Vec<String>),
     Tick,
     ToggleTheme(Theme),
     UpdateAppState(AppState),
+    UpdateIndex(Vec<PathBuf>),
 }
 
 // pub enum Action {
@@ -3,8 +3,12 @@ use serde::{Deserialize, Serialize};
 use std::path::{Path, PathBuf};
 
 use crate::{
+    file_handling::walker::ExcludeFilter,
     ui::Theme,
-    utils::{absolute_path_as_string, data_dir, format_path_for_display, user_home_dir},
+    utils::{
+        absolute_path_as_string, data_dir, expand_and_resolve_path, format_path_for_display,
+        user_home_dir,
+    },
 };
 
 pub const CONFIG_NAME: &str = "config.toml";
@@ -19,6 +23,18 @@ pub struct AppConfig {
     export_dir: PathBuf,
     /// Enable/Disable following symbolic links
     follow_sym_links: bool,
+    /// Maximum depth of the Deep search, 0 = unlimited
+    #[serde(default)]
+    deep_search_depth: u8,
+    /// Names, globs or absolute paths of the subtrees, that are skipped by the search
+    #[serde(default)]
+    exclude_patterns: Vec<String>,
+    /// Enable/Disable searching in directories on other filesystems, like mounted drives
+    #[serde(default = "default_cross_filesystems")]
+    cross_filesystems: bool,
+    /// Directories, whose file/directory names are indexed for instant name searches, none = indexing disabled
+    #[serde(default)]
+    indexed_dirs: Vec<PathBuf>,
     /// Update Rate per second for the System-Overview
     system_update_rate: u8,
     /// Frames per Second (Rendering)
@@ -39,12 +55,38 @@ impl Default for AppConfig {
             ),
             export_dir: data_dir(),
             follow_sym_links: false,
+            deep_search_depth: 0,
+            exclude_patterns: vec![],
+            cross_filesystems: default_cross_filesystems(),
+            indexed_dirs: vec![],
             system_update_rate: 1,
             fps: 45,
         }
     }
 }
 
+fn default_cross_filesystems() -> bool {
+    true
+}
+
+/// Splits the comma separated exclude patterns of the settings input
+pub fn parse_exclude_patterns(text: &str) -> Vec<String> {
+    text.split(',')
+        .map(str::trim)
+        .filter(|pattern| !pattern.is_empty())
+        .map(String::from)
+        .collect()
+}
+
+/// Splits the comma separated directories of the settings input, `~` is expanded to the home directory
+pub fn parse_dir_list(text: &str) -> Vec<PathBuf> {
+    text.split(',')
+        .map(str::trim)
+        .filter(|dir| !dir.is_empty())
+        .map(|dir| PathBuf::from(expand_and_resolve_path(dir)))
+        .collect()
+}
+
 impl AppConfig {
     pub fn load_config<P: AsRef<Path>>(p: P) -> AppConfig {
         let config_file = p.as_ref();
@@ -115,6 +157,36 @@ impl AppConfig {
             config.export_dir = export_dir;
         }
 
+        let (valid_patterns, invalid_patterns): (Vec<String>, Vec<String>) = config
+            .exclude_patterns
+            .iter()
+            .cloned()
+            .partition(|pattern| ExcludeFilter::new(std::slice::from_ref(pattern)).is_ok());
+        if !invalid_patterns.is_empty() {
+            log::warn!(
+                "[Config] Invalid patterns for option 'exclude_patterns' -> They are ignored [{}]",
+                invalid_patterns.join(", ")
+            );
+            config.exclude_patterns = valid_patterns;
+        }
+
+        let (valid_dirs, invalid_dirs): (Vec<PathBuf>, Vec<PathBuf>) = config
+            .indexed_dirs
+            .iter()
+            .cloned()
+            .partition(|dir| dir.is_dir());
+        if !invalid_dirs.is_empty() {
+            log::warn!(
+                "[Config] Invalid paths for option 'indexed_dirs' -> No such directories, they are not indexed [{}]",
+                invalid_dirs
+                    .iter()
+                    .map(absolute_path_as_string)
+                    .collect::<Vec<String>>()
+                    .join(", ")
+            );
+            config.indexed_dirs = valid_dirs;
+        }
+
         if config.fps > 60 {
             let default_fps: u8 = 45;
             log::warn!(
@@ -140,6 +212,29 @@ impl AppConfig {
         let start_dir = format_path_for_display(absolute_path_as_string(self.start_dir()));
         let export_dir = format_path_for_display(absolute_path_as_string(self.export_dir()));
         let follow_sym_links = if self.follow_sym_links() { "Yes" } else { "No" };
+        let deep_search_depth = match self.deep_search_depth() {
+            0 => "Unlimited".to_string(),
+            depth => depth.to_string(),
+        };
+        let exclude_patterns = if self.exclude_patterns.is_empty() {
+            "None".to_string()
+        } else {
+            self.exclude_patterns.join(", ")
+        };
+        let cross_filesystems = if self.cross_filesystems() {
+            "Yes"
+        } else {
+            "No"
+        };
+        let indexed_dirs = if self.indexed_dirs.is_empty() {
+            "None".to_string()
+        } else {
+            self.indexed_dirs
+                .iter()
+                .map(format_path_for_display)
+                .collect::<Vec<String>>()
+                .join(", ")
+        };
         let fps = format!("{} / sec", self.fps());
         let update_rate = format!("{} / sec", self.system_update_rate());
 
@@ -164,6 +259,26 @@ impl AppConfig {
                 follow_sym_links.to_string(),
                 "Defines whether Explorer follows symbolic links",
             ),
+            (
+                "Deep search depth",
+                deep_search_depth,
+                "Maximum directory depth of the Deep search",
+            ),
+            (
+                "Exclude patterns",
+                exclude_patterns,
+                "Directories skipped by the search, e.g. node_modules, .git, /proc",
+            ),
+            (
+                "Cross filesystems",
+                cross_filesystems.to_string(),
+                "Defines whether the search enters other filesystems",
+            ),
+            (
+                "Indexed directories",
+                indexed_dirs,
+                "Directories indexed for instant name searches, updated with F5 on the Search page",
+            ),
             (
                 "Frames per second (render)",
                 fps,
@@ -203,6 +318,22 @@ impl AppConfig {
         self.follow_sym_links = yes;
     }
 
+    pub fn set_deep_search_depth(&mut self, depth: u8) {
+        self.deep_search_depth = depth;
+    }
+
+    pub fn set_exclude_patterns(&mut self, patterns: Vec<String>) {
+        self.exclude_patterns = patterns;
+    }
+
+    pub fn set_cross_filesystems(&mut self, yes: bool) {
+        self.cross_filesystems = yes;
+    }
+
+    pub fn set_indexed_dirs(&mut self, dirs: Vec<PathBuf>) {
+        self.indexed_dirs = dirs;
+    }
+
     pub fn set_system_update_rate(&mut self, rate: u8) {
         self.system_update_rate = rate;
     }
@@ -227,6 +358,22 @@ impl AppConfig {
         self.follow_sym_links
     }
 
+    pub fn deep_search_depth(&self) -> u8 {
+        self.deep_search_depth
+    }
+
+    pub fn exclude_patterns(&self) -> Vec<String> {
+        self.exclude_patterns.clone()
+    }
+
+    pub fn cross_filesystems(&self) -> bool {
+        self.cross_filesystems
+    }
+
+    pub fn indexed_dirs(&self) -> Vec<PathBuf> {
+        self.indexed_dirs.clone()
+    }
+
     pub fn system_update_rate(&self) -> u8 {
         self.system_update_rate
     }
@@ -235,3 +382,37 @@ impl AppConfig {
         self.fps
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{AppConfig, parse_exclude_patterns};
+
+    #[test]
+    fn test_config_without_search_options_keeps_the_defaults() {
+        let dir = tempfile::tempdir().unwrap();
+        let config_file = dir.path().join("config.toml");
+        std::fs::write(
+            &config_file,
+            "default_theme = \"Dark\"\nstart_dir = \"/\"\nexport_dir = \"/\"\nfollow_sym_links = true\nsystem_update_rate = 2\nfps = 30\n",
+        )
+        .unwrap();
+
+        let config = AppConfig::load_config(&config_file);
+        // the existing options are still applied
+        assert!(config.follow_sym_links());
+        assert_eq!(config.fps(), 30);
+        assert_eq!(config.deep_search_depth(), 0);
+        assert!(config.exclude_patterns().is_empty());
+        assert!(config.cross_filesystems());
+        assert!(config.indexed_dirs().is_empty());
+    }
+
+    #[test]
+    fn test_parse_exclude_patterns() {
+        assert_eq!(
+            parse_exclude_patterns(" node_modules, .git,,/proc "),
+            vec!["node_modules", ".git", "/proc"]
+        );
+        assert!(parse_exclude_patterns(" , ").is_empty());
+    }
+}
@@ -0,0 +1,145 @@
+use anyhow::Result;
+use std::path::Path;
+
+/// Maximum number of queries that are kept in the search history
+pub const MAX_HISTORY_ENTRIES: usize = 500;
+
+/// History of the submitted search queries, ordered from the oldest to the most recent query.<br>
+/// Every query is only stored once, submitting it again moves it to the end.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct SearchHistory {
+    entries: Vec<String>,
+}
+
+impl SearchHistory {
+    /// Loads the history from the given file.<br>
+    /// A missing or unreadable file results in an empty history, so a broken file never prevents the search
+    pub fn load<P: AsRef<Path>>(path: P) -> Self {
+        let path = path.as_ref();
+        let content = match std::fs::read_to_string(path) {
+            Ok(content) => content,
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => return Self::default(),
+            Err(err) => {
+                log::warn!(
+                    "Failed to read the search history [{}]: {err}",
+                    path.display()
+                );
+                return Self::default();
+            }
+        };
+
+        match serde_json::from_str::<Vec<String>>(&content) {
+            Ok(entries) => {
+                let mut history = Self::default();
+                entries.iter().for_each(|entry| history.push(entry));
+                history
+            }
+            Err(err) => {
+                log::warn!("Invalid search history [{}]: {err}", path.display());
+                Self::default()
+            }
+        }
+    }
+
+    pub fn save<P: AsRef<Path>>(&self, path: P) -> Result<()> {
+        std::fs::write(path, serde_json::to_string_pretty(&self.entries)?)?;
+        Ok(())
+    }
+
+    /// Adds the query as most recent entry, an already existing entry is moved to the end.<br>
+    /// The oldest entries are dropped, when the history exceeds [`MAX_HISTORY_ENTRIES`]
+    pub fn push(&mut self, query: &str) {
+        if query.trim().is_empty() {
+            return;
+        }
+        self.entries.retain(|entry| entry != query);
+        self.entries.push(query.to_string());
+
+        if self.entries.len() > MAX_HISTORY_ENTRIES {
+            let overflow = self.entries.len() - MAX_HISTORY_ENTRIES;
+            self.entries.drain(..overflow);
+        }
+    }
+
+    pub fn get(&self, index: usize) -> Option<&str> {
+        self.entries.get(index).map(String::as_str)
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Searches backwards (towards older entries) for an entry containing the text.
+    ///
+    /// # Arguments
+    /// * `text` - The text the entry must contain, an empty text matches every entry
+    /// * `before` - Only entries with a smaller index are searched, use [`SearchHistory::len`] to start with the most recent entry
+    ///
+    /// # Returns
+    /// The index of the most recent matching entry
+    pub fn rfind(&self, text: &str, before: usize) -> Option<usize> {
+        self.entries
+            .iter()
+            .take(before)
+            .rposition(|entry| entry.contains(text))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{MAX_HISTORY_ENTRIES, SearchHistory};
+
+    fn history(entries: &[&str]) -> SearchHistory {
+        let mut history = SearchHistory::default();
+        entries.iter().for_each(|entry| history.push(entry));
+        history
+    }
+
+    #[test]
+    fn test_push_deduplicates_by_recency() {
+        let history = history(&["main.rs", "  ", "Cargo.toml", "main.rs"]);
+        assert_eq!(history.len(), 2);
+        assert_eq!(history.get(0), Some("Cargo.toml"));
+        assert_eq!(history.get(1), Some("main.rs"));
+    }
+
+    #[test]
+    fn test_push_drops_the_oldest_entries() {
+        let mut history = SearchHistory::default();
+        (0..MAX_HISTORY_ENTRIES + 10).for_each(|i| history.push(&format!("query {i}")));
+        assert_eq!(history.len(), MAX_HISTORY_ENTRIES);
+        assert_eq!(history.get(0), Some("query 10"));
+        assert_eq!(
+            history.get(MAX_HISTORY_ENTRIES - 1),
+            Some(format!("query {}", MAX_HISTORY_ENTRIES + 9).as_str())
+        );
+    }
+
+    #[test]
+    fn test_rfind_searches_towards_older_entries() {
+        let history = history(&["ext:rs", "todo", "name:*.rs", "readme"]);
+        assert_eq!(history.rfind("rs", history.len()), Some(2));
+        assert_eq!(history.rfind("rs", 2), Some(0));
+        assert_eq!(history.rfind("rs", 0), None);
+        assert_eq!(history.rfind("", history.len()), Some(3));
+        assert_eq!(history.rfind("missing", history.len()), None);
+    }
+
+    #[test]
+    fn test_save_and_load() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("search_history.json");
+        let saved = history(&["todo", "size:>1M"]);
+        saved.save(&path).unwrap();
+        assert_eq!(SearchHistory::load(&path), saved);
+
+        std::fs::write(&path, "no json").unwrap();
+        assert!(SearchHistory::load(&path).is_empty());
+        std::fs::remove_file(&path).unwrap();
+        assert!(SearchHistory::load(&path).is_empty());
+    }
+}
@@ -21,6 +21,7 @@ pub enum Keys {
     Char(char),
     AnyChar,
     Tab,
+    BackTab,
 }
 
 impl std::fmt::Display for Keys {
@@ -41,9 +42,11 @@ impl std::fmt::Display for Keys {
             Keys::Right => write!(f, "Right Arrow"),
             Keys::PageUp => write!(f, "PageUp"),
             Keys::PageDown => write!(f, "PageDown"),
+            Keys::Char(' ') => write!(f, "Space"),
             Keys::Char(c) => write!(f, "{}", c),
             Keys::AnyChar => write!(f, "Any Char"),
             Keys::Tab => write!(f, "Tab"),
+            Keys::BackTab => write!(f, "Tab"),
         }
     }
 }
@@ -114,6 +117,7 @@ impl KeyStroke {
             | (Keys::PageUp, crossterm::event::KeyCode::PageUp)
             | (Keys::PageDown, crossterm::event::KeyCode::PageDown) => true,
             (Keys::Tab, crossterm::event::KeyCode::Tab) => true,
+            (Keys::BackTab, crossterm::event::KeyCode::BackTab) => true,
             _ => false,
         };
 
@@ -149,7 +153,7 @@ pub struct KeyBinding {
     command_desc: Option<&'static [CommandDesc]>,
 }
 
-pub const DEFAULT_KEY_BINDING: [KeyBinding; 24] = [
+pub const DEFAULT_KEY_BINDING: [KeyBinding; 43] = [
     KeyBinding {
         key_stroke: KeyStroke::new(Keys::F1, crossterm::event::KeyModifiers::NONE),
         alt: None,
@@ -161,6 +165,9 @@ pub const DEFAULT_KEY_BINDING: [KeyBinding; 24] = [
                 AppContext::Explorer,
                 AppContext::Search,
                 AppContext::Results,
+                AppContext::DiskUsage,
+                AppContext::Diff,
+                AppContext::DirCompare,
             ],
         }]),
     },
@@ -175,6 +182,9 @@ pub const DEFAULT_KEY_BINDING: [KeyBinding; 24] = [
                 AppContext::Explorer,
                 AppContext::Search,
                 AppContext::Results,
+                AppContext::DiskUsage,
+                AppContext::Diff,
+                AppContext::DirCompare,
             ],
         }]),
     },
@@ -189,18 +199,36 @@ pub const DEFAULT_KEY_BINDING: [KeyBinding; 24] = [
                 AppContext::Explorer,
                 AppContext::Search,
                 AppContext::Results,
+                AppContext::DiskUsage,
+                AppContext::Diff,
+                AppContext::DirCompare,
             ],
         }]),
     },
     KeyBinding {
         key_stroke: KeyStroke::new(Keys::F5, crossterm::event::KeyModifiers::NONE),
         alt: None,
-        help_desc: "Refresh the current working directory",
-        help_contexts: &[AppContext::Explorer],
-        command_desc: Some(&[CommandDesc {
-            desc: "Refresh dir",
-            contexts: &[AppContext::Explorer],
-        }]),
+        help_desc: "Refresh the current working directory and its git status | Update the index of the indexed directories | Analyze the directory again",
+        help_contexts: &[
+            AppContext::Explorer,
+            AppContext::Search,
+            AppContext::DiskUsage,
+            AppContext::DirCompare,
+        ],
+        command_desc: Some(&[
+            CommandDesc {
+                desc: "Refresh dir",
+                contexts: &[
+                    AppContext::Explorer,
+                    AppContext::DiskUsage,
+                    AppContext::DirCompare,
+                ],
+            },
+            CommandDesc {
+                desc: "Reindex",
+                contexts: &[AppContext::Search],
+            },
+        ]),
     },
     KeyBinding {
         key_stroke: KeyStroke::new(Keys::F12, crossterm::event::KeyModifiers::NONE),
@@ -215,28 +243,41 @@ pub const DEFAULT_KEY_BINDING: [KeyBinding; 24] = [
     KeyBinding {
         key_stroke: KeyStroke::new(Keys::Enter, crossterm::event::KeyModifiers::NONE),
         alt: None,
-        help_desc: "Open directory, if any | Submit search",
-        help_contexts: &[AppContext::Explorer, AppContext::Search],
+        help_desc: "Open directory, if any | Submit search | Open the selected file in the editor (at the matching line), collapse/expand a directory header",
+        help_contexts: &[
+            AppContext::Explorer,
+            AppContext::Search,
+            AppContext::Results,
+            AppContext::DiskUsage,
+        ],
         command_desc: Some(&[
             CommandDesc {
                 desc: "Change dir",
-                contexts: &[AppContext::Explorer],
+                contexts: &[AppContext::Explorer, AppContext::DiskUsage],
             },
             CommandDesc {
                 desc: "Submit search",
                 contexts: &[AppContext::Search],
             },
+            CommandDesc {
+                desc: "Open in editor",
+                contexts: &[AppContext::Results],
+            },
         ]),
     },
     KeyBinding {
         key_stroke: KeyStroke::new(Keys::Backspace, crossterm::event::KeyModifiers::NONE),
         alt: None,
         help_desc: "Go to parent directory, if any | To delete search input",
-        help_contexts: &[AppContext::Explorer, AppContext::Search],
+        help_contexts: &[
+            AppContext::Explorer,
+            AppContext::Search,
+            AppContext::DiskUsage,
+        ],
         command_desc: Some(&[
             CommandDesc {
                 desc: "Change dir",
-                contexts: &[AppContext::Explorer],
+                contexts: &[AppContext::Explorer, AppContext::DiskUsage],
             },
             CommandDesc {
                 desc: " ",
@@ -247,20 +288,54 @@ pub const DEFAULT_KEY_BINDING: [KeyBinding; 24] = [
     KeyBinding {
         key_stroke: KeyStroke::new(Keys::Delete, crossterm::event::KeyModifiers::NONE),
         alt: None,
-        help_desc: "To delete search input",
-        help_contexts: &[AppContext::Search],
-        command_desc: Some(&[CommandDesc {
-            desc: " ",
-            contexts: &[AppContext::Search],
-        }]),
+        help_desc: "To delete search input | Delete the selected file/directory permanently (with confirmation) | Keep the selected duplicate and move the other copies to the trash (with confirmation)",
+        help_contexts: &[
+            AppContext::Search,
+            AppContext::DiskUsage,
+            AppContext::Results,
+        ],
+        command_desc: Some(&[
+            CommandDesc {
+                desc: " ",
+                contexts: &[AppContext::Search],
+            },
+            CommandDesc {
+                desc: "Delete",
+                contexts: &[AppContext::DiskUsage],
+            },
+            CommandDesc {
+                desc: "Trash other copies",
+                contexts: &[AppContext::Results],
+            },
+        ]),
     },
     KeyBinding {
         key_stroke: KeyStroke::new(Keys::Tab, crossterm::event::KeyModifiers::NONE),
         alt: None,
-        help_desc: "Switch Search-Mode [Flat, Deep]",
+        help_desc: "Switch Search-Mode [Flat, Deep] | Switch Diff-Mode [Unified, Side-by-Side] | Switch Dir-Compare filter",
+        help_contexts: &[AppContext::Search, AppContext::Diff, AppContext::DirCompare],
+        command_desc: Some(&[
+            CommandDesc {
+                desc: "Switch Search-Mode",
+                contexts: &[AppContext::Search],
+            },
+            CommandDesc {
+                desc: "Switch Diff-Mode",
+                contexts: &[AppContext::Diff],
+            },
+            CommandDesc {
+                desc: "Switch filter",
+                contexts: &[AppContext::DirCompare],
+            },
+        ]),
+    },
+    KeyBinding {
+        key_stroke: KeyStroke::new(Keys::BackTab, crossterm::event::KeyModifiers::SHIFT),
+        alt: None,
+        help_desc: "Switch Search-Type [Name, Content, Format, Duplicates, Largest Files, Recently Modified, Empty, Broken Symlinks]",
         help_contexts: &[AppContext::Search],
         command_desc: Some(&[CommandDesc {
-            desc: "Switch Search-Mode",
+            desc: "Switch Search-Type",
             contexts: &[AppContext::Search],
         }]),
     },
@@ -282,6 +357,9 @@ pub const DEFAULT_KEY_BINDING: [KeyBinding; 24] = [
                 AppContext::Explorer,
                 AppContext::Search,
                 AppContext::Results,
+                AppContext::DiskUsage,
+                AppContext::Diff,
+                AppContext::DirCompare,
             ],
         }]),
     },
@@ -296,6 +374,9 @@ pub const DEFAULT_KEY_BINDING: [KeyBinding; 24] = [
                 AppContext::Explorer,
                 AppContext::Search,
                 AppContext::Results,
+                AppContext::DiskUsage,
+                AppContext::Diff,
+                AppContext::DirCompare,
             ],
         }]),
     },
@@ -319,16 +400,106 @@ pub const DEFAULT_KEY_BINDING: [KeyBinding; 24] = [
             contexts: &[AppContext::Search],
         }]),
     },
+    KeyBinding {
+        key_stroke: KeyStroke::new(Keys::Char('G'), crossterm::event::KeyModifiers::CONTROL),
+        alt: None,
+        help_desc: "Switch Query-Mode [Plain, Regex, Glob, Fuzzy]",
+        help_contexts: &[AppContext::Search],
+        command_desc: Some(&[CommandDesc {
+            desc: "Switch Query-Mode",
+            contexts: &[AppContext::Search],
+        }]),
+    },
+    KeyBinding {
+        key_stroke: KeyStroke::new(Keys::Char('S'), crossterm::event::KeyModifiers::CONTROL),
+        alt: None,
+        help_desc: "Enable/Disable case sensitive matching of the search query",
+        help_contexts: &[AppContext::Search],
+        command_desc: Some(&[CommandDesc {
+            desc: "Toggle case sensitivity",
+            contexts: &[AppContext::Search],
+        }]),
+    },
+    KeyBinding {
+        key_stroke: KeyStroke::new(Keys::Char('R'), crossterm::event::KeyModifiers::CONTROL),
+        alt: None,
+        help_desc: "Reverse search through the history of previous queries, press again for an older match | Reveal the selected result in the Explorer | Return to the results after revealing one of them",
+        help_contexts: &[
+            AppContext::Search,
+            AppContext::Results,
+            AppContext::Explorer,
+        ],
+        command_desc: Some(&[
+            CommandDesc {
+                desc: "Search history",
+                contexts: &[AppContext::Search],
+            },
+            CommandDesc {
+                desc: "Reveal in Explorer",
+                contexts: &[AppContext::Results],
+            },
+            CommandDesc {
+                desc: "Back to results",
+                contexts: &[AppContext::Explorer],
+            },
+        ]),
+    },
+    KeyBinding {
+        key_stroke: KeyStroke::new(Keys::Char('P'), crossterm::event::KeyModifiers::CONTROL),
+        alt: None,
+        help_desc: "Show the saved searches, Enter re-runs the selected search and Tab loads it for editing",
+        help_contexts: &[AppContext::Search],
+        command_desc: Some(&[CommandDesc {
+            desc: "Show saved searches",
+            contexts: &[AppContext::Search],
+        }]),
+    },
+    KeyBinding {
+        key_stroke: KeyStroke::new(Keys::Char('N'), crossterm::event::KeyModifiers::CONTROL),
+        alt: None,
+        help_desc: "Save the current query with its mode, type, directory and depth under a name",
+        help_contexts: &[AppContext::Search],
+        command_desc: Some(&[CommandDesc {
+            desc: "Save search",
+            contexts: &[AppContext::Search],
+        }]),
+    },
     KeyBinding {
         key_stroke: KeyStroke::new(Keys::Char('F'), crossterm::event::KeyModifiers::CONTROL),
         alt: None,
-        help_desc: "Open search to search the current directory for file/directory names",
+        help_desc: "Open search to search the current directory for file/directory names | Refine the search results with a follow-up query",
+        help_contexts: &[AppContext::Explorer, AppContext::Results],
+        command_desc: Some(&[
+            CommandDesc {
+                desc: "Open search",
+                contexts: &[AppContext::Explorer],
+            },
+            CommandDesc {
+                desc: "Refine results",
+                contexts: &[AppContext::Results],
+            },
+        ]),
+    },
+    KeyBinding {
+        key_stroke: KeyStroke::new(Keys::Char('D'), crossterm::event::KeyModifiers::CONTROL),
+        alt: None,
+        help_desc: "Analyze the disk usage of the current directory",
         help_contexts: &[AppContext::Explorer],
         command_desc: Some(&[CommandDesc {
-            desc: "Open search",
+            desc: "Analyze disk usage",
             contexts: &[AppContext::Explorer],
         }]),
     },
+    KeyBinding {
+        key_stroke: KeyStroke::new(Keys::Char('L'), crossterm::event::KeyModifiers::CONTROL),
+        alt: None,
+        help_desc: "Keep the selected duplicate and replace the other copies with hard links (with confirmation)",
+        help_contexts: &[AppContext::Results],
+        command_desc: Some(&[CommandDesc {
+            desc: "Hard-link other copies",
+            contexts: &[AppContext::Results],
+        }]),
+    },
     KeyBinding {
         key_stroke: KeyStroke::new(Keys::Char('U'), crossterm::event::KeyModifiers::CONTROL),
         alt: None,
@@ -342,13 +513,115 @@ pub const DEFAULT_KEY_BINDING: [KeyBinding; 24] = [
     KeyBinding {
         key_stroke: KeyStroke::new(Keys::Char('A'), crossterm::event::KeyModifiers::CONTROL),
         alt: None,
-        help_desc: "Show metadata for a file or directory, if any",
+        help_desc: "Show metadata for a file or directory, if any | Compute and verify checksums of a file",
         help_contexts: &[AppContext::Explorer, AppContext::Results],
         command_desc: Some(&[CommandDesc {
             desc: "Show metadata",
             contexts: &[AppContext::Explorer, AppContext::Results],
         }]),
     },
+    KeyBinding {
+        key_stroke: KeyStroke::new(Keys::Char('E'), crossterm::event::KeyModifiers::CONTROL),
+        alt: None,
+        help_desc: "Mark a file or directory for comparison | Compare the selected file or directory with the marked one",
+        help_contexts: &[AppContext::Explorer, AppContext::Results],
+        command_desc: Some(&[CommandDesc {
+            desc: "Compare",
+            contexts: &[AppContext::Explorer, AppContext::Results],
+        }]),
+    },
+    KeyBinding {
+        key_stroke: KeyStroke::new(Keys::Char('B'), crossterm::event::KeyModifiers::CONTROL),
+        alt: None,
+        help_desc: "Copy, move, trash, delete or archive the marked results (or the selected one)",
+        help_contexts: &[AppContext::Results],
+        command_desc: Some(&[CommandDesc {
+            desc: "Bulk operations",
+            contexts: &[AppContext::Results],
+        }]),
+    },
+    KeyBinding {
+        key_stroke: KeyStroke::new(Keys::Char('s'), crossterm::event::KeyModifiers::NONE),
+        alt: None,
+        help_desc: "Sort the results by the next column: name, path, size, modified time, format or the search order",
+        help_contexts: &[AppContext::Results],
+        command_desc: Some(&[CommandDesc {
+            desc: "Sort",
+            contexts: &[AppContext::Results],
+        }]),
+    },
+    KeyBinding {
+        key_stroke: KeyStroke::new(Keys::Char('r'), crossterm::event::KeyModifiers::NONE),
+        alt: None,
+        help_desc: "Reverse the sort order of the results",
+        help_contexts: &[AppContext::Results],
+        command_desc: Some(&[CommandDesc {
+            desc: "Reverse order",
+            contexts: &[AppContext::Results],
+        }]),
+    },
+    KeyBinding {
+        key_stroke: KeyStroke::new(Keys::Char('g'), crossterm::event::KeyModifiers::NONE),
+        alt: None,
+        help_desc: "Group the results by directory with collapsible headers, or show them as one list again",
+        help_contexts: &[AppContext::Results],
+        command_desc: Some(&[CommandDesc {
+            desc: "Group by directory",
+            contexts: &[AppContext::Results],
+        }]),
+    },
+    KeyBinding {
+        key_stroke: KeyStroke::new(Keys::Char('n'), crossterm::event::KeyModifiers::NONE),
+        alt: None,
+        help_desc: "Jump to the next hunk",
+        help_contexts: &[AppContext::Diff],
+        command_desc: Some(&[CommandDesc {
+            desc: "Next hunk",
+            contexts: &[AppContext::Diff],
+        }]),
+    },
+    KeyBinding {
+        key_stroke: KeyStroke::new(Keys::Char('p'), crossterm::event::KeyModifiers::NONE),
+        alt: None,
+        help_desc: "Jump to the previous hunk",
+        help_contexts: &[AppContext::Diff],
+        command_desc: Some(&[CommandDesc {
+            desc: "Previous hunk",
+            contexts: &[AppContext::Diff],
+        }]),
+    },
+    KeyBinding {
+        key_stroke: KeyStroke::new(Keys::Char(' '), crossterm::event::KeyModifiers::NONE),
+        alt: None,
+        help_desc: "Select/Unselect the current difference for synchronization | Mark/Unmark the selected result for a bulk operation",
+        help_contexts: &[AppContext::DirCompare, AppContext::Results],
+        command_desc: Some(&[
+            CommandDesc {
+                desc: "Select",
+                contexts: &[AppContext::DirCompare],
+            },
+            CommandDesc {
+                desc: "Mark",
+                contexts: &[AppContext::Results],
+            },
+        ]),
+    },
+    KeyBinding {
+        key_stroke: KeyStroke::new(Keys::Char('a'), crossterm::event::KeyModifiers::NONE),
+        alt: None,
+        help_desc: "Select/Unselect all visible differences for synchronization | Mark/Unmark all shown results",
+        help_contexts: &[AppContext::DirCompare, AppContext::Results],
+        command_desc: Some(&[
+            CommandDesc {
+                desc: "Select all",
+                contexts: &[AppContext::DirCompare],
+            },
+            CommandDesc {
+                desc: "Mark all",
+                contexts: &[AppContext::Results],
+            },
+        ]),
+    },
     KeyBinding {
         key_stroke: KeyStroke::new(Keys::AnyChar, crossterm::event::KeyModifiers::NONE),
         alt: None,
@@ -367,6 +640,9 @@ pub const DEFAULT_KEY_BINDING: [KeyBinding; 24] = [
             AppContext::Explorer,
             AppContext::Results,
             AppContext::Search,
+            AppContext::DiskUsage,
+            AppContext::Diff,
+            AppContext::DirCompare,
         ],
         command_desc: Some(&[CommandDesc {
             desc: " ",
@@ -374,6 +650,9 @@ pub const DEFAULT_KEY_BINDING: [KeyBinding; 24] = [
                 AppContext::Explorer,
                 AppContext::Results,
                 AppContext::Search,
+                AppContext::DiskUsage,
+                AppContext::Diff,
+                AppContext::DirCompare,
             ],
         }]),
     },
@@ -385,6 +664,9 @@ pub const DEFAULT_KEY_BINDING: [KeyBinding; 24] = [
             AppContext::Explorer,
             AppContext::Results,
             AppContext::Search,
+            AppContext::DiskUsage,
+            AppContext::Diff,
+            AppContext::DirCompare,
         ],
         command_desc: Some(&[CommandDesc {
             desc: " ",
@@ -392,47 +674,122 @@ pub const DEFAULT_KEY_BINDING: [KeyBinding; 24] = [
                 AppContext::Explorer,
                 AppContext::Results,
                 AppContext::Search,
+                AppContext::DiskUsage,
+                AppContext::Diff,
+                AppContext::DirCompare,
             ],
         }]),
     },
     KeyBinding {
         key_stroke: KeyStroke::new(Keys::Left, crossterm::event::KeyModifiers::NONE),
         alt: None,
-        help_desc: "Move the cursor to the left in the input field",
-        help_contexts: &[AppContext::Search],
+        help_desc: "Move the cursor to the left in the input field | Copy the selected differences to the left directory, after a dry-run preview | Collapse the directory of the selected result, if grouped by directory",
+        help_contexts: &[
+            AppContext::Search,
+            AppContext::DirCompare,
+            AppContext::Results,
+        ],
+        command_desc: Some(&[
+            CommandDesc {
+                desc: " ",
+                contexts: &[AppContext::Search],
+            },
+            CommandDesc {
+                desc: "Copy to the left (dry run)",
+                contexts: &[AppContext::DirCompare],
+            },
+            CommandDesc {
+                desc: "Collapse directory",
+                contexts: &[AppContext::Results],
+            },
+        ]),
+    },
+    KeyBinding {
+        key_stroke: KeyStroke::new(Keys::Right, crossterm::event::KeyModifiers::NONE),
+        alt: None,
+        help_desc: "Move the cursor to the right in the input field | Copy the selected differences to the right directory, after a dry-run preview | Expand the selected directory, if grouped by directory",
+        help_contexts: &[
+            AppContext::Search,
+            AppContext::DirCompare,
+            AppContext::Results,
+        ],
+        command_desc: Some(&[
+            CommandDesc {
+                desc: " ",
+                contexts: &[AppContext::Search],
+            },
+            CommandDesc {
+                desc: "Copy to the right (dry run)",
+                contexts: &[AppContext::DirCompare],
+            },
+            CommandDesc {
+                desc: "Expand directory",
+                contexts: &[AppContext::Results],
+            },
+        ]),
+    },
+    KeyBinding {
+        key_stroke: KeyStroke::new(Keys::Left, crossterm::event::KeyModifiers::ALT),
+        alt: None,
+        help_desc: "Show the previous, broader search results after refining them",
+        help_contexts: &[AppContext::Results],
         command_desc: Some(&[CommandDesc {
-            desc: " ",
-            contexts: &[AppContext::Search],
+            desc: "Previous results",
+            contexts: &[AppContext::Results],
         }]),
     },
     KeyBinding {
-        key_stroke: KeyStroke::new(Keys::Right, crossterm::event::KeyModifiers::NONE),
+        key_stroke: KeyStroke::new(Keys::Right, crossterm::event::KeyModifiers::ALT),
         alt: None,
-        help_desc: "Move the cursor to the right in the input field",
-        help_contexts: &[AppContext::Search],
+        help_desc: "Show the next, refined search results again",
+        help_contexts: &[AppContext::Results],
         command_desc: Some(&[CommandDesc {
-            desc: " ",
-            contexts: &[AppContext::Search],
+            desc: "Next results",
+            contexts: &[AppContext::Results],
         }]),
     },
     KeyBinding {
         key_stroke: KeyStroke::new(Keys::PageUp, crossterm::event::KeyModifiers::NONE),
         alt: None,
         help_desc: "Move page up until the first item is reached",
-        help_contexts: &[AppContext::Explorer, AppContext::Results],
+        help_contexts: &[
+            AppContext::Explorer,
+            AppContext::Results,
+            AppContext::DiskUsage,
+            AppContext::Diff,
+            AppContext::DirCompare,
+        ],
         command_desc: Some(&[CommandDesc {
             desc: " ",
-            contexts: &[AppContext::Explorer, AppContext::Results],
+            contexts: &[
+                AppContext::Explorer,
+                AppContext::Results,
+                AppContext::DiskUsage,
+                AppContext::Diff,
+                AppContext::DirCompare,
+            ],
         }]),
     },
     KeyBinding {
         key_stroke: KeyStroke::new(Keys::PageDown, crossterm::event::KeyModifiers::NONE),
         alt: None,
         help_desc: "Move page down until the last item is reached",
-        help_contexts: &[AppContext::Explorer, AppContext::Results],
+        help_contexts: &[
+            AppContext::Explorer,
+            AppContext::Results,
+            AppContext::DiskUsage,
+            AppContext::Diff,
+            AppContext::DirCompare,
+        ],
         command_desc: Some(&[CommandDesc {
             desc: " ",
-            contexts: &[AppContext::Explorer, AppContext::Results],
+            contexts: &[
+                AppContext::Explorer,
+                AppContext::Results,
+                AppContext::DiskUsage,
+                AppContext::Diff,
+                AppContext::DirCompare,
+            ],
         }]),
     },
 ];
@@ -13,16 +13,20 @@ use crate::{
     file_handling::ExplorerTask,
     tui,
     ui::{
-        about_widget::AboutPage, explorer_widget::ExplorerWidget, footer_widget::Footer,
+        about_widget::AboutPage, diff_widget::DiffWidget, dir_compare_widget::DirCompareWidget,
+        disk_usage_widget::DiskUsageWidget, explorer_widget::ExplorerWidget, footer_widget::Footer,
         help_widget::HelpPage, info_widget::SystemOverview, metadata_widget::MetadataPage,
         result_widget::ResultWidget, search_widget::SearchWidget, settings_widget::SettingsPage,
         title_widget::TitleBar,
     },
+    utils,
 };
 
 pub mod actions;
 pub mod config;
+pub mod history;
 pub mod key_bindings;
+pub mod presets;
 
 pub const APP_NAME: &str = env!("CARGO_CRATE_NAME");
 pub const GRACEFUL_SHUTDOWN_MSG: &str = "Graceful shutdown... success";
@@ -38,6 +42,12 @@ pub enum AppContext {
     Search,
     /// Result page called from the Search page
     Results,
+    /// Disk-Usage page called from the Explorer, to analyze the size of the directory content
+    DiskUsage,
+    /// Diff page called from the Explorer or Result page, to compare two files
+    Diff,
+    /// Dir-Compare page called from the Explorer or Result page, to compare and synchronize two directories
+    DirCompare,
     /// Helper context for the Help-Page => describes possible contexts
     All,
     NotActive,
@@ -49,6 +59,9 @@ impl std::fmt::Display for AppContext {
             AppContext::Explorer => write!(f, "Explorer"),
             AppContext::Search => write!(f, "Search"),
             AppContext::Results => write!(f, "Result"),
+            AppContext::DiskUsage => write!(f, "Disk-Usage"),
+            AppContext::Diff => write!(f, "Diff"),
+            AppContext::DirCompare => write!(f, "Dir-Compare"),
             AppContext::All => write!(f, "All Contexts"),
             AppContext::NotActive => write!(f, ""),
         }
@@ -97,6 +110,9 @@ impl App {
             ExplorerWidget::new(config.start_dir().clone(), config.follow_sym_links());
         let search_widget = SearchWidget::default();
         let result_widget = ResultWidget::default();
+        let disk_usage_widget = DiskUsageWidget::default();
+        let diff_widget = DiffWidget::default();
+        let dir_compare_widget = DirCompareWidget::default();
         let footer = Footer::default();
         let help_page = HelpPage::default();
         let about_page = AboutPage::default();
@@ -112,6 +128,9 @@ impl App {
                 Box::new(file_explorer),
                 Box::new(search_widget),
                 Box::new(result_widget),
+                Box::new(disk_usage_widget),
+                Box::new(diff_widget),
+                Box::new(dir_compare_widget),
                 Box::new(footer),
                 Box::new(help_page),
                 Box::new(about_page),
@@ -166,6 +185,13 @@ impl App {
             component.register_explorer_action_sender(explorer_sender.clone())?;
         }
 
+        // Brings the index up to date with the changes made while the app was not running
+        if !self.config.indexed_dirs().is_empty() {
+            explorer_sender
+                .send(Action::UpdateIndex(self.config.indexed_dirs()))
+                .await?;
+        }
+
         // This is the Application main loop
         loop {
             // Try to receive some TUI-Events
@@ -208,9 +234,29 @@ impl App {
                             tui.frame_rate(c.fps() as f64);
                             tui.start();
                         }
+                        // Index the added directories and remove the index of the removed directories
+                        if c.indexed_dirs() != self.config.indexed_dirs() {
+                            explorer_sender
+                                .send(Action::UpdateIndex(c.indexed_dirs()))
+                                .await?;
+                        }
                         self.config = c.clone();
                     }
                     Action::ForcedShutdown => self.is_forced_shutdown = true,
+                    Action::OpenInEditor(path, line) => {
+                        // the editor takes over the terminal, until it is closed
+                        tui.exit()?;
+                        let result = utils::open_in_editor(path, *line);
+                        tui.enter()?;
+                        tui.clear()?;
+
+                        if let Err(err) = result {
+                            log::error!("{:?}", err);
+                            component_tx.send(Action::UpdateAppState(AppState::Failure(
+                                format!("{err}"),
+                            )))?;
+                        }
+                    }
                     Action::Quit => self.should_quit = true,
                     // draw to the screen buffer only if Action::Render or Action::Resize will received
                     Action::Render => {
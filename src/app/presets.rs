@@ -0,0 +1,152 @@
+use anyhow::{Context, Result, bail};
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+
+use crate::{
+    file_handling::search::{QueryMode, SearchType},
+    ui::search_widget::SearchMode,
+    utils::expand_and_resolve_path,
+};
+
+/// A search saved under a name, to re-run it later from the Search page.<br>
+/// Every preset is stored in its own file, so that preset files can be shared.
+#[derive(Debug, Default, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SearchPreset {
+    pub name: String,
+    /// The search query together with its filters, e.g. `*.log modified:>30d`
+    pub query: String,
+    pub search_type: SearchType,
+    pub query_mode: QueryMode,
+    pub case_sensitive: bool,
+    pub mode: SearchMode,
+    /// Maximum depth of the Deep search, 0 = unlimited
+    pub depth: u8,
+    /// Directory in which to search, a leading `~` is expanded to the home directory.
+    /// The current directory of the Search page is used, if empty
+    pub root: String,
+}
+
+impl SearchPreset {
+    /// The file name of the preset, derived from its name
+    pub fn file_name(&self) -> String {
+        let stem: String = self
+            .name
+            .trim()
+            .chars()
+            .map(|c| {
+                if c.is_alphanumeric() || c == '-' || c == '_' {
+                    c.to_ascii_lowercase()
+                } else {
+                    '_'
+                }
+            })
+            .collect();
+        format!("{stem}.toml")
+    }
+
+    /// Returns the resolved root directory, or `None` if the preset searches the current directory
+    pub fn root_dir(&self) -> Option<PathBuf> {
+        (!self.root.trim().is_empty())
+            .then(|| PathBuf::from(expand_and_resolve_path(self.root.trim())))
+    }
+
+    /// Saves the preset into the given directory, an existing preset with the same name is replaced
+    ///
+    /// # Returns
+    /// The path of the preset file
+    pub fn save<P: AsRef<Path>>(&self, dir: P) -> Result<PathBuf> {
+        if self.name.trim().is_empty() {
+            bail!("The name of a saved search must not be empty");
+        }
+        let dir = dir.as_ref();
+        std::fs::create_dir_all(dir)
+            .with_context(|| "Failed to create the directory of the saved searches")?;
+
+        let path = dir.join(self.file_name());
+        confy::store_path(&path, self)?;
+        Ok(path)
+    }
+}
+
+/// Loads all presets of the given directory sorted by name.<br>
+/// Invalid preset files are skipped, so a single broken file does not hide the other presets
+pub fn load_presets<P: AsRef<Path>>(dir: P) -> Vec<SearchPreset> {
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return vec![];
+    };
+
+    let mut presets: Vec<SearchPreset> = entries
+        .flatten()
+        .map(|entry| entry.path())
+        .filter(|path| path.is_file() && path.extension().is_some_and(|ext| ext == "toml"))
+        .filter_map(|path| match confy::load_path::<SearchPreset>(&path) {
+            Ok(mut preset) => {
+                // a hand written preset file may omit the name
+                if preset.name.trim().is_empty() {
+                    preset.name = path.file_stem()?.to_string_lossy().to_string();
+                }
+                Some(preset)
+            }
+            Err(err) => {
+                log::warn!("Invalid saved search [{}]: {err}", path.display());
+                None
+            }
+        })
+        .collect();
+
+    presets.sort_by_key(|preset| preset.name.to_lowercase());
+    presets
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{SearchPreset, load_presets};
+    use crate::{
+        file_handling::{reports::ReportKind, search::SearchType},
+        ui::search_widget::SearchMode,
+    };
+
+    #[test]
+    fn test_save_and_load_presets() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        // the presets directory is created by the first save
+        let dir = temp_dir.path().join("presets");
+
+        let stale_logs = SearchPreset {
+            name: "Stale logs".to_string(),
+            query: "*.log modified:>30d".to_string(),
+            mode: SearchMode::Deep,
+            depth: 4,
+            root: "~/logs".to_string(),
+            ..Default::default()
+        };
+        let large_videos = SearchPreset {
+            name: "large videos".to_string(),
+            query: "format:video".to_string(),
+            search_type: SearchType::Report(ReportKind::LargestFiles),
+            ..Default::default()
+        };
+        assert_eq!(stale_logs.save(&dir).unwrap(), dir.join("stale_logs.toml"));
+        large_videos.save(&dir).unwrap();
+        // a hand written preset without a name and a broken preset
+        std::fs::write(dir.join("empty-files.toml"), "query = \"size:0\"\n").unwrap();
+        std::fs::write(dir.join("broken.toml"), "mode = 42\n").unwrap();
+
+        let presets = load_presets(&dir);
+        let names: Vec<&str> = presets.iter().map(|p| p.name.as_str()).collect();
+        assert_eq!(names, vec!["empty-files", "large videos", "Stale logs"]);
+        assert_eq!(presets[0].query, "size:0");
+        assert_eq!(presets[1], large_videos);
+        assert_eq!(presets[2], stale_logs);
+    }
+
+    #[test]
+    fn test_preset_root_dir() {
+        let mut preset = SearchPreset::default();
+        assert_eq!(preset.root_dir(), None);
+        preset.root = "/tmp".to_string();
+        assert_eq!(preset.root_dir(), Some("/tmp".into()));
+        assert!(SearchPreset::default().save(std::env::temp_dir()).is_err());
+    }
+}
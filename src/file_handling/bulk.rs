@@ -0,0 +1,530 @@
+use anyhow::{Result, bail};
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::HashSet,
+    fs::File,
+    io::{BufWriter, ErrorKind},
+    path::{Path, PathBuf},
+    time::Instant,
+};
+use tokio::sync::mpsc;
+use tokio_util::sync::CancellationToken;
+use walkdir::WalkDir;
+use zip::{CompressionMethod, ZipWriter, write::SimpleFileOptions};
+
+use crate::{
+    app::{AppState, actions::Action},
+    file_handling::{
+        SearchResult,
+        dir_compare::{copy_dir, copy_file},
+        disk_usage::delete_path,
+        walker::PROGRESS_INTERVAL,
+    },
+    utils,
+};
+
+/// An operation, that is applied to all selected entries of the Results page
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum BulkOperation {
+    Copy,
+    Move,
+    Trash,
+    Delete,
+    /// Collects the entries into a zip archive
+    Archive,
+}
+
+impl std::fmt::Display for BulkOperation {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            BulkOperation::Copy => write!(f, "Copy to directory"),
+            BulkOperation::Move => write!(f, "Move to directory"),
+            BulkOperation::Trash => write!(f, "Move to trash"),
+            BulkOperation::Delete => write!(f, "Delete permanently"),
+            BulkOperation::Archive => write!(f, "Collect into zip archive"),
+        }
+    }
+}
+
+impl BulkOperation {
+    pub const ALL: [BulkOperation; 5] = [
+        BulkOperation::Copy,
+        BulkOperation::Move,
+        BulkOperation::Trash,
+        BulkOperation::Delete,
+        BulkOperation::Archive,
+    ];
+
+    /// Copy, move and archive ask for the directory, in which the entries or the archive are created
+    pub fn requires_target_dir(self) -> bool {
+        matches!(
+            self,
+            BulkOperation::Copy | BulkOperation::Move | BulkOperation::Archive
+        )
+    }
+
+    /// The entries no longer exist at their path, after the operation succeeded
+    pub fn removes_entries(self) -> bool {
+        matches!(
+            self,
+            BulkOperation::Move | BulkOperation::Trash | BulkOperation::Delete
+        )
+    }
+
+    fn progress_desc(self) -> &'static str {
+        match self {
+            BulkOperation::Copy => "Copying",
+            BulkOperation::Move => "Moving",
+            BulkOperation::Trash => "Moving to trash",
+            BulkOperation::Delete => "Deleting",
+            BulkOperation::Archive => "Archiving",
+        }
+    }
+
+    /// Describes the successful operation in the footer, e.g. `3 entries copied`
+    pub fn done_desc(self) -> &'static str {
+        match self {
+            BulkOperation::Copy => "copied",
+            BulkOperation::Move => "moved",
+            BulkOperation::Trash => "moved to trash",
+            BulkOperation::Delete => "deleted",
+            BulkOperation::Archive => "archived",
+        }
+    }
+}
+
+/// An operation together with the entries it is applied to
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BulkJob {
+    pub operation: BulkOperation,
+    /// The entries, without entries inside of another selected directory
+    pub paths: Vec<PathBuf>,
+    /// The destination directory of a copy or move, the archive file of an archive
+    pub target: Option<PathBuf>,
+    /// The entries of an archive are stored with their path relative to this directory
+    pub root: PathBuf,
+}
+
+impl BulkJob {
+    pub fn new(
+        operation: BulkOperation,
+        paths: Vec<PathBuf>,
+        target: Option<PathBuf>,
+        root: PathBuf,
+    ) -> Self {
+        Self {
+            operation,
+            paths: remove_nested(paths),
+            target,
+            root,
+        }
+    }
+}
+
+/// The outcome of a [`BulkJob`], also reported if the job was cancelled
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BulkReport {
+    pub operation: BulkOperation,
+    /// The entries, the operation was applied to successfully
+    pub done: Vec<PathBuf>,
+    /// One message per entry, that failed
+    pub errors: Vec<String>,
+    pub is_cancelled: bool,
+}
+
+/// Removes duplicates and the entries inside of another selected directory,
+/// because the operation already applies to them together with their directory
+fn remove_nested(mut paths: Vec<PathBuf>) -> Vec<PathBuf> {
+    // the components are compared, so every directory is directly followed by its content
+    paths.sort();
+    paths.dedup();
+
+    let mut kept: Vec<PathBuf> = Vec::with_capacity(paths.len());
+    for path in paths {
+        if !kept.last().is_some_and(|parent| path.starts_with(parent)) {
+            kept.push(path);
+        }
+    }
+    kept
+}
+
+/// Returns the path of the entry in the target directory, existing entries are never replaced
+fn destination(source: &Path, target_dir: &Path) -> Result<PathBuf> {
+    let Some(name) = source.file_name() else {
+        bail!("Invalid path");
+    };
+    if target_dir.starts_with(source) {
+        bail!("The destination is inside of the directory");
+    }
+
+    let target = target_dir.join(name);
+    if target.exists() || target.is_symlink() {
+        bail!("{} already exists", utils::format_path_for_display(&target));
+    }
+    Ok(target)
+}
+
+fn copy_entry(source: &Path, target: &Path, cancellation_token: &CancellationToken) -> Result<()> {
+    if source.is_dir() && !source.is_symlink() {
+        let mut errors = vec![];
+        copy_dir(source, target, cancellation_token, &mut errors)?;
+        if !errors.is_empty() {
+            bail!("Unable to copy {}", errors.join(", "));
+        }
+        Ok(())
+    } else {
+        copy_file(source, target)
+    }
+}
+
+/// Renames the entry, an entry on another filesystem is copied and deleted afterwards
+fn move_entry(source: &Path, target: &Path, cancellation_token: &CancellationToken) -> Result<()> {
+    match std::fs::rename(source, target) {
+        Ok(_) => Ok(()),
+        Err(err) if err.kind() == ErrorKind::CrossesDevices => {
+            copy_entry(source, target, cancellation_token)?;
+            if cancellation_token.is_cancelled() {
+                bail!("Cancelled, the copy is incomplete");
+            }
+            delete_path(source)
+        }
+        Err(err) => Err(err.into()),
+    }
+}
+
+/// Returns the name of the entry in the archive, relative to the search directory if possible.
+/// Entries outside of it are stored relative to the parent of the selected entry.
+fn archive_name(path: &Path, root: &Path, selected: &Path) -> String {
+    let relative = path
+        .strip_prefix(root)
+        .ok()
+        .filter(|relative| !relative.as_os_str().is_empty())
+        .or_else(|| path.strip_prefix(selected.parent()?).ok())
+        .unwrap_or(path);
+
+    relative
+        .components()
+        .map(|component| component.as_os_str().to_string_lossy())
+        .collect::<Vec<_>>()
+        .join("/")
+}
+
+/// Adds the entry with its whole content to the archive, symlinks are skipped
+fn add_to_archive(
+    zip: &mut ZipWriter<BufWriter<File>>,
+    selected: &Path,
+    job: &BulkJob,
+    archive_path: &Path,
+    cancellation_token: &CancellationToken,
+) -> Result<()> {
+    let options = SimpleFileOptions::default().compression_method(CompressionMethod::Deflated);
+
+    for entry in WalkDir::new(selected) {
+        if cancellation_token.is_cancelled() {
+            return Ok(());
+        }
+        let entry = entry?;
+        // the archive may be created inside of a selected directory
+        if entry.path() == archive_path {
+            continue;
+        }
+
+        let name = archive_name(entry.path(), &job.root, selected);
+        if entry.file_type().is_dir() {
+            zip.add_directory(name, options)?;
+        } else if entry.file_type().is_file() {
+            zip.start_file(name, options)?;
+            std::io::copy(&mut File::open(entry.path())?, zip)?;
+        }
+    }
+    Ok(())
+}
+
+fn send_progress(
+    tx: &mpsc::UnboundedSender<Action>,
+    operation: BulkOperation,
+    index: usize,
+    total: usize,
+) -> Result<()> {
+    tx.send(Action::UpdateAppState(AppState::Working(format!(
+        "{}... {index}/{total} entries, press Esc to cancel",
+        operation.progress_desc()
+    ))))?;
+    Ok(())
+}
+
+/// Collects the selected entries into a new zip archive, a cancelled archive is removed again
+fn write_archive(
+    tx: &mpsc::UnboundedSender<Action>,
+    job: &BulkJob,
+    cancellation_token: &CancellationToken,
+) -> Result<BulkReport> {
+    let mut report = BulkReport {
+        operation: job.operation,
+        done: vec![],
+        errors: vec![],
+        is_cancelled: false,
+    };
+    let Some(archive_path) = job.target.as_deref() else {
+        bail!("The archive requires a target file");
+    };
+
+    let file = match File::create_new(archive_path) {
+        Ok(file) => file,
+        Err(err) => {
+            report.errors.push(format!(
+                "{}: {err}",
+                utils::format_path_for_display(archive_path)
+            ));
+            return Ok(report);
+        }
+    };
+    let mut zip = ZipWriter::new(BufWriter::new(file));
+    let mut last_progress = Instant::now();
+
+    for (i, path) in job.paths.iter().enumerate() {
+        if cancellation_token.is_cancelled() {
+            break;
+        }
+        if last_progress.elapsed() >= PROGRESS_INTERVAL {
+            send_progress(tx, job.operation, i, job.paths.len())?;
+            last_progress = Instant::now();
+        }
+
+        match add_to_archive(&mut zip, path, job, archive_path, cancellation_token) {
+            Ok(_) => report.done.push(path.clone()),
+            Err(err) => {
+                log::error!(
+                    "Bulk: Unable to archive {} - Details {:?}",
+                    path.display(),
+                    err
+                );
+                report
+                    .errors
+                    .push(format!("{}: {err}", utils::format_path_for_display(path)));
+            }
+        }
+    }
+
+    let result = zip.finish().map_err(anyhow::Error::from);
+    if cancellation_token.is_cancelled() || result.is_err() {
+        let _ = std::fs::remove_file(archive_path);
+        report.done.clear();
+    }
+    if let Err(err) = result {
+        report.errors.push(format!(
+            "{}: {err}",
+            utils::format_path_for_display(archive_path)
+        ));
+    }
+    report.is_cancelled = cancellation_token.is_cancelled();
+    Ok(report)
+}
+
+/// Applies the operation to all entries of the job, one after another.<br>
+/// A failing entry does not stop the job, its error is reported at the end instead.
+/// A cancelled job stops after the current entry and reports the entries processed so far.
+///
+/// # Returns
+/// An error, if it was not possible to send the progress over the channel
+pub fn run_bulk_job(
+    tx: &mpsc::UnboundedSender<Action>,
+    job: &BulkJob,
+    cancellation_token: &CancellationToken,
+) -> Result<BulkReport> {
+    if job.operation == BulkOperation::Archive {
+        return write_archive(tx, job, cancellation_token);
+    }
+
+    let mut report = BulkReport {
+        operation: job.operation,
+        done: vec![],
+        errors: vec![],
+        is_cancelled: false,
+    };
+    let mut last_progress = Instant::now();
+
+    for (i, path) in job.paths.iter().enumerate() {
+        if cancellation_token.is_cancelled() {
+            break;
+        }
+        if last_progress.elapsed() >= PROGRESS_INTERVAL {
+            send_progress(tx, job.operation, i, job.paths.len())?;
+            last_progress = Instant::now();
+        }
+
+        let target = || match job.target.as_deref() {
+            Some(target_dir) => destination(path, target_dir),
+            None => bail!("No destination directory"),
+        };
+        let result = match job.operation {
+            BulkOperation::Copy => {
+                target().and_then(|target| copy_entry(path, &target, cancellation_token))
+            }
+            BulkOperation::Move => {
+                target().and_then(|target| move_entry(path, &target, cancellation_token))
+            }
+            BulkOperation::Trash => trash::delete(path).map_err(anyhow::Error::from),
+            BulkOperation::Delete => delete_path(path),
+            BulkOperation::Archive => unreachable!("the archive is written as a whole"),
+        };
+
+        match result {
+            Ok(_) => report.done.push(path.clone()),
+            Err(err) => {
+                log::error!(
+                    "Bulk: Unable to apply '{}' to {} - Details {:?}",
+                    job.operation,
+                    path.display(),
+                    err
+                );
+                report
+                    .errors
+                    .push(format!("{}: {err}", utils::format_path_for_display(path)));
+            }
+        }
+    }
+
+    report.is_cancelled = cancellation_token.is_cancelled();
+    Ok(report)
+}
+
+impl SearchResult {
+    /// Removes the entries, that no longer exist at their path, together with the content of removed directories.<br>
+    /// Duplicate groups with less than two remaining files are removed completely.
+    pub fn remove_paths(&mut self, removed: &[PathBuf]) {
+        let removed: HashSet<&Path> = removed.iter().map(PathBuf::as_path).collect();
+        let is_removed = |path: &Path| path.ancestors().any(|path| removed.contains(path));
+
+        if !self.duplicate_groups.is_empty() {
+            let resolved: Vec<PathBuf> = self
+                .items
+                .iter()
+                .filter(|entry| is_removed(&entry.path))
+                .map(|entry| entry.path.clone())
+                .collect();
+            self.remove_resolved_duplicates(&resolved);
+            return;
+        }
+
+        let keep: Vec<bool> = self
+            .items
+            .iter()
+            .map(|entry| !is_removed(&entry.path))
+            .collect();
+        retain_by_index(&mut self.items, &keep);
+        retain_by_index(&mut self.line_matches, &keep);
+        retain_by_index(&mut self.detected_formats, &keep);
+
+        self.rebuild_rows();
+        self.selected = self.selected.min(self.row_count().saturating_sub(1));
+        self.start_index = self.start_index.min(self.selected);
+    }
+}
+
+/// Keeps the values of the items or of a parallel vector, that are flagged in `keep`
+fn retain_by_index<T>(values: &mut Vec<T>, keep: &[bool]) {
+    let mut index = 0;
+    values.retain(|_| {
+        index += 1;
+        keep.get(index - 1).copied().unwrap_or(false)
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{BulkJob, BulkOperation, remove_nested, run_bulk_job};
+    use std::{io::Read, path::PathBuf};
+    use tokio_util::sync::CancellationToken;
+
+    #[test]
+    fn test_remove_nested() {
+        let paths = remove_nested(vec![
+            PathBuf::from("/data/b.txt"),
+            PathBuf::from("/data/a/c.txt"),
+            PathBuf::from("/data/a"),
+            PathBuf::from("/data/a-b"),
+            PathBuf::from("/data/b.txt"),
+        ]);
+        assert_eq!(
+            paths,
+            vec![
+                PathBuf::from("/data/a"),
+                PathBuf::from("/data/a-b"),
+                PathBuf::from("/data/b.txt")
+            ]
+        );
+    }
+
+    #[test]
+    fn test_bulk_jobs() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path().to_path_buf();
+        let target = root.join("target");
+        std::fs::create_dir_all(root.join("dir")).unwrap();
+        std::fs::create_dir_all(&target).unwrap();
+        std::fs::write(root.join("dir/a.txt"), "a").unwrap();
+        std::fs::write(root.join("b.txt"), "b").unwrap();
+
+        let (tx, _rx) = tokio::sync::mpsc::unbounded_channel();
+        let token = CancellationToken::new();
+        let run = |operation, paths: Vec<PathBuf>, target: Option<PathBuf>| {
+            let job = BulkJob::new(operation, paths, target, root.clone());
+            run_bulk_job(&tx, &job, &token).unwrap()
+        };
+
+        // copy a directory and a file, an existing entry is reported and not replaced
+        let copied = run(
+            BulkOperation::Copy,
+            vec![root.join("dir"), root.join("b.txt"), root.join("dir/a.txt")],
+            Some(target.clone()),
+        );
+        assert_eq!(copied.done.len(), 2);
+        assert!(target.join("dir/a.txt").is_file() && target.join("b.txt").is_file());
+        let copied_again = run(
+            BulkOperation::Copy,
+            vec![root.join("b.txt")],
+            Some(target.clone()),
+        );
+        assert_eq!(copied_again.errors.len(), 1);
+
+        // archive the entries relative to the search directory
+        let archive_path = root.join("results.zip");
+        let archived = run(
+            BulkOperation::Archive,
+            vec![root.join("dir"), root.join("b.txt")],
+            Some(archive_path.clone()),
+        );
+        assert_eq!(archived.done.len(), 2);
+        let mut archive =
+            zip::ZipArchive::new(std::fs::File::open(&archive_path).unwrap()).unwrap();
+        let mut content = String::new();
+        archive
+            .by_name("dir/a.txt")
+            .unwrap()
+            .read_to_string(&mut content)
+            .unwrap();
+        assert_eq!(content, "a");
+        assert!(archive.by_name("b.txt").is_ok());
+
+        // move into a new directory and delete the moved entries
+        let moved_dir = root.join("moved");
+        std::fs::create_dir(&moved_dir).unwrap();
+        let moved = run(
+            BulkOperation::Move,
+            vec![root.join("dir")],
+            Some(moved_dir.clone()),
+        );
+        assert_eq!(moved.done, vec![root.join("dir")]);
+        assert!(!root.join("dir").exists() && moved_dir.join("dir/a.txt").is_file());
+
+        let deleted = run(
+            BulkOperation::Delete,
+            vec![moved_dir.join("dir"), root.join("missing.txt")],
+            None,
+        );
+        assert_eq!(deleted.done.len(), 1);
+        assert_eq!(deleted.errors.len(), 1);
+        assert!(!moved_dir.join("dir").exists());
+    }
+}
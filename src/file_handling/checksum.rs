@@ -0,0 +1,275 @@
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use sha2::Digest;
+use std::{
+    fs::File,
+    io::Read,
+    path::{Path, PathBuf},
+    time::Instant,
+};
+use tokio::sync::mpsc;
+use tokio_util::sync::CancellationToken;
+
+use crate::{app::actions::Action, file_handling::walker::PROGRESS_INTERVAL};
+
+/// Size of the buffer used to read the file content
+const READ_BUFFER_SIZE: usize = 64 * 1024;
+
+/// Represents the supported hash algorithms to compute a file checksum
+#[derive(Default, Debug, Copy, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ChecksumAlgorithm {
+    Md5,
+    Sha1,
+    #[default]
+    Sha256,
+    Blake3,
+}
+
+impl std::fmt::Display for ChecksumAlgorithm {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ChecksumAlgorithm::Md5 => write!(f, "MD5"),
+            ChecksumAlgorithm::Sha1 => write!(f, "SHA-1"),
+            ChecksumAlgorithm::Sha256 => write!(f, "SHA-256"),
+            ChecksumAlgorithm::Blake3 => write!(f, "BLAKE3"),
+        }
+    }
+}
+
+impl ChecksumAlgorithm {
+    /// Get the next available algorithm
+    pub fn next(self) -> Self {
+        match self {
+            ChecksumAlgorithm::Md5 => ChecksumAlgorithm::Sha1,
+            ChecksumAlgorithm::Sha1 => ChecksumAlgorithm::Sha256,
+            ChecksumAlgorithm::Sha256 => ChecksumAlgorithm::Blake3,
+            ChecksumAlgorithm::Blake3 => ChecksumAlgorithm::Md5,
+        }
+    }
+
+    /// Extension of a checksum file next to the checked file, e.g. `archive.tar.gz.sha256`
+    fn file_extension(&self) -> &'static str {
+        match self {
+            ChecksumAlgorithm::Md5 => "md5",
+            ChecksumAlgorithm::Sha1 => "sha1",
+            ChecksumAlgorithm::Sha256 => "sha256",
+            ChecksumAlgorithm::Blake3 => "b3",
+        }
+    }
+}
+
+/// Wrapper around the hash implementations of the different algorithms
+enum Hasher {
+    Md5(md5::Md5),
+    Sha1(sha1::Sha1),
+    Sha256(sha2::Sha256),
+    Blake3(Box<blake3::Hasher>),
+}
+
+impl Hasher {
+    fn new(algorithm: ChecksumAlgorithm) -> Self {
+        match algorithm {
+            ChecksumAlgorithm::Md5 => Hasher::Md5(md5::Md5::new()),
+            ChecksumAlgorithm::Sha1 => Hasher::Sha1(sha1::Sha1::new()),
+            ChecksumAlgorithm::Sha256 => Hasher::Sha256(sha2::Sha256::new()),
+            ChecksumAlgorithm::Blake3 => Hasher::Blake3(Box::new(blake3::Hasher::new())),
+        }
+    }
+
+    fn update(&mut self, data: &[u8]) {
+        match self {
+            Hasher::Md5(hasher) => hasher.update(data),
+            Hasher::Sha1(hasher) => hasher.update(data),
+            Hasher::Sha256(hasher) => hasher.update(data),
+            Hasher::Blake3(hasher) => {
+                hasher.update(data);
+            }
+        }
+    }
+
+    fn finalize_hex(self) -> String {
+        match self {
+            Hasher::Md5(hasher) => hex::encode(hasher.finalize()),
+            Hasher::Sha1(hasher) => hex::encode(hasher.finalize()),
+            Hasher::Sha256(hasher) => hex::encode(hasher.finalize()),
+            Hasher::Blake3(hasher) => hasher.finalize().to_hex().to_string(),
+        }
+    }
+}
+
+/// Represents a checksum file next to the checked file
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct SiblingChecksum {
+    pub path: PathBuf,
+    /// The expected hash, as lowercase hex string
+    pub hash: String,
+}
+
+/// Represents the computed checksum of a file
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct Checksum {
+    pub path: PathBuf,
+    pub algorithm: ChecksumAlgorithm,
+    /// The digest as lowercase hex string
+    pub digest: String,
+    /// Checksum file of the same algorithm next to the file, if any
+    pub sibling: Option<SiblingChecksum>,
+}
+
+impl Checksum {
+    /// Compares the digest with the given hash, ignoring the case and surrounding whitespace
+    pub fn matches(&self, hash: &str) -> bool {
+        self.digest.eq_ignore_ascii_case(hash.trim())
+    }
+}
+
+/// Computes the checksum of the given file.<br>
+/// The progress is reported with [`Action::ChecksumProgress`], containing the processed and the total bytes.
+///
+/// # Returns
+/// `None`, if the computation was cancelled
+pub fn compute_checksum(
+    tx: &mpsc::UnboundedSender<Action>,
+    path: &Path,
+    algorithm: ChecksumAlgorithm,
+    cancellation_token: &CancellationToken,
+) -> Result<Option<Checksum>> {
+    let file = File::open(path)?;
+    let total = file.metadata()?.len();
+    let mut last_progress = Instant::now();
+
+    let digest = hash_reader(file, algorithm, cancellation_token, |processed| {
+        if last_progress.elapsed() >= PROGRESS_INTERVAL {
+            let _ = tx.send(Action::ChecksumProgress(processed, total));
+            last_progress = Instant::now();
+        }
+    })?;
+
+    Ok(digest.map(|digest| Checksum {
+        path: path.to_path_buf(),
+        algorithm,
+        digest,
+        sibling: find_sibling_checksum(path, algorithm),
+    }))
+}
+
+fn hash_reader<R: Read>(
+    mut reader: R,
+    algorithm: ChecksumAlgorithm,
+    cancellation_token: &CancellationToken,
+    mut on_progress: impl FnMut(u64),
+) -> Result<Option<String>> {
+    let mut hasher = Hasher::new(algorithm);
+    let mut buffer = vec![0; READ_BUFFER_SIZE];
+    let mut processed: u64 = 0;
+
+    loop {
+        if cancellation_token.is_cancelled() {
+            return Ok(None);
+        }
+
+        let read = match reader.read(&mut buffer) {
+            Ok(0) => break,
+            Ok(read) => read,
+            Err(err) if err.kind() == std::io::ErrorKind::Interrupted => continue,
+            Err(err) => return Err(err.into()),
+        };
+
+        hasher.update(&buffer[..read]);
+        processed += read as u64;
+        on_progress(processed);
+    }
+
+    Ok(Some(hasher.finalize_hex()))
+}
+
+/// Looks for a checksum file of the given algorithm next to the file, e.g. `archive.tar.gz.sha256`
+fn find_sibling_checksum(path: &Path, algorithm: ChecksumAlgorithm) -> Option<SiblingChecksum> {
+    let file_name = path.file_name()?.to_string_lossy().to_string();
+    let sibling_path = path.with_file_name(format!("{}.{}", file_name, algorithm.file_extension()));
+    let content = std::fs::read_to_string(&sibling_path).ok()?;
+
+    parse_checksum_file(&content, &file_name).map(|hash| SiblingChecksum {
+        path: sibling_path,
+        hash,
+    })
+}
+
+/// Extracts the hash of the given file from the content of a checksum file.
+///
+/// Supported formats:
+/// - only the hash
+/// - `<hash>  <file name>` (GNU coreutils, `*` marks binary mode)
+/// - `<ALGORITHM> (<file name>) = <hash>` (BSD)
+fn parse_checksum_file(content: &str, file_name: &str) -> Option<String> {
+    let is_hash = |value: &str| !value.is_empty() && value.chars().all(|c| c.is_ascii_hexdigit());
+
+    content.lines().find_map(|line| {
+        let line = line.trim();
+
+        if let Some((name_part, hash)) = line.rsplit_once(" = ") {
+            let name = name_part
+                .split_once('(')
+                .and_then(|(_, rest)| rest.strip_suffix(')'))?;
+            return (name == file_name && is_hash(hash.trim())).then(|| hash.trim().to_lowercase());
+        }
+
+        let mut parts = line.splitn(2, char::is_whitespace);
+        let hash = parts.next()?;
+        let name = parts.next().map(|name| name.trim().trim_start_matches('*'));
+
+        match name {
+            Some(name) if name != file_name => None,
+            _ => is_hash(hash).then(|| hash.to_lowercase()),
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn digest_of(data: &[u8], algorithm: ChecksumAlgorithm) -> String {
+        hash_reader(data, algorithm, &CancellationToken::new(), |_| {})
+            .unwrap()
+            .unwrap()
+    }
+
+    #[test]
+    fn test_known_digests() {
+        assert_eq!(
+            digest_of(b"abc", ChecksumAlgorithm::Md5),
+            "900150983cd24fb0d6963f7d28e17f72"
+        );
+        assert_eq!(
+            digest_of(b"abc", ChecksumAlgorithm::Sha1),
+            "a9993e364706816aba3e25717850c26c9cd0d89d"
+        );
+        assert_eq!(
+            digest_of(b"abc", ChecksumAlgorithm::Sha256),
+            "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad"
+        );
+        assert_eq!(
+            digest_of(b"abc", ChecksumAlgorithm::Blake3),
+            "6437b3ac38465133ffb63b75273a8db548c558465d79db03fd359c6cd5bd9d85"
+        );
+    }
+
+    #[test]
+    fn test_parse_checksum_file() {
+        assert_eq!(
+            parse_checksum_file("ABCDEF\n", "file.bin").as_deref(),
+            Some("abcdef")
+        );
+        assert_eq!(
+            parse_checksum_file("123  other.bin\nabc *file.bin\n", "file.bin").as_deref(),
+            Some("abc")
+        );
+        assert_eq!(
+            parse_checksum_file("SHA256 (file.bin) = abc123", "file.bin").as_deref(),
+            Some("abc123")
+        );
+        assert_eq!(parse_checksum_file("123  other.bin", "file.bin"), None);
+        assert_eq!(parse_checksum_file("not a hash", "file.bin"), None);
+    }
+}
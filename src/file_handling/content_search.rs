@@ -0,0 +1,348 @@
+use anyhow::Result;
+use file_format::FileFormat;
+use ignore::WalkState;
+use serde::{Deserialize, Serialize};
+use std::{
+    fs::File,
+    io::{BufRead, BufReader},
+    path::Path,
+    sync::{
+        Mutex,
+        atomic::{AtomicUsize, Ordering},
+    },
+};
+use tokio::sync::mpsc;
+use tokio_util::sync::CancellationToken;
+
+use crate::{
+    app::actions::Action,
+    file_handling::{
+        DiskEntry, SearchResult, read_file_metadata,
+        search::{NameMatcher, SearchQuery},
+        walker::walk_parallel_streamed,
+    },
+};
+
+/// The search stops after this number of matching lines, to limit the memory usage
+pub const MAX_CONTENT_MATCHES: usize = 10_000;
+
+/// Matching lines are shortened to this number of characters
+const MAX_LINE_LENGTH: usize = 256;
+
+/// Represents a line of a file that matches the search query.<br>
+/// The line matches are stored in the same order as the items of the [`SearchResult`].
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct LineMatch {
+    /// Line number, starting at 1
+    pub line_number: usize,
+    /// The trimmed content of the line
+    pub line: String,
+}
+
+impl SearchResult {
+    /// Returns the matching line of the item at the given index, only available for a content search
+    pub fn line_match_of(&self, index: usize) -> Option<&LineMatch> {
+        self.line_matches.get(index)
+    }
+}
+
+/// Searches the content of all text files for lines that match the search query, with one thread per core.<br>
+/// Binary files are detected by their file format and skipped. The matching lines are streamed as
+/// [`Action::SearchChunk`] with each progress update.
+/// The final files are sorted by their path, so the result doesn't depend on the order in which the threads find them.
+///
+/// # Returns
+/// `None`, if no lines were found or the search was cancelled
+pub fn find_content_matches(
+    tx: &mpsc::UnboundedSender<Action>,
+    query: &SearchQuery,
+    cancellation_token: &CancellationToken,
+) -> Result<Option<SearchResult>> {
+    let matcher = query.name_matcher()?;
+    let filter = query.filters.compile(query.case_sensitive)?;
+    let file_counter = AtomicUsize::new(0);
+    let match_counter = AtomicUsize::new(0);
+    // number of lines, that were already streamed to the Results page
+    let streamed_lines = Mutex::new(0);
+
+    let mut files = walk_parallel_streamed(
+        tx,
+        query,
+        cancellation_token,
+        || {
+            format!(
+                "Searching file contents... {} Files scanned, {} Matches",
+                file_counter.load(Ordering::Relaxed),
+                match_counter.load(Ordering::Relaxed)
+            )
+        },
+        |new_files| {
+            let mut streamed_lines = streamed_lines.lock().ok()?;
+            let (items, line_matches) = flatten_file_matches(new_files, &mut streamed_lines);
+            Some(Action::SearchChunk(items, line_matches))
+        },
+        |entry| {
+            if !entry
+                .file_type()
+                .is_some_and(|file_type| file_type.is_file())
+            {
+                return (None, WalkState::Continue);
+            }
+
+            file_counter.fetch_add(1, Ordering::Relaxed);
+            if !filter.matches(&entry) || !is_text_file(entry.path()) {
+                return (None, WalkState::Continue);
+            }
+
+            let remaining =
+                MAX_CONTENT_MATCHES.saturating_sub(match_counter.load(Ordering::Relaxed));
+            let matches = match search_file(entry.path(), &matcher, remaining, cancellation_token) {
+                Ok(matches) if !matches.is_empty() => matches,
+                _ => return (None, WalkState::Continue),
+            };
+
+            let total = match_counter.fetch_add(matches.len(), Ordering::Relaxed) + matches.len();
+            let disk_entry = DiskEntry {
+                name: entry.file_name().to_string_lossy().to_string(),
+                file_metadata: read_file_metadata(&entry),
+                path: entry.into_path(),
+                is_dir: false,
+            };
+
+            if total >= MAX_CONTENT_MATCHES {
+                log::warn!(
+                    "Content search stopped after {} matching lines",
+                    MAX_CONTENT_MATCHES
+                );
+                return (Some((disk_entry, matches)), WalkState::Quit);
+            }
+            (Some((disk_entry, matches)), WalkState::Continue)
+        },
+    )?;
+
+    if cancellation_token.is_cancelled() || files.is_empty() {
+        return Ok(None);
+    }
+
+    files.sort_by(|(e1, _), (e2, _)| e1.path.cmp(&e2.path));
+
+    let (items, line_matches) = flatten_file_matches(files, &mut 0);
+
+    Ok(Some(SearchResult {
+        items,
+        line_matches,
+        ..SearchResult::for_query(query)
+    }))
+}
+
+/// Repeats the entry of each file for every matching line, so that there is one item per line match.<br>
+/// `line_count` is the number of lines, that were already returned, e.g. in previous chunks. It is increased
+/// by the returned lines, so that at most [`MAX_CONTENT_MATCHES`] lines are returned in total.
+fn flatten_file_matches(
+    files: Vec<(DiskEntry, Vec<LineMatch>)>,
+    line_count: &mut usize,
+) -> (Vec<DiskEntry>, Vec<LineMatch>) {
+    let mut items: Vec<DiskEntry> = vec![];
+    let mut line_matches: Vec<LineMatch> = vec![];
+    for (disk_entry, mut matches) in files {
+        // several threads may have found the last matches at the same time
+        matches.truncate(MAX_CONTENT_MATCHES.saturating_sub(*line_count));
+        *line_count += matches.len();
+        items.extend(std::iter::repeat_n(disk_entry, matches.len()));
+        line_matches.extend(matches);
+    }
+    (items, line_matches)
+}
+
+/// Checks the file format, only plain text and text based formats like XML or JSON are searched
+fn is_text_file(path: &Path) -> bool {
+    FileFormat::from_file(path).is_ok_and(|format| {
+        let media_type = format.media_type();
+        format == FileFormat::PlainText
+            || media_type.starts_with("text/")
+            || media_type.ends_with("xml")
+            || media_type.ends_with("json")
+            || media_type.ends_with("javascript")
+    })
+}
+
+/// Reads the file line by line and returns at most `limit` matching lines
+fn search_file(
+    path: &Path,
+    matcher: &NameMatcher,
+    limit: usize,
+    cancellation_token: &CancellationToken,
+) -> Result<Vec<LineMatch>> {
+    let mut reader = BufReader::new(File::open(path)?);
+    let mut buffer = Vec::new();
+    let mut matches = vec![];
+    let mut line_number = 0;
+
+    while matches.len() < limit && !cancellation_token.is_cancelled() {
+        buffer.clear();
+        if reader.read_until(b'\n', &mut buffer)? == 0 {
+            break;
+        }
+        line_number += 1;
+
+        // invalid UTF-8 sequences must not stop the search of the remaining lines
+        let line = String::from_utf8_lossy(&buffer);
+        let line = line.trim_end_matches(['\n', '\r']);
+        if matcher.is_match(line) {
+            matches.push(LineMatch {
+                line_number,
+                line: shorten_line(line),
+            });
+        }
+    }
+
+    Ok(matches)
+}
+
+/// Trims the whitespaces and shortens the line to [`MAX_LINE_LENGTH`] characters
+fn shorten_line(line: &str) -> String {
+    let line = line.trim();
+    match line.char_indices().nth(MAX_LINE_LENGTH) {
+        Some((end, _)) => line[..end].to_string(),
+        None => line.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        LineMatch, MAX_CONTENT_MATCHES, MAX_LINE_LENGTH, flatten_file_matches, search_file,
+        shorten_line,
+    };
+    use crate::file_handling::{
+        DiskEntry, SearchResult,
+        search::{NameMatcher, QueryMode},
+    };
+    use std::path::PathBuf;
+    use tokio_util::sync::CancellationToken;
+
+    #[test]
+    fn test_search_file_reports_line_numbers() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("content_search_test.txt");
+        std::fs::write(
+            &path,
+            "fn main() {\n    let todo = 1;\n}\n// TODO: refactor\n",
+        )
+        .unwrap();
+
+        let matcher = NameMatcher::new("todo", QueryMode::Plain, false).unwrap();
+        let matches = search_file(&path, &matcher, 10, &CancellationToken::new()).unwrap();
+        assert_eq!(
+            matches
+                .iter()
+                .map(|m| (m.line_number, m.line.as_str()))
+                .collect::<Vec<_>>(),
+            vec![(2, "let todo = 1;"), (4, "// TODO: refactor")]
+        );
+
+        let matches = search_file(&path, &matcher, 1, &CancellationToken::new()).unwrap();
+        assert_eq!(matches.len(), 1);
+
+        // the line terminator is not part of the line
+        let matcher = NameMatcher::new("refactor$", QueryMode::Regex, true).unwrap();
+        let matches = search_file(&path, &matcher, 10, &CancellationToken::new()).unwrap();
+        assert_eq!(matches.len(), 1);
+
+        std::fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn test_shorten_line() {
+        assert_eq!(shorten_line("  \tvalue\r\n"), "value");
+        let long_line = "ä".repeat(MAX_LINE_LENGTH + 10);
+        assert_eq!(shorten_line(&long_line).chars().count(), MAX_LINE_LENGTH);
+    }
+
+    #[test]
+    fn test_streamed_chunks_respect_the_global_limit() {
+        let file_matches = |name: &str, count: usize| {
+            (
+                DiskEntry {
+                    name: name.to_string(),
+                    path: PathBuf::from(name),
+                    file_metadata: None,
+                    is_dir: false,
+                },
+                (1..=count)
+                    .map(|line_number| LineMatch {
+                        line_number,
+                        line: String::new(),
+                    })
+                    .collect::<Vec<_>>(),
+            )
+        };
+
+        let mut streamed_lines = 0;
+        let (items, line_matches) = flatten_file_matches(
+            vec![file_matches("a", MAX_CONTENT_MATCHES - 2)],
+            &mut streamed_lines,
+        );
+        assert_eq!(items.len(), MAX_CONTENT_MATCHES - 2);
+        assert_eq!(line_matches.len(), MAX_CONTENT_MATCHES - 2);
+
+        // the next chunk is truncated against the lines of all previous chunks
+        let (items, line_matches) = flatten_file_matches(
+            vec![file_matches("b", 3), file_matches("c", 1)],
+            &mut streamed_lines,
+        );
+        assert_eq!(items.len(), 2);
+        assert_eq!(line_matches.len(), 2);
+        assert_eq!(streamed_lines, MAX_CONTENT_MATCHES);
+
+        let (items, _) = flatten_file_matches(vec![file_matches("d", 1)], &mut streamed_lines);
+        assert!(items.is_empty());
+    }
+
+    #[test]
+    fn test_stopped_search_is_sorted_and_keeps_selection() {
+        let streamed_match = |path: &str, line_number| {
+            (
+                DiskEntry {
+                    name: path.to_string(),
+                    path: PathBuf::from(path),
+                    file_metadata: None,
+                    is_dir: false,
+                },
+                LineMatch {
+                    line_number,
+                    line: String::new(),
+                },
+            )
+        };
+        let (items, line_matches): (Vec<_>, Vec<_>) = vec![
+            streamed_match("b", 7),
+            streamed_match("a", 3),
+            streamed_match("b", 2),
+        ]
+        .into_iter()
+        .unzip();
+
+        let mut result = SearchResult {
+            is_in_progress: true,
+            ..Default::default()
+        };
+        result.set_terminal_height(10);
+        result.append_matches(&items, &line_matches);
+        // the first streamed match is selected: b, line 7
+        assert_eq!(result.selected(), 0);
+
+        result.stop();
+        assert!(!result.is_in_progress());
+        assert_eq!(
+            result
+                .items()
+                .iter()
+                .zip(&result.line_matches)
+                .map(|(entry, line_match)| (entry.name.as_str(), line_match.line_number))
+                .collect::<Vec<_>>(),
+            vec![("a", 3), ("b", 2), ("b", 7)]
+        );
+        assert_eq!(result.selected(), 2);
+    }
+}
@@ -0,0 +1,288 @@
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use similar::{ChangeTag, TextDiff};
+use std::{
+    fs::File,
+    path::{Path, PathBuf},
+    time::Duration,
+};
+
+/// Number of unchanged lines shown around each change
+const CONTEXT_LINES: usize = 3;
+
+/// Files above this size are only compared by size and hash
+const MAX_TEXT_DIFF_SIZE: u64 = 16 * 1024 * 1024;
+
+/// Number of bytes at the beginning of a file, that are checked for binary content
+const BINARY_CHECK_SIZE: usize = 8000;
+
+/// Maximum time spent to find the smallest diff, afterwards a less optimal diff is used
+const DIFF_TIMEOUT: Duration = Duration::from_secs(5);
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+pub enum DiffTag {
+    Equal,
+    Delete,
+    Insert,
+}
+
+/// Represents a single line of a hunk
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct DiffLine {
+    pub tag: DiffTag,
+    /// Line number in the left file (1-based), not available for inserted lines
+    pub old_line: Option<usize>,
+    /// Line number in the right file (1-based), not available for deleted lines
+    pub new_line: Option<usize>,
+    /// The line content without the line break
+    pub text: String,
+}
+
+/// Represents a group of changes, including the surrounding unchanged lines
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct DiffHunk {
+    old_start: usize,
+    old_len: usize,
+    new_start: usize,
+    new_len: usize,
+    pub lines: Vec<DiffLine>,
+}
+
+impl DiffHunk {
+    /// The hunk header in the unified diff format, e.g. `@@ -1,4 +1,5 @@`
+    pub fn header(&self) -> String {
+        format!(
+            "@@ -{},{} +{},{} @@",
+            self.old_start, self.old_len, self.new_start, self.new_len
+        )
+    }
+
+    /// Pairs the lines of the hunk for a side-by-side view.<br>
+    /// Unchanged lines are shown on both sides, deleted lines are paired with the following inserted lines.
+    pub fn side_by_side(&self) -> Vec<(Option<&DiffLine>, Option<&DiffLine>)> {
+        let mut rows = vec![];
+        let mut deleted: Vec<&DiffLine> = vec![];
+        let mut inserted: Vec<&DiffLine> = vec![];
+
+        for line in &self.lines {
+            match line.tag {
+                DiffTag::Equal => {
+                    flush(&mut rows, &mut deleted, &mut inserted);
+                    rows.push((Some(line), Some(line)));
+                }
+                DiffTag::Delete => {
+                    // a delete after an insert starts a new block of changes
+                    if !inserted.is_empty() {
+                        flush(&mut rows, &mut deleted, &mut inserted);
+                    }
+                    deleted.push(line);
+                }
+                DiffTag::Insert => inserted.push(line),
+            }
+        }
+        flush(&mut rows, &mut deleted, &mut inserted);
+
+        rows
+    }
+}
+
+/// Pairs the collected deleted and inserted lines row by row and clears them
+fn flush<'a>(
+    rows: &mut Vec<(Option<&'a DiffLine>, Option<&'a DiffLine>)>,
+    deleted: &mut Vec<&'a DiffLine>,
+    inserted: &mut Vec<&'a DiffLine>,
+) {
+    for i in 0..deleted.len().max(inserted.len()) {
+        rows.push((deleted.get(i).copied(), inserted.get(i).copied()));
+    }
+    deleted.clear();
+    inserted.clear();
+}
+
+/// Size and hash of a file, used to compare binary or very large files
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct FileSummary {
+    pub size: u64,
+    /// BLAKE3 hash as hex string
+    pub hash: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub enum DiffContent {
+    /// Line based diff of two text files, no hunks if both files are identical
+    Text(Vec<DiffHunk>),
+    /// At least one of the files is binary or too large, so only size and hash are compared
+    Binary(FileSummary, FileSummary),
+}
+
+/// Represents the differences between two files
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct FileDiff {
+    pub left: PathBuf,
+    pub right: PathBuf,
+    pub content: DiffContent,
+}
+
+impl FileDiff {
+    pub fn is_identical(&self) -> bool {
+        match &self.content {
+            DiffContent::Text(hunks) => hunks.is_empty(),
+            DiffContent::Binary(left, right) => left == right,
+        }
+    }
+
+    /// Number of deleted and inserted lines
+    pub fn stats(&self) -> (usize, usize) {
+        match &self.content {
+            DiffContent::Text(hunks) => hunks.iter().flat_map(|hunk| &hunk.lines).fold(
+                (0, 0),
+                |(deleted, inserted), line| match line.tag {
+                    DiffTag::Equal => (deleted, inserted),
+                    DiffTag::Delete => (deleted + 1, inserted),
+                    DiffTag::Insert => (deleted, inserted + 1),
+                },
+            ),
+            DiffContent::Binary(_, _) => (0, 0),
+        }
+    }
+}
+
+/// Compares the two files line by line.<br>
+/// Binary files and files larger than [`MAX_TEXT_DIFF_SIZE`] are only compared by size and hash.
+pub fn diff_files(left: &Path, right: &Path) -> Result<FileDiff> {
+    let left_size = left.metadata()?.len();
+    let right_size = right.metadata()?.len();
+
+    let text_content = if left_size.max(right_size) <= MAX_TEXT_DIFF_SIZE {
+        read_text(left)?.zip(read_text(right)?)
+    } else {
+        None
+    };
+
+    let content = match text_content {
+        Some((left_text, right_text)) => DiffContent::Text(diff_text(&left_text, &right_text)),
+        None => DiffContent::Binary(summarize(left, left_size)?, summarize(right, right_size)?),
+    };
+
+    Ok(FileDiff {
+        left: left.to_path_buf(),
+        right: right.to_path_buf(),
+        content,
+    })
+}
+
+/// Reads the file as text, `None` if the file contains binary data
+fn read_text(path: &Path) -> Result<Option<String>> {
+    let bytes = std::fs::read(path)?;
+    if bytes[..bytes.len().min(BINARY_CHECK_SIZE)].contains(&0) {
+        return Ok(None);
+    }
+    Ok(String::from_utf8(bytes).ok())
+}
+
+fn summarize(path: &Path, size: u64) -> Result<FileSummary> {
+    let mut hasher = blake3::Hasher::new();
+    hasher.update_reader(File::open(path)?)?;
+    Ok(FileSummary {
+        size,
+        hash: hasher.finalize().to_hex().to_string(),
+    })
+}
+
+fn diff_text(left: &str, right: &str) -> Vec<DiffHunk> {
+    let diff = TextDiff::configure()
+        .timeout(DIFF_TIMEOUT)
+        .diff_lines(left, right);
+
+    diff.grouped_ops(CONTEXT_LINES)
+        .iter()
+        .filter_map(|ops| {
+            let (first, last) = (ops.first()?, ops.last()?);
+            let old_range = first.old_range().start..last.old_range().end;
+            let new_range = first.new_range().start..last.new_range().end;
+
+            let lines = ops
+                .iter()
+                .flat_map(|op| diff.iter_changes(op))
+                .map(|change| DiffLine {
+                    tag: match change.tag() {
+                        ChangeTag::Equal => DiffTag::Equal,
+                        ChangeTag::Delete => DiffTag::Delete,
+                        ChangeTag::Insert => DiffTag::Insert,
+                    },
+                    old_line: change.old_index().map(|i| i + 1),
+                    new_line: change.new_index().map(|i| i + 1),
+                    text: change.value().trim_end_matches(['\n', '\r']).to_string(),
+                })
+                .collect();
+
+            Some(DiffHunk {
+                // an empty range starts at the line before, like in the unified diff format
+                old_start: old_range.start + usize::from(!old_range.is_empty()),
+                old_len: old_range.len(),
+                new_start: new_range.start + usize::from(!new_range.is_empty()),
+                new_len: new_range.len(),
+                lines,
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_diff_text_hunks() {
+        let hunks = diff_text("a\nb\nc\n", "a\nB\nc\nd\n");
+
+        assert_eq!(hunks.len(), 1);
+        assert_eq!(hunks[0].header(), "@@ -1,3 +1,4 @@");
+
+        let tags: Vec<DiffTag> = hunks[0].lines.iter().map(|line| line.tag).collect();
+        assert_eq!(
+            tags,
+            vec![
+                DiffTag::Equal,
+                DiffTag::Delete,
+                DiffTag::Insert,
+                DiffTag::Equal,
+                DiffTag::Insert
+            ]
+        );
+        assert_eq!(hunks[0].lines[4].new_line, Some(4));
+        assert_eq!(hunks[0].lines[4].text, "d");
+    }
+
+    #[test]
+    fn test_identical_text_has_no_hunks() {
+        assert!(diff_text("a\nb\n", "a\nb\n").is_empty());
+    }
+
+    #[test]
+    fn test_side_by_side_pairs_changes() {
+        let hunks = diff_text("a\nb\nc\nd\n", "a\nB\nC\nX\nd\n");
+        let rows = hunks[0].side_by_side();
+
+        let texts: Vec<(Option<&str>, Option<&str>)> = rows
+            .iter()
+            .map(|(left, right)| {
+                (
+                    left.map(|line| line.text.as_str()),
+                    right.map(|line| line.text.as_str()),
+                )
+            })
+            .collect();
+
+        assert_eq!(
+            texts,
+            vec![
+                (Some("a"), Some("a")),
+                (Some("b"), Some("B")),
+                (Some("c"), Some("C")),
+                (None, Some("X")),
+                (Some("d"), Some("d")),
+            ]
+        );
+    }
+}
@@ -0,0 +1,618 @@
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    fs::File,
+    path::{Path, PathBuf},
+    time::{Duration, Instant, SystemTime},
+};
+use tokio::sync::mpsc;
+use tokio_util::sync::CancellationToken;
+use walkdir::WalkDir;
+
+use crate::{
+    app::{AppState, actions::Action},
+    file_handling::walker::PROGRESS_INTERVAL,
+    utils,
+};
+
+/// Modification times within this tolerance are treated as equal,
+/// because some file systems (e.g. FAT) only store them with a 2 second resolution
+const MODIFIED_TOLERANCE: Duration = Duration::from_secs(2);
+
+/// Represents the difference of an entry between the left and the right directory tree
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum CompareStatus {
+    /// The entry exists only in the left tree
+    OnlyLeft,
+    /// The entry exists only in the right tree
+    OnlyRight,
+    /// Both files have the same size, but the left one was modified later
+    LeftNewer,
+    /// Both files have the same size, but the right one was modified later
+    RightNewer,
+    /// Both files have a different size
+    SizeDiffers,
+    /// The entry is a file on one side and a directory on the other side, can't be synchronized
+    TypeDiffers,
+}
+
+impl std::fmt::Display for CompareStatus {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            CompareStatus::OnlyLeft => write!(f, "Only left"),
+            CompareStatus::OnlyRight => write!(f, "Only right"),
+            CompareStatus::LeftNewer => write!(f, "Left newer"),
+            CompareStatus::RightNewer => write!(f, "Right newer"),
+            CompareStatus::SizeDiffers => write!(f, "Size differs"),
+            CompareStatus::TypeDiffers => write!(f, "Type differs"),
+        }
+    }
+}
+
+/// Represents the filters of the directory comparison
+#[derive(Default, Debug, Copy, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum CompareFilter {
+    #[default]
+    All,
+    OnlyLeft,
+    OnlyRight,
+    Newer,
+    SizeDiffers,
+}
+
+impl std::fmt::Display for CompareFilter {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            CompareFilter::All => write!(f, "All"),
+            CompareFilter::OnlyLeft => write!(f, "Only left"),
+            CompareFilter::OnlyRight => write!(f, "Only right"),
+            CompareFilter::Newer => write!(f, "Newer"),
+            CompareFilter::SizeDiffers => write!(f, "Size differs"),
+        }
+    }
+}
+
+impl CompareFilter {
+    /// Get the next available filter
+    pub fn next(self) -> Self {
+        match self {
+            CompareFilter::All => CompareFilter::OnlyLeft,
+            CompareFilter::OnlyLeft => CompareFilter::OnlyRight,
+            CompareFilter::OnlyRight => CompareFilter::Newer,
+            CompareFilter::Newer => CompareFilter::SizeDiffers,
+            CompareFilter::SizeDiffers => CompareFilter::All,
+        }
+    }
+
+    pub fn matches(&self, status: CompareStatus) -> bool {
+        match self {
+            CompareFilter::All => true,
+            CompareFilter::OnlyLeft => status == CompareStatus::OnlyLeft,
+            CompareFilter::OnlyRight => status == CompareStatus::OnlyRight,
+            CompareFilter::Newer => {
+                matches!(status, CompareStatus::LeftNewer | CompareStatus::RightNewer)
+            }
+            CompareFilter::SizeDiffers => status == CompareStatus::SizeDiffers,
+        }
+    }
+}
+
+/// Represents the direction of a one-way synchronization
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum SyncDirection {
+    LeftToRight,
+    RightToLeft,
+}
+
+impl std::fmt::Display for SyncDirection {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SyncDirection::LeftToRight => write!(f, "left → right"),
+            SyncDirection::RightToLeft => write!(f, "right → left"),
+        }
+    }
+}
+
+/// Size and modification time of an entry on one side of the comparison
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct EntryInfo {
+    pub is_dir: bool,
+    /// Size in bytes, always 0 for directories
+    pub size: u64,
+    pub modified: Option<SystemTime>,
+}
+
+/// Represents a entry which differs between both directory trees.<br>
+/// Directories that exist only on one side are listed once, without their content.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct CompareEntry {
+    /// Path relative to the compared directories
+    pub path: PathBuf,
+    pub status: CompareStatus,
+    pub left: Option<EntryInfo>,
+    pub right: Option<EntryInfo>,
+}
+
+impl CompareEntry {
+    pub fn is_dir(&self) -> bool {
+        self.left
+            .as_ref()
+            .or(self.right.as_ref())
+            .is_some_and(|info| info.is_dir)
+    }
+}
+
+/// Represents the result of a directory comparison
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq)]
+pub struct DirComparison {
+    pub left: PathBuf,
+    pub right: PathBuf,
+    /// The differing entries, sorted by their relative path
+    pub entries: Vec<CompareEntry>,
+}
+
+/// Represents a single copy operation of a synchronization
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct SyncOperation {
+    pub source: PathBuf,
+    pub target: PathBuf,
+    /// The relative path, used to display the operation
+    pub path: PathBuf,
+    pub is_dir: bool,
+    /// The target already exists and will be replaced
+    pub overwrite: bool,
+    /// The replaced target was modified later than the source, so its changes are lost
+    pub overwrites_newer: bool,
+}
+
+/// Collects all entries of the given directory tree by their relative path
+fn snapshot(
+    tx: &mpsc::UnboundedSender<Action>,
+    root: &Path,
+    follow_sym_links: bool,
+    cancellation_token: &CancellationToken,
+    entry_counter: &mut usize,
+    last_progress: &mut Instant,
+) -> Result<Option<BTreeMap<PathBuf, EntryInfo>>> {
+    let mut entries = BTreeMap::new();
+
+    for entry in WalkDir::new(root)
+        .follow_links(follow_sym_links)
+        .min_depth(1)
+        .into_iter()
+        .filter_map(Result::ok)
+    {
+        if cancellation_token.is_cancelled() {
+            return Ok(None);
+        }
+
+        let Ok(relative_path) = entry.path().strip_prefix(root) else {
+            continue;
+        };
+        let metadata = entry.metadata().ok();
+        let is_dir = entry.file_type().is_dir();
+
+        entries.insert(
+            relative_path.to_path_buf(),
+            EntryInfo {
+                is_dir,
+                size: if is_dir {
+                    0
+                } else {
+                    metadata.as_ref().map_or(0, |metadata| metadata.len())
+                },
+                modified: metadata.and_then(|metadata| metadata.modified().ok()),
+            },
+        );
+
+        *entry_counter += 1;
+        if last_progress.elapsed() >= PROGRESS_INTERVAL {
+            tx.send(Action::UpdateAppState(AppState::Working(format!(
+                "Comparing directories... {} entries scanned",
+                entry_counter
+            ))))?;
+            *last_progress = Instant::now();
+        }
+    }
+
+    Ok(Some(entries))
+}
+
+/// Compares the modification times of two files with the same size
+fn compare_modified(left: &EntryInfo, right: &EntryInfo) -> Option<CompareStatus> {
+    let (left_modified, right_modified) = (left.modified?, right.modified?);
+    match left_modified.duration_since(right_modified) {
+        Ok(diff) if diff > MODIFIED_TOLERANCE => Some(CompareStatus::LeftNewer),
+        Ok(_) => None,
+        Err(err) if err.duration() > MODIFIED_TOLERANCE => Some(CompareStatus::RightNewer),
+        Err(_) => None,
+    }
+}
+
+/// Compares an entry of both sides
+///
+/// # Returns
+/// `None`, if the entry is equal on both sides
+fn compare_status(left: Option<&EntryInfo>, right: Option<&EntryInfo>) -> Option<CompareStatus> {
+    match (left, right) {
+        (Some(_), None) => Some(CompareStatus::OnlyLeft),
+        (None, Some(_)) => Some(CompareStatus::OnlyRight),
+        (Some(l), Some(r)) if l.is_dir != r.is_dir => Some(CompareStatus::TypeDiffers),
+        (Some(l), Some(_)) if l.is_dir => None,
+        (Some(l), Some(r)) if l.size != r.size => Some(CompareStatus::SizeDiffers),
+        (Some(l), Some(r)) => compare_modified(l, r),
+        (None, None) => None,
+    }
+}
+
+/// Merges both snapshots into the list of differing entries
+fn compare_snapshots(
+    mut left: BTreeMap<PathBuf, EntryInfo>,
+    mut right: BTreeMap<PathBuf, EntryInfo>,
+) -> Vec<CompareEntry> {
+    let paths: BTreeSet<PathBuf> = left.keys().chain(right.keys()).cloned().collect();
+    let mut entries = vec![];
+    // the content of a directory that exists only on one side is not listed separately
+    let mut skipped_dir: Option<PathBuf> = None;
+
+    for path in paths {
+        if skipped_dir
+            .as_ref()
+            .is_some_and(|dir| path.starts_with(dir))
+        {
+            continue;
+        }
+
+        let left_info = left.remove(&path);
+        let right_info = right.remove(&path);
+
+        let Some(status) = compare_status(left_info.as_ref(), right_info.as_ref()) else {
+            continue;
+        };
+
+        if left_info
+            .iter()
+            .chain(right_info.iter())
+            .any(|info| info.is_dir)
+        {
+            skipped_dir = Some(path.clone());
+        }
+
+        entries.push(CompareEntry {
+            path,
+            status,
+            left: left_info,
+            right: right_info,
+        });
+    }
+
+    entries
+}
+
+/// Compares both directory trees.
+///
+/// # Returns
+/// `None`, if the comparison was cancelled
+pub fn compare_dirs(
+    tx: &mpsc::UnboundedSender<Action>,
+    left: &Path,
+    right: &Path,
+    follow_sym_links: bool,
+    cancellation_token: &CancellationToken,
+) -> Result<Option<DirComparison>> {
+    let mut entry_counter: usize = 0;
+    let mut last_progress = Instant::now();
+
+    let Some(left_entries) = snapshot(
+        tx,
+        left,
+        follow_sym_links,
+        cancellation_token,
+        &mut entry_counter,
+        &mut last_progress,
+    )?
+    else {
+        return Ok(None);
+    };
+    let Some(right_entries) = snapshot(
+        tx,
+        right,
+        follow_sym_links,
+        cancellation_token,
+        &mut entry_counter,
+        &mut last_progress,
+    )?
+    else {
+        return Ok(None);
+    };
+
+    Ok(Some(DirComparison {
+        left: left.to_path_buf(),
+        right: right.to_path_buf(),
+        entries: compare_snapshots(left_entries, right_entries),
+    }))
+}
+
+/// Plans the copy operations to synchronize the given entries in one direction.<br>
+/// Entries which don't exist on the source side are skipped, because a one-way sync never deletes anything.
+/// Equal entries are skipped too, they would only be copied again.
+pub fn plan_sync(
+    comparison: &DirComparison,
+    entries: &[&CompareEntry],
+    direction: SyncDirection,
+) -> Vec<SyncOperation> {
+    let (source_root, target_root) = match direction {
+        SyncDirection::LeftToRight => (&comparison.left, &comparison.right),
+        SyncDirection::RightToLeft => (&comparison.right, &comparison.left),
+    };
+
+    let target_newer = match direction {
+        SyncDirection::LeftToRight => CompareStatus::RightNewer,
+        SyncDirection::RightToLeft => CompareStatus::LeftNewer,
+    };
+
+    entries
+        .iter()
+        .filter_map(|entry| {
+            // compared again from the sizes and modification times, instead of trusting the listed status
+            let status = compare_status(entry.left.as_ref(), entry.right.as_ref())
+                .filter(|status| *status != CompareStatus::TypeDiffers)?;
+            let (source, target) = match direction {
+                SyncDirection::LeftToRight => (entry.left.as_ref()?, entry.right.as_ref()),
+                SyncDirection::RightToLeft => (entry.right.as_ref()?, entry.left.as_ref()),
+            };
+
+            Some(SyncOperation {
+                source: source_root.join(&entry.path),
+                target: target_root.join(&entry.path),
+                path: entry.path.clone(),
+                is_dir: source.is_dir,
+                overwrite: target.is_some(),
+                overwrites_newer: status == target_newer,
+            })
+        })
+        .collect()
+}
+
+/// Copies a file and keeps its modification time,
+/// so that a following comparison does not list it as newer
+pub fn copy_file(source: &Path, target: &Path) -> Result<()> {
+    if let Some(parent) = target.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    std::fs::copy(source, target)?;
+
+    let modified = source.metadata()?.modified()?;
+    File::options()
+        .write(true)
+        .open(target)?
+        .set_modified(modified)?;
+    Ok(())
+}
+
+/// Copies the directory with its whole content
+pub fn copy_dir(
+    source: &Path,
+    target: &Path,
+    cancellation_token: &CancellationToken,
+    errors: &mut Vec<String>,
+) -> Result<()> {
+    for entry in WalkDir::new(source) {
+        if cancellation_token.is_cancelled() {
+            return Ok(());
+        }
+
+        let entry = match entry {
+            Ok(entry) => entry,
+            Err(err) => {
+                log::error!(
+                    "Sync: Unable to read {} - Details {:?}",
+                    source.display(),
+                    err
+                );
+                errors.push(utils::format_path_for_display(err.path().unwrap_or(source)));
+                continue;
+            }
+        };
+        let Ok(relative_path) = entry.path().strip_prefix(source) else {
+            continue;
+        };
+        let target_path = target.join(relative_path);
+
+        let result = if entry.file_type().is_dir() {
+            std::fs::create_dir_all(&target_path).map_err(anyhow::Error::from)
+        } else {
+            copy_file(entry.path(), &target_path)
+        };
+
+        if let Err(err) = result {
+            log::error!(
+                "Sync: Unable to copy {} - Details {:?}",
+                entry.path().display(),
+                err
+            );
+            errors.push(utils::format_path_for_display(entry.path()));
+        }
+    }
+    Ok(())
+}
+
+/// Runs the given copy operations.
+///
+/// # Returns
+/// The number of successful operations and the paths which could not be copied,
+/// `None` if the synchronization was cancelled
+pub fn run_sync(
+    tx: &mpsc::UnboundedSender<Action>,
+    operations: &[SyncOperation],
+    cancellation_token: &CancellationToken,
+) -> Result<Option<(usize, Vec<String>)>> {
+    let mut copied: usize = 0;
+    let mut errors: Vec<String> = vec![];
+    let mut last_progress = Instant::now();
+
+    for (i, operation) in operations.iter().enumerate() {
+        if cancellation_token.is_cancelled() {
+            return Ok(None);
+        }
+
+        if last_progress.elapsed() >= PROGRESS_INTERVAL {
+            tx.send(Action::UpdateAppState(AppState::Working(format!(
+                "Synchronizing... {}/{}",
+                i,
+                operations.len()
+            ))))?;
+            last_progress = Instant::now();
+        }
+
+        let error_count = errors.len();
+        let result = if operation.is_dir {
+            copy_dir(
+                &operation.source,
+                &operation.target,
+                cancellation_token,
+                &mut errors,
+            )
+        } else {
+            copy_file(&operation.source, &operation.target)
+        };
+
+        match result {
+            Ok(_) if errors.len() == error_count => copied += 1,
+            Ok(_) => {}
+            Err(err) => {
+                log::error!(
+                    "Sync: Unable to copy {} - Details {:?}",
+                    operation.source.display(),
+                    err
+                );
+                errors.push(utils::format_path_for_display(&operation.source));
+            }
+        }
+    }
+
+    if cancellation_token.is_cancelled() {
+        return Ok(None);
+    }
+
+    Ok(Some((copied, errors)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn file(size: u64, modified_secs: u64) -> EntryInfo {
+        EntryInfo {
+            is_dir: false,
+            size,
+            modified: Some(SystemTime::UNIX_EPOCH + Duration::from_secs(modified_secs)),
+        }
+    }
+
+    fn dir() -> EntryInfo {
+        EntryInfo {
+            is_dir: true,
+            size: 0,
+            modified: None,
+        }
+    }
+
+    #[test]
+    fn test_compare_snapshots() {
+        let left = BTreeMap::from([
+            (PathBuf::from("same.txt"), file(10, 100)),
+            (PathBuf::from("newer.txt"), file(10, 200)),
+            (PathBuf::from("size.txt"), file(10, 100)),
+            (PathBuf::from("left_dir"), dir()),
+            (PathBuf::from("left_dir/a.txt"), file(1, 100)),
+            (PathBuf::from("conflict"), dir()),
+        ]);
+        let right = BTreeMap::from([
+            (PathBuf::from("same.txt"), file(10, 101)),
+            (PathBuf::from("newer.txt"), file(10, 100)),
+            (PathBuf::from("size.txt"), file(20, 100)),
+            (PathBuf::from("right.txt"), file(1, 100)),
+            (PathBuf::from("conflict"), file(1, 100)),
+        ]);
+
+        let statuses: Vec<(String, CompareStatus)> = compare_snapshots(left, right)
+            .into_iter()
+            .map(|entry| (entry.path.to_string_lossy().to_string(), entry.status))
+            .collect();
+
+        assert_eq!(
+            statuses,
+            vec![
+                ("conflict".to_string(), CompareStatus::TypeDiffers),
+                ("left_dir".to_string(), CompareStatus::OnlyLeft),
+                ("newer.txt".to_string(), CompareStatus::LeftNewer),
+                ("right.txt".to_string(), CompareStatus::OnlyRight),
+                ("size.txt".to_string(), CompareStatus::SizeDiffers),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_plan_sync_skips_missing_sources() {
+        let comparison = DirComparison {
+            left: PathBuf::from("/left"),
+            right: PathBuf::from("/right"),
+            entries: compare_snapshots(
+                BTreeMap::from([
+                    (PathBuf::from("a.txt"), file(1, 100)),
+                    (PathBuf::from("b.txt"), file(1, 100)),
+                    (PathBuf::from("d.txt"), file(1, 100)),
+                ]),
+                BTreeMap::from([
+                    (PathBuf::from("b.txt"), file(2, 100)),
+                    (PathBuf::from("c.txt"), file(1, 100)),
+                    (PathBuf::from("d.txt"), file(1, 200)),
+                ]),
+            ),
+        };
+        let entries: Vec<&CompareEntry> = comparison.entries.iter().collect();
+
+        let operations = plan_sync(&comparison, &entries, SyncDirection::LeftToRight);
+        assert_eq!(operations.len(), 3);
+        assert_eq!(operations[0].target, PathBuf::from("/right/a.txt"));
+        assert!(!operations[0].overwrite);
+        assert_eq!(operations[1].source, PathBuf::from("/left/b.txt"));
+        assert!(operations[1].overwrite);
+        assert!(!operations[1].overwrites_newer);
+        // the right file was modified later and is overwritten with the older one
+        assert_eq!(operations[2].path, PathBuf::from("d.txt"));
+        assert!(operations[2].overwrites_newer);
+
+        let operations = plan_sync(&comparison, &entries, SyncDirection::RightToLeft);
+        let paths: Vec<&PathBuf> = operations.iter().map(|op| &op.path).collect();
+        assert_eq!(
+            paths,
+            vec![
+                &PathBuf::from("b.txt"),
+                &PathBuf::from("c.txt"),
+                &PathBuf::from("d.txt")
+            ]
+        );
+        assert!(operations.iter().all(|op| !op.overwrites_newer));
+    }
+
+    #[test]
+    fn test_plan_sync_skips_equal_entries() {
+        let comparison = DirComparison {
+            left: PathBuf::from("/left"),
+            right: PathBuf::from("/right"),
+            entries: vec![],
+        };
+        // an entry, that is equal on both sides, e.g. after it was synchronized
+        let equal = CompareEntry {
+            path: PathBuf::from("same.txt"),
+            status: CompareStatus::LeftNewer,
+            left: Some(file(10, 100)),
+            right: Some(file(10, 101)),
+        };
+
+        assert!(plan_sync(&comparison, &[&equal], SyncDirection::LeftToRight).is_empty());
+    }
+}
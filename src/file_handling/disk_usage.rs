@@ -0,0 +1,292 @@
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::HashMap,
+    path::{Path, PathBuf},
+    time::Instant,
+};
+use tokio::sync::mpsc;
+use tokio_util::sync::CancellationToken;
+use walkdir::WalkDir;
+
+use crate::{
+    app::{AppState, actions::Action},
+    file_handling::walker::PROGRESS_INTERVAL,
+    utils,
+};
+
+/// Represents a direct child of an analyzed directory
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct UsageEntry {
+    pub name: String,
+    pub path: PathBuf,
+    /// Size in bytes, for directories the recursive size of all contained files
+    pub size: u64,
+    pub is_dir: bool,
+    /// Number of recursively contained files and directories, always 0 for files
+    pub item_count: u64,
+}
+
+/// Represents the disk usage of a directory and its direct children, sorted by size (largest first)
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq)]
+pub struct DirUsage {
+    pub path: PathBuf,
+    pub size: u64,
+    pub item_count: u64,
+    pub entries: Vec<UsageEntry>,
+}
+
+impl DirUsage {
+    fn new(path: PathBuf, mut entries: Vec<UsageEntry>) -> Self {
+        Self::sort_entries(&mut entries);
+        Self {
+            path,
+            size: entries.iter().map(|entry| entry.size).sum(),
+            item_count: entries.iter().map(|entry| entry.item_count + 1).sum(),
+            entries,
+        }
+    }
+
+    fn sort_entries(entries: &mut [UsageEntry]) {
+        entries.sort_by(|e1, e2| e2.size.cmp(&e1.size).then_with(|| e1.name.cmp(&e2.name)));
+    }
+}
+
+/// Cache of all analyzed directories, so that drilling down into a child
+/// or returning to a parent does not require a new walk of the file system
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq)]
+pub struct DiskUsageCache {
+    dirs: HashMap<PathBuf, DirUsage>,
+}
+
+impl DiskUsageCache {
+    /// Recursively analyzes the disk usage of the given directory and all of its subdirectories.
+    ///
+    /// The directory tree is walked only once, the children are visited before their parent directory,
+    /// so that the size of each directory is the sum of its already analyzed children.
+    ///
+    /// # Returns
+    /// `None`, if the analysis was cancelled or the directory could not be read
+    pub fn analyze(
+        tx: &mpsc::UnboundedSender<Action>,
+        root: &Path,
+        follow_sym_links: bool,
+        cancellation_token: &CancellationToken,
+    ) -> Result<Option<Self>> {
+        let mut children: HashMap<PathBuf, Vec<UsageEntry>> = HashMap::new();
+        let mut dirs: HashMap<PathBuf, DirUsage> = HashMap::new();
+        let mut total_size: u64 = 0;
+        let mut entry_counter: u64 = 0;
+        let mut last_progress = Instant::now();
+
+        for entry in WalkDir::new(root)
+            .follow_links(follow_sym_links)
+            .contents_first(true)
+            .into_iter()
+            .filter_map(Result::ok)
+        {
+            if cancellation_token.is_cancelled() {
+                return Ok(None);
+            }
+
+            let path = entry.path().to_path_buf();
+            let name = entry.file_name().to_string_lossy().to_string();
+
+            let usage_entry = if entry.file_type().is_dir() {
+                let dir_usage =
+                    DirUsage::new(path.clone(), children.remove(&path).unwrap_or_default());
+                let usage_entry = UsageEntry {
+                    name,
+                    path: path.clone(),
+                    size: dir_usage.size,
+                    is_dir: true,
+                    item_count: dir_usage.item_count,
+                };
+                dirs.insert(path.clone(), dir_usage);
+                usage_entry
+            } else {
+                let size = entry.metadata().map(|metadata| metadata.len()).unwrap_or(0);
+                total_size += size;
+                UsageEntry {
+                    name,
+                    path: path.clone(),
+                    size,
+                    is_dir: false,
+                    item_count: 0,
+                }
+            };
+
+            // the root directory has no parent within the analyzed tree
+            if entry.depth() > 0
+                && let Some(parent) = path.parent()
+            {
+                children
+                    .entry(parent.to_path_buf())
+                    .or_default()
+                    .push(usage_entry);
+            }
+
+            entry_counter += 1;
+            if last_progress.elapsed() >= PROGRESS_INTERVAL {
+                tx.send(Action::UpdateAppState(AppState::Working(format!(
+                    "Analyzing disk usage... {} entries, {}",
+                    entry_counter,
+                    utils::convert_bytes_to_human_readable(total_size)
+                ))))?;
+                last_progress = Instant::now();
+            }
+        }
+
+        if dirs.contains_key(root) {
+            Ok(Some(Self { dirs }))
+        } else {
+            Ok(None)
+        }
+    }
+
+    pub fn get(&self, path: &Path) -> Option<&DirUsage> {
+        self.dirs.get(path)
+    }
+
+    /// Adds all analyzed directories of the other cache, already cached directories are replaced
+    pub fn extend(&mut self, other: DiskUsageCache) {
+        self.dirs.extend(other.dirs);
+    }
+
+    /// Removes a deleted file or directory from the cache.<br>
+    /// The size and item count of all cached parent directories are reduced accordingly.
+    pub fn remove(&mut self, path: &Path) {
+        let removed = path
+            .parent()
+            .and_then(|parent| self.dirs.get(parent))
+            .and_then(|parent| parent.entries.iter().find(|entry| entry.path == path))
+            .map(|entry| (entry.size, entry.item_count + 1));
+
+        if let Some((size, item_count)) = removed {
+            let mut child = path;
+            for dir in path.ancestors().skip(1) {
+                if let Some(dir_usage) = self.dirs.get_mut(dir) {
+                    if child == path {
+                        dir_usage.entries.retain(|entry| entry.path != path);
+                    } else if let Some(entry) = dir_usage
+                        .entries
+                        .iter_mut()
+                        .find(|entry| entry.path == child)
+                    {
+                        entry.size = entry.size.saturating_sub(size);
+                        entry.item_count = entry.item_count.saturating_sub(item_count);
+                    }
+                    dir_usage.size = dir_usage.size.saturating_sub(size);
+                    dir_usage.item_count = dir_usage.item_count.saturating_sub(item_count);
+                    DirUsage::sort_entries(&mut dir_usage.entries);
+                }
+                child = dir;
+            }
+        }
+
+        self.dirs.retain(|dir, _| !dir.starts_with(path));
+    }
+}
+
+/// Permanently deletes the given file or directory (including its content)
+pub fn delete_path(path: &Path) -> Result<()> {
+    if path.is_dir() && !path.is_symlink() {
+        std::fs::remove_dir_all(path)?;
+    } else {
+        std::fs::remove_file(path)?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn file(path: &str, size: u64) -> UsageEntry {
+        UsageEntry {
+            name: Path::new(path)
+                .file_name()
+                .unwrap()
+                .to_string_lossy()
+                .to_string(),
+            path: PathBuf::from(path),
+            size,
+            is_dir: false,
+            item_count: 0,
+        }
+    }
+
+    fn dir(usage: &DirUsage) -> UsageEntry {
+        UsageEntry {
+            name: usage
+                .path
+                .file_name()
+                .unwrap()
+                .to_string_lossy()
+                .to_string(),
+            path: usage.path.clone(),
+            size: usage.size,
+            is_dir: true,
+            item_count: usage.item_count,
+        }
+    }
+
+    /// /root
+    /// ├── a.txt (10)
+    /// └── sub
+    ///     ├── b.txt (30)
+    ///     └── c.txt (60)
+    fn cache() -> DiskUsageCache {
+        let sub = DirUsage::new(
+            PathBuf::from("/root/sub"),
+            vec![file("/root/sub/b.txt", 30), file("/root/sub/c.txt", 60)],
+        );
+        let root = DirUsage::new(
+            PathBuf::from("/root"),
+            vec![file("/root/a.txt", 10), dir(&sub)],
+        );
+
+        DiskUsageCache {
+            dirs: HashMap::from([(sub.path.clone(), sub), (root.path.clone(), root)]),
+        }
+    }
+
+    #[test]
+    fn test_dir_usage_sorted_by_size() {
+        let cache = cache();
+        let root = cache.get(Path::new("/root")).unwrap();
+
+        assert_eq!(root.size, 100);
+        assert_eq!(root.item_count, 4);
+        assert_eq!(root.entries[0].name, "sub");
+        assert_eq!(root.entries[1].name, "a.txt");
+    }
+
+    #[test]
+    fn test_remove_file_updates_parents() {
+        let mut cache = cache();
+        cache.remove(Path::new("/root/sub/c.txt"));
+
+        let sub = cache.get(Path::new("/root/sub")).unwrap();
+        assert_eq!(sub.size, 30);
+        assert_eq!(sub.item_count, 1);
+
+        let root = cache.get(Path::new("/root")).unwrap();
+        assert_eq!(root.size, 40);
+        assert_eq!(root.item_count, 3);
+        assert_eq!(root.entries[0].size, 30);
+    }
+
+    #[test]
+    fn test_remove_dir_drops_cached_subtree() {
+        let mut cache = cache();
+        cache.remove(Path::new("/root/sub"));
+
+        assert!(cache.get(Path::new("/root/sub")).is_none());
+
+        let root = cache.get(Path::new("/root")).unwrap();
+        assert_eq!(root.size, 10);
+        assert_eq!(root.item_count, 1);
+        assert_eq!(root.entries.len(), 1);
+    }
+}
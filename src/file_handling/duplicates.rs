@@ -0,0 +1,403 @@
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::{HashMap, HashSet},
+    fs::File,
+    io::Read,
+    ops::Range,
+    path::{Path, PathBuf},
+    time::Instant,
+};
+use tokio::sync::mpsc;
+use tokio_util::sync::CancellationToken;
+
+use crate::{
+    app::{AppState, actions::Action},
+    file_handling::{
+        DiskEntry, SearchResult, read_file_metadata,
+        search::SearchQuery,
+        walker::{PROGRESS_INTERVAL, walk_sequential},
+    },
+    utils,
+};
+
+/// Number of bytes at the beginning of a file, used to quickly exclude files with the same size
+const PARTIAL_HASH_SIZE: u64 = 4096;
+
+/// Represents a group of files with identical content.<br>
+/// The files of a group are stored contiguously in the items of the [`SearchResult`].
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+pub struct DuplicateGroup {
+    /// Index of the first file of the group in the search result items
+    pub start: usize,
+    /// Number of files in the group
+    pub len: usize,
+    /// Size of each file in bytes
+    pub size: u64,
+}
+
+impl DuplicateGroup {
+    /// Space that can be freed, if only one copy is kept
+    pub fn reclaimable(&self) -> u64 {
+        self.size * (self.len as u64).saturating_sub(1)
+    }
+
+    pub fn range(&self) -> Range<usize> {
+        self.start..self.start + self.len
+    }
+}
+
+/// How the redundant copies of a duplicate group should be resolved
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+pub enum DuplicateResolution {
+    /// Move the redundant copies to the trash
+    Trash,
+    /// Replace the redundant copies with hard links to the kept copy
+    HardLink,
+}
+
+impl SearchResult {
+    pub fn duplicate_groups(&self) -> &[DuplicateGroup] {
+        &self.duplicate_groups
+    }
+
+    /// Returns the group number and the group that contains the item with the given index, if any
+    pub fn duplicate_group_of(&self, index: usize) -> Option<(usize, &DuplicateGroup)> {
+        self.duplicate_groups
+            .iter()
+            .enumerate()
+            .find(|(_, group)| group.range().contains(&index))
+    }
+
+    /// Total space that can be freed, if only one copy of each group is kept
+    pub fn reclaimable_space(&self) -> u64 {
+        self.duplicate_groups
+            .iter()
+            .map(DuplicateGroup::reclaimable)
+            .sum()
+    }
+
+    /// Removes the resolved files from the result.<br>
+    /// Groups with less than two remaining files are no duplicates anymore and are removed completely.
+    pub fn remove_resolved_duplicates(&mut self, resolved: &[PathBuf]) {
+        let resolved: HashSet<&PathBuf> = resolved.iter().collect();
+        let mut items = Vec::with_capacity(self.items.len());
+        let mut groups = Vec::with_capacity(self.duplicate_groups.len());
+
+        for group in &self.duplicate_groups {
+            let remaining: Vec<DiskEntry> = self.items[group.range()]
+                .iter()
+                .filter(|entry| !resolved.contains(&entry.path))
+                .cloned()
+                .collect();
+
+            if remaining.len() >= 2 {
+                groups.push(DuplicateGroup {
+                    start: items.len(),
+                    len: remaining.len(),
+                    size: group.size,
+                });
+                items.extend(remaining);
+            }
+        }
+
+        self.items = items;
+        self.duplicate_groups = groups;
+        self.selected = self.selected.min(self.items.len().saturating_sub(1));
+        self.start_index = self.start_index.min(self.selected);
+    }
+}
+
+/// Finds all files with identical content in the directory of the given query.
+///
+/// To stay fast, the files are compared in three stages, each stage only processes the candidates of the previous one:
+/// 1. Group by file size
+/// 2. Group by the hash of the first bytes
+/// 3. Group by the hash of the whole content
+///
+/// # Returns
+/// `None`, if no duplicates were found or the search was cancelled
+pub fn find_duplicates(
+    tx: &mpsc::UnboundedSender<Action>,
+    query: &SearchQuery,
+    cancellation_token: &CancellationToken,
+) -> Result<Option<SearchResult>> {
+    let matcher = query.name_matcher()?;
+    let filter = query.filters.compile(query.case_sensitive)?;
+    let mut by_size: HashMap<u64, Vec<DiskEntry>> = HashMap::new();
+    // files that are already hard links of each other don't take up additional space
+    #[cfg(unix)]
+    let mut seen_inodes: HashSet<(u64, u64)> = HashSet::new();
+    let mut file_counter: usize = 0;
+    let mut last_progress = Instant::now();
+
+    for entry in walk_sequential(query)?
+        .filter_map(Result::ok)
+        .filter(|entry| entry.file_type().is_file())
+    {
+        if cancellation_token.is_cancelled() {
+            return Ok(None);
+        }
+
+        file_counter += 1;
+        if last_progress.elapsed() >= PROGRESS_INTERVAL {
+            tx.send(Action::UpdateAppState(AppState::Working(format!(
+                "Searching duplicates... {} Files scanned",
+                file_counter
+            ))))?;
+            last_progress = Instant::now();
+        }
+
+        let name = entry.file_name().to_string_lossy().to_string();
+        if !matcher.is_match(&name) || !filter.matches(&entry) {
+            continue;
+        }
+
+        let Some(file_metadata) = read_file_metadata(&entry) else {
+            continue;
+        };
+
+        // empty files are all equal, but there is nothing to reclaim
+        if file_metadata.size == 0 {
+            continue;
+        }
+
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::MetadataExt;
+            if let Ok(metadata) = entry.metadata()
+                && !seen_inodes.insert((metadata.dev(), metadata.ino()))
+            {
+                continue;
+            }
+        }
+
+        by_size
+            .entry(file_metadata.size)
+            .or_default()
+            .push(DiskEntry {
+                name,
+                path: entry.path().to_path_buf(),
+                file_metadata: Some(file_metadata),
+                is_dir: false,
+            });
+    }
+
+    let candidates: Vec<(u64, Vec<DiskEntry>)> = by_size
+        .into_iter()
+        .filter(|(_, entries)| entries.len() >= 2)
+        .collect();
+
+    let candidate_counter: usize = candidates.iter().map(|(_, entries)| entries.len()).sum();
+    let mut hashed_counter: usize = 0;
+    let mut groups: Vec<(u64, Vec<DiskEntry>)> = vec![];
+
+    for (size, entries) in candidates {
+        // for small files the partial hash already covers the whole content
+        let mut stages = vec![Some(PARTIAL_HASH_SIZE)];
+        if size > PARTIAL_HASH_SIZE {
+            stages.push(None);
+        }
+
+        let mut current_groups = vec![entries];
+
+        for limit in stages {
+            let mut next_groups = vec![];
+
+            for group in current_groups {
+                let mut by_hash: HashMap<blake3::Hash, Vec<DiskEntry>> = HashMap::new();
+
+                for entry in group {
+                    if cancellation_token.is_cancelled() {
+                        return Ok(None);
+                    }
+
+                    // unreadable files are ignored
+                    if let Ok(hash) = hash_file(&entry.path, limit) {
+                        by_hash.entry(hash).or_default().push(entry);
+                    }
+                }
+
+                next_groups.extend(by_hash.into_values().filter(|entries| entries.len() >= 2));
+            }
+
+            current_groups = next_groups;
+        }
+
+        hashed_counter += current_groups.iter().map(Vec::len).sum::<usize>();
+        groups.extend(current_groups.into_iter().map(|entries| (size, entries)));
+
+        if last_progress.elapsed() >= PROGRESS_INTERVAL {
+            tx.send(Action::UpdateAppState(AppState::Working(format!(
+                "Comparing file contents... {} candidates, {} duplicates",
+                candidate_counter, hashed_counter
+            ))))?;
+            last_progress = Instant::now();
+        }
+    }
+
+    if groups.is_empty() {
+        return Ok(None);
+    }
+
+    // the groups with the most reclaimable space first
+    groups.sort_by(|(size1, entries1), (size2, entries2)| {
+        let reclaimable1 = size1 * (entries1.len() as u64 - 1);
+        let reclaimable2 = size2 * (entries2.len() as u64 - 1);
+        reclaimable2.cmp(&reclaimable1)
+    });
+
+    let mut items = vec![];
+    let mut duplicate_groups = vec![];
+
+    for (size, mut entries) in groups {
+        entries.sort_by(|e1, e2| e1.path.cmp(&e2.path));
+        duplicate_groups.push(DuplicateGroup {
+            start: items.len(),
+            len: entries.len(),
+            size,
+        });
+        items.extend(entries);
+    }
+
+    Ok(Some(SearchResult {
+        items,
+        duplicate_groups,
+        ..SearchResult::for_query(query)
+    }))
+}
+
+/// Computes the hash of the file content, optionally only of the first bytes
+fn hash_file(path: &Path, limit: Option<u64>) -> std::io::Result<blake3::Hash> {
+    let mut file = File::open(path)?;
+    let mut hasher = blake3::Hasher::new();
+
+    match limit {
+        Some(limit) => std::io::copy(&mut (&mut file).take(limit), &mut hasher)?,
+        None => std::io::copy(&mut file, &mut hasher)?,
+    };
+
+    Ok(hasher.finalize())
+}
+
+/// Resolves the redundant copies of a duplicate group, the file to keep is not modified.
+///
+/// # Returns
+/// The successfully resolved files and a error message for each file that could not be resolved
+pub fn resolve_duplicates(
+    keep: &Path,
+    others: &[PathBuf],
+    resolution: DuplicateResolution,
+) -> (Vec<PathBuf>, Vec<String>) {
+    let mut resolved = vec![];
+    let mut errors = vec![];
+
+    for other in others.iter().filter(|other| other.as_path() != keep) {
+        let result = match resolution {
+            DuplicateResolution::Trash => trash::delete(other).map_err(anyhow::Error::from),
+            DuplicateResolution::HardLink => replace_with_hard_link(keep, other),
+        };
+
+        match result {
+            Ok(_) => resolved.push(other.clone()),
+            Err(err) => {
+                log::error!(
+                    "Unable to resolve duplicate {} - Details {:?}",
+                    other.display(),
+                    err
+                );
+                errors.push(format!(
+                    "{}: {}",
+                    utils::format_path_for_display(other),
+                    err
+                ));
+            }
+        }
+    }
+
+    (resolved, errors)
+}
+
+/// Replaces the target with a hard link to the source.<br>
+/// The link is created next to the target first and then renamed, so the target is never lost if linking fails,
+/// e.g. because both files are located on different file systems.
+fn replace_with_hard_link(source: &Path, target: &Path) -> Result<()> {
+    let file_name = target
+        .file_name()
+        .map(|name| name.to_string_lossy().to_string())
+        .unwrap_or_default();
+    let tmp_link = target.with_file_name(format!(".{}.{}-link", file_name, utils::app_name()));
+
+    std::fs::hard_link(source, &tmp_link)?;
+    if let Err(err) = std::fs::rename(&tmp_link, target) {
+        let _ = std::fs::remove_file(&tmp_link);
+        return Err(err.into());
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(path: &str) -> DiskEntry {
+        DiskEntry {
+            name: path.to_string(),
+            path: PathBuf::from(path),
+            file_metadata: None,
+            is_dir: false,
+        }
+    }
+
+    fn result() -> SearchResult {
+        SearchResult {
+            items: vec![
+                entry("a1"),
+                entry("a2"),
+                entry("a3"),
+                entry("b1"),
+                entry("b2"),
+            ],
+            duplicate_groups: vec![
+                DuplicateGroup {
+                    start: 0,
+                    len: 3,
+                    size: 100,
+                },
+                DuplicateGroup {
+                    start: 3,
+                    len: 2,
+                    size: 10,
+                },
+            ],
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_reclaimable_space() {
+        let result = result();
+
+        assert_eq!(result.reclaimable_space(), 210);
+        assert_eq!(result.duplicate_group_of(4).map(|(i, _)| i), Some(1));
+    }
+
+    #[test]
+    fn test_remove_resolved_duplicates() {
+        let mut result = result();
+        result.remove_resolved_duplicates(&[PathBuf::from("a2"), PathBuf::from("b1")]);
+
+        // the second group has only one file left and is removed
+        assert_eq!(result.items().len(), 2);
+        assert_eq!(
+            result.duplicate_groups(),
+            &[DuplicateGroup {
+                start: 0,
+                len: 2,
+                size: 100
+            }]
+        );
+        assert_eq!(result.items()[1].path, PathBuf::from("a3"));
+    }
+}
@@ -0,0 +1,428 @@
+use anyhow::{Result, anyhow, bail};
+use chrono::{Local, NaiveDate, TimeZone};
+use file_format::FileFormat;
+use serde::{Deserialize, Serialize};
+use std::time::{Duration, SystemTime};
+
+use crate::file_handling::{
+    format_search::FormatMatcher,
+    reports::parse_duration,
+    search::{NameMatcher, QueryMode},
+    walker::WalkEntry,
+};
+
+/// Keys of the filters, that can be combined with the search query, e.g. `size:>100M`
+const FILTER_KEYS: [&str; 7] = ["name", "size", "modified", "type", "ext", "format", "owner"];
+
+const ONE_DAY: Duration = Duration::from_secs(24 * 60 * 60);
+
+/// How the size of an entry is compared with the size of the filter
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Comparison {
+    Less,
+    Equal,
+    Greater,
+}
+
+/// The kind of entries, that are accepted by the `type:` filter
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum EntryKind {
+    File,
+    Dir,
+    Symlink,
+}
+
+/// Time range of the `modified:` filter, both bounds are exclusive
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TimeRange {
+    pub after: Option<SystemTime>,
+    pub before: Option<SystemTime>,
+}
+
+impl TimeRange {
+    fn contains(&self, time: SystemTime) -> bool {
+        self.after.is_none_or(|after| time > after)
+            && self.before.is_none_or(|before| time < before)
+    }
+}
+
+/// Represents the filters entered together with the search query.<br>
+/// All filters must match, so that an entry is part of the search result.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SearchFilters {
+    /// Glob pattern, that must match the whole name
+    pub name: Option<String>,
+    pub size: Option<(Comparison, u64)>,
+    pub modified: Option<TimeRange>,
+    pub kind: Option<EntryKind>,
+    /// Accepted file extensions in lowercase, without the leading dot
+    pub extensions: Vec<String>,
+    /// File formats detected by the content, e.g. `pdf`, `image` or `video/*`
+    pub format: Option<String>,
+    /// User id of the owner
+    pub owner: Option<u32>,
+}
+
+impl SearchFilters {
+    pub fn is_empty(&self) -> bool {
+        *self == SearchFilters::default()
+    }
+
+    /// Compiles the filters, so they can be applied to the entries of the directory walk
+    pub fn compile(&self, case_sensitive: bool) -> Result<EntryFilter> {
+        let name = self
+            .name
+            .as_deref()
+            .map(|name| NameMatcher::new(name, QueryMode::Glob, case_sensitive))
+            .transpose()?;
+        let format = self.format.as_deref().map(FormatMatcher::new).transpose()?;
+
+        Ok(EntryFilter {
+            filters: self.clone(),
+            name,
+            format,
+        })
+    }
+}
+
+/// The compiled [`SearchFilters`]
+#[derive(Debug, Clone)]
+pub struct EntryFilter {
+    filters: SearchFilters,
+    name: Option<NameMatcher>,
+    format: Option<FormatMatcher>,
+}
+
+impl EntryFilter {
+    /// Checks all filters, the cheap checks first and the detection of the file format last
+    pub fn matches<E: WalkEntry>(&self, entry: &E) -> bool {
+        let filters = &self.filters;
+        if filters.is_empty() {
+            return true;
+        }
+
+        let file_type = entry.file_type();
+        let is_file = file_type.is_some_and(|file_type| file_type.is_file());
+
+        let kind_matches = match filters.kind {
+            Some(EntryKind::File) => is_file,
+            Some(EntryKind::Dir) => file_type.is_some_and(|file_type| file_type.is_dir()),
+            Some(EntryKind::Symlink) => entry.path_is_symlink(),
+            None => true,
+        };
+        if !kind_matches {
+            return false;
+        }
+
+        if let Some(name) = &self.name
+            && !name.is_match(&entry.file_name().to_string_lossy())
+        {
+            return false;
+        }
+
+        if !filters.extensions.is_empty() {
+            let extension = entry
+                .path()
+                .extension()
+                .map(|extension| extension.to_string_lossy().to_lowercase());
+            if !is_file
+                || extension.is_none_or(|extension| !filters.extensions.contains(&extension))
+            {
+                return false;
+            }
+        }
+
+        if filters.size.is_some() || filters.modified.is_some() || filters.owner.is_some() {
+            let Some(metadata) = entry.metadata() else {
+                return false;
+            };
+
+            if let Some((comparison, size)) = filters.size {
+                let size_matches = match comparison {
+                    Comparison::Less => metadata.len() < size,
+                    Comparison::Equal => metadata.len() == size,
+                    Comparison::Greater => metadata.len() > size,
+                };
+                if !is_file || !size_matches {
+                    return false;
+                }
+            }
+
+            if let Some(range) = filters.modified
+                && metadata
+                    .modified()
+                    .is_ok_and(|modified| !range.contains(modified))
+            {
+                return false;
+            }
+
+            #[cfg(unix)]
+            if let Some(owner) = filters.owner {
+                use std::os::unix::fs::MetadataExt;
+                if metadata.uid() != owner {
+                    return false;
+                }
+            }
+        }
+
+        if let Some(format) = &self.format {
+            return is_file
+                && FileFormat::from_file(entry.path())
+                    .is_ok_and(|file_format| format.matches(&file_format));
+        }
+
+        true
+    }
+}
+
+/// Splits the text entered on the Search-Page into the search query and the filters.<br>
+/// Words like `size:>100M` are parsed as filter, all other words form the search query.
+pub fn parse_query(text: &str) -> Result<(String, SearchFilters)> {
+    let mut filters = SearchFilters::default();
+    let mut used_keys = vec![];
+    let mut words = vec![];
+
+    for word in text.split_whitespace() {
+        let Some((key, value)) = word
+            .split_once(':')
+            .filter(|(key, _)| FILTER_KEYS.contains(&key.to_lowercase().as_str()))
+        else {
+            words.push(word);
+            continue;
+        };
+
+        let key = key.to_lowercase();
+        if used_keys.contains(&key) {
+            bail!("The filter {key}: is used more than once");
+        }
+        if value.is_empty() {
+            bail!("The filter {key}: requires a value");
+        }
+
+        match key.as_str() {
+            "name" => {
+                // compile the glob pattern, to report an invalid pattern before the search starts
+                NameMatcher::new(value, QueryMode::Glob, false)?;
+                filters.name = Some(value.to_string());
+            }
+            "size" => filters.size = Some(parse_size_filter(value)?),
+            "modified" => filters.modified = Some(parse_modified_filter(value)?),
+            "type" => filters.kind = Some(parse_kind_filter(value)?),
+            "ext" => filters.extensions = parse_extension_filter(value)?,
+            "format" => {
+                // the formats are separated by commas, e.g. `format:pdf,image`
+                FormatMatcher::new(value)?;
+                filters.format = Some(value.to_lowercase());
+            }
+            "owner" => filters.owner = Some(parse_owner_filter(value)?),
+            _ => unreachable!("unknown filter key"),
+        }
+        used_keys.push(key);
+    }
+
+    Ok((words.join(" "), filters))
+}
+
+/// Splits the comparison operator from the value, no operator means equal
+fn split_comparison(value: &str) -> (Comparison, &str) {
+    if let Some(value) = value.strip_prefix('<') {
+        (Comparison::Less, value)
+    } else if let Some(value) = value.strip_prefix('>') {
+        (Comparison::Greater, value)
+    } else {
+        (Comparison::Equal, value.strip_prefix('=').unwrap_or(value))
+    }
+}
+
+/// Parses a size like `>100M`, `<1.5G` or `0`, the units are based on 1024
+fn parse_size_filter(value: &str) -> Result<(Comparison, u64)> {
+    let (comparison, size) = split_comparison(value);
+    let size = size.to_lowercase();
+    let number_end = size
+        .find(|c: char| !c.is_ascii_digit() && c != '.')
+        .unwrap_or(size.len());
+    let (number, unit) = size.split_at(number_end);
+
+    let factor: u64 = match unit {
+        "" | "b" => 1,
+        "k" | "kb" | "kib" => 1 << 10,
+        "m" | "mb" | "mib" => 1 << 20,
+        "g" | "gb" | "gib" => 1 << 30,
+        "t" | "tb" | "tib" => 1 << 40,
+        _ => bail!("Invalid size filter size:{value}, use e.g. size:>100M"),
+    };
+
+    match number.parse::<f64>() {
+        Ok(number) if number >= 0.0 => Ok((comparison, (number * factor as f64) as u64)),
+        _ => bail!("Invalid size filter size:{value}, use e.g. size:>100M"),
+    }
+}
+
+/// Parses a time span relative to now like `<7d` (modified within the last 7 days) or `>12h`,
+/// or a date like `>2024-01-31`, `<2024-01-31` or `2024-01-31` (modified on this day)
+fn parse_modified_filter(value: &str) -> Result<TimeRange> {
+    let (comparison, time) = split_comparison(value);
+    let invalid = || {
+        anyhow!(
+            "Invalid modified filter modified:{value}, use e.g. modified:<7d or modified:>2024-01-31"
+        )
+    };
+
+    if let Some(duration) = parse_duration(time) {
+        let reference = SystemTime::now()
+            .checked_sub(duration)
+            .ok_or_else(invalid)?;
+        return Ok(match comparison {
+            // older than the time span
+            Comparison::Greater => TimeRange {
+                before: Some(reference),
+                ..Default::default()
+            },
+            // within the time span
+            Comparison::Less | Comparison::Equal => TimeRange {
+                after: Some(reference),
+                ..Default::default()
+            },
+        });
+    }
+
+    let date = NaiveDate::parse_from_str(time, "%Y-%m-%d").map_err(|_| invalid())?;
+    let start_of_day: SystemTime = date
+        .and_hms_opt(0, 0, 0)
+        .and_then(|datetime| Local.from_local_datetime(&datetime).earliest())
+        .ok_or_else(invalid)?
+        .into();
+    let end_of_day = start_of_day + ONE_DAY;
+
+    Ok(match comparison {
+        Comparison::Less => TimeRange {
+            before: Some(start_of_day),
+            ..Default::default()
+        },
+        Comparison::Greater => TimeRange {
+            after: Some(end_of_day - Duration::from_nanos(1)),
+            ..Default::default()
+        },
+        Comparison::Equal => TimeRange {
+            after: Some(start_of_day - Duration::from_nanos(1)),
+            before: Some(end_of_day),
+        },
+    })
+}
+
+fn parse_kind_filter(value: &str) -> Result<EntryKind> {
+    match value.to_lowercase().as_str() {
+        "f" | "file" => Ok(EntryKind::File),
+        "d" | "dir" | "directory" => Ok(EntryKind::Dir),
+        "l" | "link" | "symlink" => Ok(EntryKind::Symlink),
+        _ => bail!("Invalid type filter type:{value}, use type:file, type:dir or type:symlink"),
+    }
+}
+
+/// Parses a comma separated list of extensions like `rs,toml` or `.rs,.toml`
+fn parse_extension_filter(value: &str) -> Result<Vec<String>> {
+    let extensions: Vec<String> = value
+        .split(',')
+        .map(|extension| extension.trim_start_matches('.').to_lowercase())
+        .filter(|extension| !extension.is_empty())
+        .collect();
+
+    if extensions.is_empty() {
+        bail!("Invalid extension filter ext:{value}, use e.g. ext:rs,toml");
+    }
+    Ok(extensions)
+}
+
+/// Parses the user name or the user id of the owner
+#[cfg(unix)]
+fn parse_owner_filter(value: &str) -> Result<u32> {
+    if let Ok(uid) = value.parse::<u32>() {
+        return Ok(uid);
+    }
+
+    match nix::unistd::User::from_name(value) {
+        Ok(Some(user)) => Ok(user.uid.as_raw()),
+        _ => bail!("Unknown user in owner filter owner:{value}"),
+    }
+}
+
+#[cfg(not(unix))]
+fn parse_owner_filter(_value: &str) -> Result<u32> {
+    bail!("The owner filter is not supported on this platform")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_query_splits_filters_and_query() {
+        let (pattern, filters) =
+            parse_query("report name:*.log  size:>100M type:file ext:.RS,toml final").unwrap();
+
+        assert_eq!(pattern, "report final");
+        assert_eq!(filters.name.as_deref(), Some("*.log"));
+        assert_eq!(filters.size, Some((Comparison::Greater, 100 * 1024 * 1024)));
+        assert_eq!(filters.kind, Some(EntryKind::File));
+        assert_eq!(filters.extensions, vec!["rs", "toml"]);
+
+        // unknown keys are part of the search query
+        let (pattern, filters) = parse_query("C:temp").unwrap();
+        assert_eq!(pattern, "C:temp");
+        assert!(filters.is_empty());
+    }
+
+    #[test]
+    fn test_parse_size_filter() {
+        assert_eq!(
+            parse_size_filter("<1.5k").unwrap(),
+            (Comparison::Less, 1536)
+        );
+        assert_eq!(parse_size_filter("0").unwrap(), (Comparison::Equal, 0));
+        assert_eq!(
+            parse_size_filter("=2GiB").unwrap(),
+            (Comparison::Equal, 2 << 30)
+        );
+        assert!(parse_size_filter(">100X").is_err());
+        assert!(parse_size_filter(">").is_err());
+    }
+
+    #[test]
+    fn test_parse_modified_filter() {
+        let now = SystemTime::now();
+        let hour = Duration::from_secs(60 * 60);
+
+        let within = parse_modified_filter("<7d").unwrap();
+        assert!(within.contains(now - hour));
+        assert!(!within.contains(now - 8 * ONE_DAY));
+
+        let older = parse_modified_filter(">12h").unwrap();
+        assert!(older.contains(now - ONE_DAY));
+        assert!(!older.contains(now - hour));
+
+        let day = parse_modified_filter("2024-01-31").unwrap();
+        let noon: SystemTime = Local
+            .with_ymd_and_hms(2024, 1, 31, 12, 0, 0)
+            .unwrap()
+            .into();
+        assert!(day.contains(noon));
+        assert!(!day.contains(noon + ONE_DAY));
+        assert!(
+            parse_modified_filter(">2024-01-31")
+                .unwrap()
+                .contains(noon + ONE_DAY)
+        );
+        assert!(!parse_modified_filter("<2024-01-31").unwrap().contains(noon));
+
+        assert!(parse_modified_filter("<yesterday").is_err());
+    }
+
+    #[test]
+    fn test_invalid_filters() {
+        assert!(parse_query("type:pipe").is_err());
+        assert!(parse_query("size:").is_err());
+        assert!(parse_query("ext:,").is_err());
+        assert!(parse_query("name:[a").is_err());
+        assert!(parse_query("size:>1M size:<2M").is_err());
+    }
+}
@@ -0,0 +1,252 @@
+use anyhow::{Result, bail};
+use file_format::{FileFormat, Kind};
+use ignore::WalkState;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use tokio::sync::mpsc;
+use tokio_util::sync::CancellationToken;
+
+use crate::{
+    app::actions::Action,
+    file_handling::{
+        DiskEntry, SearchResult, read_file_metadata, search::SearchQuery,
+        walker::walk_parallel_streamed,
+    },
+};
+
+/// Named groups of file kinds, that can be searched instead of a single format, e.g. `archive` or `videos`
+pub const FORMAT_CATEGORIES: [(&str, &[Kind]); 9] = [
+    ("image", &[Kind::Image]),
+    ("video", &[Kind::Video]),
+    ("audio", &[Kind::Audio]),
+    ("archive", &[Kind::Archive, Kind::Compressed, Kind::Package]),
+    (
+        "document",
+        &[
+            Kind::Document,
+            Kind::Spreadsheet,
+            Kind::Presentation,
+            Kind::Ebook,
+        ],
+    ),
+    ("executable", &[Kind::Executable]),
+    ("font", &[Kind::Font]),
+    ("database", &[Kind::Database]),
+    ("disk", &[Kind::Disk, Kind::Rom]),
+];
+
+/// A single word of the format query
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum FormatTerm {
+    /// One of the [`FORMAT_CATEGORIES`]
+    Category(&'static [Kind]),
+    /// A MIME type like `image/png`, or all MIME types of a top-level type like `video/*`
+    MediaType { media_type: String, is_prefix: bool },
+    /// A specific format, compared with the extension, the short name and the kind, e.g. `pdf`, `png` or `model`
+    Format(String),
+}
+
+/// Matches the detected format of files against formats, categories or MIME types.<br>
+/// A file matches, if one of the comma or space separated words of the query matches.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FormatMatcher {
+    terms: Vec<FormatTerm>,
+}
+
+impl FormatMatcher {
+    pub fn new(query: &str) -> Result<Self> {
+        let terms: Vec<FormatTerm> = query
+            .split(|c: char| c == ',' || c.is_whitespace())
+            .filter(|word| !word.is_empty())
+            .map(|word| parse_term(&word.to_lowercase()))
+            .collect::<Result<_>>()?;
+
+        if terms.is_empty() {
+            bail!("Enter a format like pdf, a category like image or a MIME type like video/*");
+        }
+        Ok(Self { terms })
+    }
+
+    pub fn matches(&self, file_format: &FileFormat) -> bool {
+        self.terms.iter().any(|term| match term {
+            FormatTerm::Category(kinds) => kinds.contains(&file_format.kind()),
+            FormatTerm::MediaType {
+                media_type,
+                is_prefix: true,
+            } => file_format.media_type().starts_with(media_type.as_str()),
+            FormatTerm::MediaType { media_type, .. } => {
+                file_format.media_type().eq_ignore_ascii_case(media_type)
+            }
+            FormatTerm::Format(format) => {
+                file_format.extension().eq_ignore_ascii_case(format)
+                    || file_format
+                        .short_name()
+                        .is_some_and(|short_name| short_name.eq_ignore_ascii_case(format))
+                    || format!("{:?}", file_format.kind()).eq_ignore_ascii_case(format)
+            }
+        })
+    }
+}
+
+fn parse_term(word: &str) -> Result<FormatTerm> {
+    if let Some((top_level, sub_type)) = word.split_once('/') {
+        if top_level.is_empty() || sub_type.is_empty() {
+            bail!("Invalid MIME type '{word}', use e.g. image/png or video/*");
+        }
+        return Ok(match sub_type {
+            "*" => FormatTerm::MediaType {
+                media_type: format!("{top_level}/"),
+                is_prefix: true,
+            },
+            _ => FormatTerm::MediaType {
+                media_type: word.to_string(),
+                is_prefix: false,
+            },
+        });
+    }
+
+    // the categories can be entered in plural, e.g. `images`
+    let category = FORMAT_CATEGORIES.iter().find(|(name, _)| {
+        word == *name
+            || word
+                .strip_suffix('s')
+                .is_some_and(|singular| singular == *name)
+    });
+    Ok(match category {
+        Some((_, kinds)) => FormatTerm::Category(kinds),
+        None => FormatTerm::Format(word.to_string()),
+    })
+}
+
+/// Describes the format in the Results page, e.g. `PDF` or `Matroska Video`
+pub fn format_label(file_format: &FileFormat) -> String {
+    file_format
+        .short_name()
+        .unwrap_or(file_format.name())
+        .to_string()
+}
+
+impl SearchResult {
+    /// Returns the detected format of the item at the given index, only available for a format search
+    pub fn detected_format_of(&self, index: usize) -> Option<&str> {
+        self.detected_formats.get(index).map(String::as_str)
+    }
+}
+
+/// Searches for files by the format, that is detected from their content, with one thread per core.<br>
+/// The file names and extensions are ignored, so e.g. a renamed image is found as well.
+/// The files are sorted by their path, so the result doesn't depend on the order in which the threads find them.
+///
+/// # Returns
+/// `None`, if no files were found or the search was cancelled
+pub fn find_entries_by_format(
+    tx: &mpsc::UnboundedSender<Action>,
+    query: &SearchQuery,
+    cancellation_token: &CancellationToken,
+) -> Result<Option<SearchResult>> {
+    let matcher = FormatMatcher::new(&query.pattern)?;
+    let filter = query.filters.compile(query.case_sensitive)?;
+    let file_counter = AtomicUsize::new(0);
+    let match_counter = AtomicUsize::new(0);
+
+    let mut files = walk_parallel_streamed(
+        tx,
+        query,
+        cancellation_token,
+        || {
+            format!(
+                "Detecting file formats... {} Files scanned, {} Matches",
+                file_counter.load(Ordering::Relaxed),
+                match_counter.load(Ordering::Relaxed)
+            )
+        },
+        // the files are only shown, when the search is done
+        |_| None,
+        |entry| {
+            if !entry
+                .file_type()
+                .is_some_and(|file_type| file_type.is_file())
+            {
+                return (None, WalkState::Continue);
+            }
+
+            file_counter.fetch_add(1, Ordering::Relaxed);
+            // the cheap filters first, the format detection reads the beginning of the file
+            if !filter.matches(&entry) {
+                return (None, WalkState::Continue);
+            }
+            let file_format = match FileFormat::from_file(entry.path()) {
+                Ok(file_format) if matcher.matches(&file_format) => file_format,
+                _ => return (None, WalkState::Continue),
+            };
+
+            match_counter.fetch_add(1, Ordering::Relaxed);
+            let disk_entry = DiskEntry {
+                name: entry.file_name().to_string_lossy().to_string(),
+                file_metadata: read_file_metadata(&entry),
+                path: entry.into_path(),
+                is_dir: false,
+            };
+            (
+                Some((disk_entry, format_label(&file_format))),
+                WalkState::Continue,
+            )
+        },
+    )?;
+    if cancellation_token.is_cancelled() || files.is_empty() {
+        return Ok(None);
+    }
+
+    files.sort_by(|(e1, _), (e2, _)| e1.path.cmp(&e2.path));
+    let (items, detected_formats) = files.into_iter().unzip();
+
+    Ok(Some(SearchResult {
+        items,
+        detected_formats,
+        ..SearchResult::for_query(query)
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::FormatMatcher;
+    use file_format::FileFormat;
+
+    #[test]
+    fn test_format_matcher() {
+        let images = FormatMatcher::new("images").unwrap();
+        assert!(images.matches(&FileFormat::PortableNetworkGraphics));
+        assert!(!images.matches(&FileFormat::PortableDocumentFormat));
+
+        let archives_and_pdf = FormatMatcher::new("archive, PDF").unwrap();
+        assert!(archives_and_pdf.matches(&FileFormat::Zip));
+        assert!(archives_and_pdf.matches(&FileFormat::Gzip));
+        assert!(archives_and_pdf.matches(&FileFormat::PortableDocumentFormat));
+        assert!(!archives_and_pdf.matches(&FileFormat::PlainText));
+
+        let videos = FormatMatcher::new("video/*").unwrap();
+        assert!(videos.matches(&FileFormat::Mpeg4Part14Video));
+        assert!(!videos.matches(&FileFormat::Mpeg4Part14Audio));
+
+        let png = FormatMatcher::new("image/png").unwrap();
+        assert!(png.matches(&FileFormat::PortableNetworkGraphics));
+        assert!(!png.matches(&FileFormat::JointPhotographicExpertsGroup));
+    }
+
+    #[test]
+    fn test_invalid_format_queries() {
+        assert!(FormatMatcher::new(" , ").is_err());
+        assert!(FormatMatcher::new("video/").is_err());
+    }
+
+    #[test]
+    fn test_format_is_detected_from_the_content() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("format_search_test.txt");
+        // a PNG signature in a file with a misleading extension
+        std::fs::write(&path, b"\x89PNG\r\n\x1a\n\0\0\0\rIHDR").unwrap();
+        let file_format = FileFormat::from_file(&path).unwrap();
+
+        assert!(FormatMatcher::new("image").unwrap().matches(&file_format));
+        assert_eq!(super::format_label(&file_format), "PNG");
+    }
+}
@@ -0,0 +1,184 @@
+/// Score of every matched character
+const SCORE_MATCH: i64 = 16;
+/// Bonus for a character at the start of the text or after a separator like `/`, `_`, `-`, `.` or a space
+const BONUS_BOUNDARY: i64 = 10;
+/// Bonus for an uppercase character after a lowercase character, e.g. the `B` of `fooBar`
+const BONUS_CAMEL_CASE: i64 = 8;
+/// Bonus for a character that directly follows the previous matched character
+const BONUS_CONSECUTIVE: i64 = 8;
+/// Penalty for every skipped character between two matched characters
+const PENALTY_GAP: i64 = 1;
+
+/// The best alignment of a fuzzy pattern in a text
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FuzzyMatch {
+    /// Higher is better
+    pub score: i64,
+    /// Char indices of the matched characters in the text, in ascending order
+    pub positions: Vec<usize>,
+}
+
+/// Matches the pattern as subsequence of the text and returns the alignment with the highest score.<br>
+/// The score prefers characters at word boundaries and consecutive runs of characters, skipped characters
+/// between the matched characters are penalized.
+///
+/// # Arguments
+/// * `pattern` - The characters to match, already lowercased if the match is case insensitive
+/// * `text` - The text to match, e.g. a file name or path
+/// * `case_sensitive` - If `false`, the characters of the text are lowercased before they are compared
+///
+/// # Returns
+/// `None` if the text does not contain the pattern as subsequence, an empty pattern matches every text
+pub fn fuzzy_match(pattern: &[char], text: &str, case_sensitive: bool) -> Option<FuzzyMatch> {
+    let chars: Vec<char> = text.chars().collect();
+    let folded: Vec<char> = if case_sensitive {
+        chars.clone()
+    } else {
+        chars.iter().map(|c| fold_case(*c)).collect()
+    };
+
+    if pattern.is_empty() {
+        return Some(FuzzyMatch {
+            score: 0,
+            positions: vec![],
+        });
+    }
+    if !is_subsequence(pattern, &folded) {
+        return None;
+    }
+
+    let bonuses: Vec<i64> = (0..chars.len()).map(|j| char_bonus(&chars, j)).collect();
+    let (n, m) = (pattern.len(), chars.len());
+
+    // scores[i][j] is the best score of the pattern up to `i`, with `pattern[i]` matched at `text[j]`
+    let mut scores = vec![vec![None::<i64>; m]; n];
+    // previous[i][j] is the position of `pattern[i - 1]` in the best alignment of scores[i][j]
+    let mut previous = vec![vec![0_usize; m]; n];
+
+    for j in 0..m {
+        if folded[j] == pattern[0] {
+            scores[0][j] = Some(SCORE_MATCH + bonuses[j]);
+        }
+    }
+
+    for i in 1..n {
+        // best `scores[i - 1][k] + PENALTY_GAP * k` of all k < j - 1, the gap penalty is applied relative to j
+        let mut best_gapped: Option<(i64, usize)> = None;
+
+        for j in i..m {
+            if j >= 2
+                && let Some(score) = scores[i - 1][j - 2]
+            {
+                let candidate = score + PENALTY_GAP * (j - 2) as i64;
+                if best_gapped.is_none_or(|(best, _)| candidate > best) {
+                    best_gapped = Some((candidate, j - 2));
+                }
+            }
+
+            if folded[j] != pattern[i] {
+                continue;
+            }
+
+            let consecutive = scores[i - 1][j - 1].map(|score| (score + BONUS_CONSECUTIVE, j - 1));
+            let gapped = best_gapped.map(|(score, k)| (score - PENALTY_GAP * (j as i64 - 1), k));
+
+            let best = match (consecutive, gapped) {
+                (Some(c), Some(g)) => Some(if c.0 >= g.0 { c } else { g }),
+                (c, g) => c.or(g),
+            };
+            if let Some((score, k)) = best {
+                scores[i][j] = Some(score + SCORE_MATCH + bonuses[j]);
+                previous[i][j] = k;
+            }
+        }
+    }
+
+    // the first end position with the highest score, so that earlier matches are preferred
+    let (mut j, score) = scores[n - 1]
+        .iter()
+        .enumerate()
+        .filter_map(|(j, score)| score.map(|score| (j, score)))
+        .fold(None, |best: Option<(usize, i64)>, (j, score)| match best {
+            Some((_, best_score)) if best_score >= score => best,
+            _ => Some((j, score)),
+        })?;
+
+    let mut positions = vec![0; n];
+    for i in (0..n).rev() {
+        positions[i] = j;
+        j = previous[i][j];
+    }
+
+    Some(FuzzyMatch { score, positions })
+}
+
+/// Lowercases a character, characters with a multi character lowercase form are kept
+pub fn fold_case(c: char) -> char {
+    let mut lower = c.to_lowercase();
+    match (lower.next(), lower.next()) {
+        (Some(l), None) => l,
+        _ => c,
+    }
+}
+
+fn is_subsequence(pattern: &[char], text: &[char]) -> bool {
+    let mut text = text.iter();
+    pattern.iter().all(|p| text.any(|c| c == p))
+}
+
+fn char_bonus(chars: &[char], j: usize) -> i64 {
+    let Some(prev) = j.checked_sub(1).map(|i| chars[i]) else {
+        return BONUS_BOUNDARY;
+    };
+    let current = chars[j];
+
+    if matches!(prev, '/' | '\\' | '_' | '-' | '.' | ' ') {
+        BONUS_BOUNDARY
+    } else if prev.is_lowercase() && current.is_uppercase() {
+        BONUS_CAMEL_CASE
+    } else {
+        0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::fuzzy_match;
+
+    fn positions(pattern: &str, text: &str) -> Option<Vec<usize>> {
+        let pattern: Vec<char> = pattern.chars().collect();
+        fuzzy_match(&pattern, text, false).map(|m| m.positions)
+    }
+
+    fn score(pattern: &str, text: &str) -> i64 {
+        let pattern: Vec<char> = pattern.chars().collect();
+        fuzzy_match(&pattern, text, false).unwrap().score
+    }
+
+    #[test]
+    fn test_fuzzy_match_positions() {
+        assert_eq!(positions("mrs", "main.rs"), Some(vec![0, 5, 6]));
+        // the boundaries are preferred over the first occurrence
+        assert_eq!(positions("fb", "foo_bar"), Some(vec![0, 4]));
+        assert_eq!(positions("sw", "search_widget.rs"), Some(vec![0, 7]));
+        assert_eq!(positions("rsm", "main.rs"), None);
+        assert_eq!(positions("", "main.rs"), Some(vec![]));
+    }
+
+    #[test]
+    fn test_fuzzy_match_is_case_insensitive() {
+        assert_eq!(positions("rw", "ResultWidget.rs"), Some(vec![0, 6]));
+        let pattern: Vec<char> = "rw".chars().collect();
+        assert!(fuzzy_match(&pattern, "ResultWidget.rs", true).is_none());
+    }
+
+    #[test]
+    fn test_fuzzy_score_ranking() {
+        // consecutive runs before scattered characters
+        assert!(score("mod", "mod.rs") > score("mod", "my_old_data.rs"));
+        // word boundaries before characters in the middle of a word
+        assert!(score("cfg", "config_file_generator") > score("cfg", "unconfigured"));
+        // fewer skipped characters
+        assert!(score("ab", "a_b") > score("ab", "a___b"));
+    }
+}
@@ -0,0 +1,310 @@
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::HashMap,
+    fmt::Display,
+    path::{Path, PathBuf},
+};
+
+/// Represents the git status of a file or directory.<br>
+/// The order of the variants defines the priority when several statuses are aggregated onto a directory,
+/// e.g. a directory that contains a conflicted and a modified file is shown as conflicted.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum GitStatus {
+    Ignored,
+    Untracked,
+    Staged,
+    Modified,
+    Conflicted,
+}
+
+impl GitStatus {
+    /// Short marker to display next to an entry
+    pub fn marker(&self) -> &'static str {
+        match self {
+            GitStatus::Ignored => "!",
+            GitStatus::Untracked => "?",
+            GitStatus::Staged => "S",
+            GitStatus::Modified => "M",
+            GitStatus::Conflicted => "U",
+        }
+    }
+
+    /// Convert the libgit2 status flags of a single entry, if there is something to display
+    fn from_flags(status: git2::Status) -> Option<Self> {
+        if status.is_conflicted() {
+            Some(GitStatus::Conflicted)
+        } else if status.intersects(
+            git2::Status::WT_MODIFIED
+                | git2::Status::WT_DELETED
+                | git2::Status::WT_RENAMED
+                | git2::Status::WT_TYPECHANGE,
+        ) {
+            Some(GitStatus::Modified)
+        } else if status.intersects(
+            git2::Status::INDEX_NEW
+                | git2::Status::INDEX_MODIFIED
+                | git2::Status::INDEX_DELETED
+                | git2::Status::INDEX_RENAMED
+                | git2::Status::INDEX_TYPECHANGE,
+        ) {
+            Some(GitStatus::Staged)
+        } else if status.is_wt_new() {
+            Some(GitStatus::Untracked)
+        } else if status.is_ignored() {
+            Some(GitStatus::Ignored)
+        } else {
+            None
+        }
+    }
+}
+
+impl Display for GitStatus {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let status = match self {
+            GitStatus::Ignored => "Ignored",
+            GitStatus::Untracked => "Untracked",
+            GitStatus::Staged => "Staged",
+            GitStatus::Modified => "Modified",
+            GitStatus::Conflicted => "Conflicted",
+        };
+        write!(f, "{}", status)
+    }
+}
+
+/// Represents the git status of the work tree that contains the current directory
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq)]
+pub struct GitInfo {
+    /// Root directory of the work tree
+    workdir: PathBuf,
+    /// Name of the checked out branch or the short commit id, if the HEAD is detached
+    branch: Option<String>,
+    /// Status of every changed entry, including the aggregated status of their parent directories
+    statuses: HashMap<PathBuf, GitStatus>,
+}
+
+impl GitInfo {
+    /// Reads the git status of the work tree that contains the given path.
+    ///
+    /// # Returns
+    /// `None`, if the path is not inside a git work tree
+    pub fn load(path: &Path) -> Result<Option<Self>> {
+        let repo = match git2::Repository::discover(path) {
+            Ok(repo) => repo,
+            Err(err) if err.code() == git2::ErrorCode::NotFound => return Ok(None),
+            Err(err) => return Err(err.into()),
+        };
+
+        let Some(workdir) = repo.workdir().map(normalize) else {
+            // a bare repository has no work tree
+            return Ok(None);
+        };
+
+        let branch = match repo.head() {
+            Ok(head) if head.is_branch() => head.shorthand().map(str::to_string),
+            Ok(head) => head
+                .target()
+                .map(|oid| oid.to_string().chars().take(7).collect()),
+            // e.g. a freshly initialized repository without any commit
+            Err(_) => repo
+                .find_reference("HEAD")
+                .ok()
+                .and_then(|head| head.symbolic_target().map(str::to_string))
+                .map(|target| target.trim_start_matches("refs/heads/").to_string()),
+        };
+
+        let mut options = git2::StatusOptions::new();
+        options
+            .include_untracked(true)
+            .include_ignored(true)
+            .recurse_untracked_dirs(false)
+            .recurse_ignored_dirs(false)
+            .exclude_submodules(true);
+
+        let mut statuses: HashMap<PathBuf, GitStatus> = HashMap::new();
+
+        for entry in repo.statuses(Some(&mut options))?.iter() {
+            let (Some(relative_path), Some(status)) =
+                (entry.path(), GitStatus::from_flags(entry.status()))
+            else {
+                continue;
+            };
+
+            Self::add_status(
+                &mut statuses,
+                &workdir,
+                normalize(workdir.join(relative_path)),
+                status,
+            );
+        }
+
+        Ok(Some(Self {
+            workdir,
+            branch,
+            statuses,
+        }))
+    }
+
+    /// Adds the status of the given path and aggregates it onto all parent directories inside the work tree.<br>
+    /// Ignored entries are not aggregated, otherwise every directory that contains e.g. a build folder would be marked.
+    fn add_status(
+        statuses: &mut HashMap<PathBuf, GitStatus>,
+        workdir: &Path,
+        path: PathBuf,
+        status: GitStatus,
+    ) {
+        let parents: Vec<PathBuf> = if status == GitStatus::Ignored {
+            vec![]
+        } else {
+            path.ancestors()
+                .skip(1)
+                .take_while(|parent| parent.starts_with(workdir) && *parent != workdir)
+                .map(Path::to_path_buf)
+                .collect()
+        };
+
+        for p in std::iter::once(path).chain(parents) {
+            statuses
+                .entry(p)
+                .and_modify(|current| *current = (*current).max(status))
+                .or_insert(status);
+        }
+    }
+
+    pub fn branch(&self) -> Option<&str> {
+        self.branch.as_deref()
+    }
+
+    /// Checks if the given path is inside the work tree
+    pub fn contains(&self, path: &Path) -> bool {
+        path.starts_with(&self.workdir)
+    }
+
+    /// Checks if the given path is inside the work tree and not inside a nested repository or submodule,
+    /// which has its own branch and status
+    pub fn is_status_known(&self, path: &Path) -> bool {
+        self.contains(path)
+            && !path
+                .ancestors()
+                .take_while(|parent| *parent != self.workdir)
+                .any(|parent| parent.join(".git").exists())
+    }
+
+    /// Returns the git status of the given path, if any.<br>
+    /// Entries inside an untracked or ignored directory inherit the status of this directory.
+    pub fn status_of(&self, path: &Path) -> Option<GitStatus> {
+        if !self.contains(path) {
+            return None;
+        }
+
+        if let Some(status) = self.statuses.get(path) {
+            return Some(*status);
+        }
+
+        path.ancestors()
+            .skip(1)
+            .take_while(|parent| *parent != self.workdir)
+            .find_map(|parent| match self.statuses.get(parent) {
+                Some(status @ (GitStatus::Untracked | GitStatus::Ignored)) => Some(*status),
+                _ => None,
+            })
+    }
+}
+
+/// Removes trailing separators, e.g. libgit2 reports untracked directories as `dir/`
+fn normalize<P: AsRef<Path>>(path: P) -> PathBuf {
+    path.as_ref().components().collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn git_info(statuses: &[(&str, GitStatus)]) -> GitInfo {
+        let workdir = PathBuf::from("/repo");
+        let mut map = HashMap::new();
+        for (path, status) in statuses {
+            GitInfo::add_status(&mut map, &workdir, workdir.join(path), *status);
+        }
+        GitInfo {
+            workdir,
+            branch: Some("main".into()),
+            statuses: map,
+        }
+    }
+
+    #[test]
+    fn test_status_aggregation_uses_highest_priority() {
+        let info = git_info(&[
+            ("src/a.rs", GitStatus::Staged),
+            ("src/ui/b.rs", GitStatus::Conflicted),
+            ("src/c.rs", GitStatus::Modified),
+        ]);
+
+        assert_eq!(
+            info.status_of(Path::new("/repo/src")),
+            Some(GitStatus::Conflicted)
+        );
+        assert_eq!(
+            info.status_of(Path::new("/repo/src/a.rs")),
+            Some(GitStatus::Staged)
+        );
+        assert_eq!(info.status_of(Path::new("/repo/src/d.rs")), None);
+        assert_eq!(info.status_of(Path::new("/other/src")), None);
+    }
+
+    #[test]
+    fn test_ignored_status_is_not_aggregated() {
+        let info = git_info(&[("build/out", GitStatus::Ignored)]);
+
+        assert_eq!(info.status_of(Path::new("/repo/build")), None);
+    }
+
+    #[test]
+    fn test_status_inherited_from_untracked_dir() {
+        let info = git_info(&[
+            ("target", GitStatus::Ignored),
+            ("new", GitStatus::Untracked),
+        ]);
+
+        assert_eq!(
+            info.status_of(Path::new("/repo/target/debug/app")),
+            Some(GitStatus::Ignored)
+        );
+        assert_eq!(
+            info.status_of(Path::new("/repo/new/file.txt")),
+            Some(GitStatus::Untracked)
+        );
+    }
+
+    #[test]
+    fn test_nested_repository_is_not_known() {
+        let dir = tempfile::tempdir().unwrap();
+        let workdir = dir.path().join("repo");
+        std::fs::create_dir_all(workdir.join(".git")).unwrap();
+        std::fs::create_dir_all(workdir.join("src")).unwrap();
+        // a submodule has a .git file instead of a directory
+        std::fs::create_dir_all(workdir.join("libs/sub/src")).unwrap();
+        std::fs::write(
+            workdir.join("libs/sub/.git"),
+            "gitdir: ../../.git/modules/sub",
+        )
+        .unwrap();
+
+        let info = GitInfo {
+            workdir: workdir.clone(),
+            ..Default::default()
+        };
+        assert!(info.is_status_known(&workdir));
+        assert!(info.is_status_known(&workdir.join("src")));
+        assert!(info.is_status_known(&workdir.join("libs")));
+        assert!(!info.is_status_known(&workdir.join("libs/sub")));
+        assert!(!info.is_status_known(&workdir.join("libs/sub/src")));
+        assert!(!info.is_status_known(dir.path()));
+    }
+
+    #[test]
+    fn test_normalize_trailing_separator() {
+        assert_eq!(normalize("/repo/dir/"), PathBuf::from("/repo/dir"));
+    }
+}
@@ -0,0 +1,652 @@
+use anyhow::{Result, anyhow};
+use rusqlite::{Connection, OptionalExtension, Transaction, params};
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::{HashMap, HashSet},
+    ffi::OsStr,
+    fs::{self, FileType, Metadata},
+    path::{MAIN_SEPARATOR, Path, PathBuf},
+    time::{Duration, Instant, SystemTime, UNIX_EPOCH},
+};
+use tokio::sync::mpsc;
+use tokio_util::sync::CancellationToken;
+
+use crate::{
+    app::{AppState, actions::Action},
+    file_handling::{
+        DiskEntry, SearchResult, read_file_metadata,
+        search::SearchQuery,
+        walker::{PROGRESS_INTERVAL, WalkEntry},
+    },
+    utils,
+};
+
+/// Every indexed file/directory is stored with its parent, so that the entries of a directory can be compared with the disk.
+/// Only directories have a modification time, it is empty until the directory was read.
+const SCHEMA: &str = "
+    CREATE TABLE IF NOT EXISTS roots (
+        path TEXT PRIMARY KEY,
+        updated_at INTEGER NOT NULL,
+        entry_count INTEGER NOT NULL
+    ) WITHOUT ROWID;
+    CREATE TABLE IF NOT EXISTS entries (
+        path TEXT PRIMARY KEY,
+        parent TEXT NOT NULL,
+        is_dir INTEGER NOT NULL,
+        mtime INTEGER,
+        dev INTEGER
+    ) WITHOUT ROWID;
+    CREATE INDEX IF NOT EXISTS entries_parent ON entries (parent);
+";
+
+/// A directory of the index and the time of its last complete update
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct IndexedRoot {
+    pub path: PathBuf,
+    pub updated_at: SystemTime,
+    /// Number of files and directories below the directory
+    pub entry_count: usize,
+}
+
+/// Persistent index of the file/directory names below the configured directories, similar to `locate`.<br>
+/// Name searches inside an indexed directory are answered from the index instead of walking the directory tree.
+/// Paths, that are not valid UTF-8, are not indexed.
+pub struct FileIndex {
+    connection: Connection,
+}
+
+impl FileIndex {
+    pub fn open(path: &Path) -> Result<Self> {
+        let connection = Connection::open(path)?;
+        connection.busy_timeout(Duration::from_secs(5))?;
+        // a running update doesn't block the searches
+        connection.pragma_update(None, "journal_mode", "WAL")?;
+        connection.execute_batch(SCHEMA)?;
+        Ok(Self { connection })
+    }
+
+    /// Opens the index in the data directory, the index file is created by the first update
+    pub fn open_existing() -> Result<Option<Self>> {
+        let path = utils::index_file();
+        if !path.exists() {
+            return Ok(None);
+        }
+        Self::open(&path).map(Some)
+    }
+
+    /// Returns all completely indexed directories
+    pub fn roots(&self) -> Result<Vec<IndexedRoot>> {
+        let mut statement = self
+            .connection
+            .prepare("SELECT path, updated_at, entry_count FROM roots ORDER BY path")?;
+        let roots = statement
+            .query_map([], |row| {
+                Ok(IndexedRoot {
+                    path: PathBuf::from(row.get::<_, String>(0)?),
+                    updated_at: UNIX_EPOCH
+                        + Duration::from_secs(row.get::<_, i64>(1)?.max(0) as u64),
+                    entry_count: row.get::<_, i64>(2)? as usize,
+                })
+            })?
+            .collect::<rusqlite::Result<_>>()?;
+        Ok(roots)
+    }
+
+    /// Returns the indexed directory, that contains the given directory
+    pub fn root_of(&self, dir: &Path) -> Result<Option<IndexedRoot>> {
+        Ok(self
+            .roots()?
+            .into_iter()
+            .find(|root| dir.starts_with(&root.path)))
+    }
+
+    /// Updates the index of the given directories, the index of all other directories is removed.<br>
+    /// Like `updatedb` of plocate, only the directories, whose modification time changed since the last update,
+    /// are read again. All other directories keep their indexed entries, but their subdirectories are still checked.
+    /// A cancelled update keeps the previous index of the unfinished directory.
+    pub fn update(
+        &mut self,
+        tx: &mpsc::UnboundedSender<Action>,
+        dirs: &[PathBuf],
+        cancellation_token: &CancellationToken,
+    ) -> Result<()> {
+        let dirs = disjoint_dirs(dirs);
+
+        for root in self.roots()? {
+            if dirs.contains(&root.path) {
+                continue;
+            }
+            let Some(root_path) = root.path.to_str() else {
+                continue;
+            };
+            let transaction = self.connection.transaction()?;
+            transaction.execute("DELETE FROM roots WHERE path = ?1", [root_path])?;
+            // the entries are still needed, if the directory is now part of a larger indexed directory
+            if !dirs.iter().any(|dir| root.path.starts_with(dir)) {
+                delete_subtree(&transaction, root_path)?;
+            }
+            transaction.commit()?;
+        }
+
+        for dir in &dirs {
+            if cancellation_token.is_cancelled() {
+                break;
+            }
+            self.update_root(tx, dir, cancellation_token)?;
+        }
+        Ok(())
+    }
+
+    fn update_root(
+        &mut self,
+        tx: &mpsc::UnboundedSender<Action>,
+        root: &Path,
+        cancellation_token: &CancellationToken,
+    ) -> Result<()> {
+        let root_path = root
+            .to_str()
+            .ok_or_else(|| anyhow!("The path is not valid UTF-8 [{}]", root.display()))?;
+        let transaction = self.connection.transaction()?;
+        let mut stack = vec![root.to_path_buf()];
+        let mut entry_count = 0;
+        let mut last_progress = Instant::now();
+
+        while let Some(dir) = stack.pop() {
+            // the transaction is rolled back, when it is dropped
+            if cancellation_token.is_cancelled() {
+                return Ok(());
+            }
+            if last_progress.elapsed() >= PROGRESS_INTERVAL {
+                tx.send(Action::UpdateAppState(AppState::Working(format!(
+                    "Indexing {}... {} entries",
+                    utils::format_path_for_display(root),
+                    entry_count
+                ))))?;
+                last_progress = Instant::now();
+            }
+
+            let children = update_dir_entries(&transaction, &dir)?;
+            entry_count += children.len();
+            stack.extend(
+                children
+                    .into_iter()
+                    .filter_map(|(path, is_dir)| is_dir.then_some(path)),
+            );
+        }
+
+        transaction.execute(
+            "INSERT OR REPLACE INTO roots (path, updated_at, entry_count) VALUES (?1, ?2, ?3)",
+            params![root_path, unix_secs(SystemTime::now()), entry_count as i64],
+        )?;
+        transaction.commit()?;
+        Ok(())
+    }
+
+    /// Searches the names of the indexed entries below the search directory.<br>
+    /// The depth, exclude and filesystem options of the query are applied like during a directory walk.
+    /// Each match is read from disk, so that deleted entries are skipped and the filters use the current metadata.
+    fn find_entries(
+        &self,
+        tx: &mpsc::UnboundedSender<Action>,
+        query: &SearchQuery,
+        root: &IndexedRoot,
+        cancellation_token: &CancellationToken,
+    ) -> Result<Option<SearchResult>> {
+        let matcher = query.name_matcher()?;
+        let filter = query.filters.compile(query.case_sensitive)?;
+        let exclude = query.exclude_filter()?;
+        let cwd = query
+            .cwd
+            .to_str()
+            .ok_or_else(|| anyhow!("The path is not valid UTF-8 [{}]", query.cwd.display()))?;
+        let cwd_dev = fs::metadata(&query.cwd).ok().and_then(|m| device_id(&m));
+
+        // a flat search only needs the entries of the directory itself
+        let mut statement;
+        let mut rows = if query.depth == 1 {
+            statement = self
+                .connection
+                .prepare("SELECT path, is_dir, dev FROM entries WHERE parent = ?1 ORDER BY path")?;
+            statement.query([cwd])?
+        } else {
+            let (lower, upper) = subtree_range(cwd);
+            statement = self.connection.prepare(
+                "SELECT path, is_dir, dev FROM entries WHERE path > ?1 AND path < ?2 ORDER BY path",
+            )?;
+            statement.query([lower, upper])?
+        };
+
+        // the directories, whose entries are skipped, because they are excluded or on another filesystem.
+        // The rows are sorted by path, so a directory is always visited before its entries.
+        let mut pruned: HashSet<PathBuf> = HashSet::new();
+        let mut matches: Vec<DiskEntry> = vec![];
+        let mut streamed = 0;
+        let mut entry_count = 0;
+        let mut last_progress = Instant::now();
+
+        while let Some(row) = rows.next()? {
+            if cancellation_token.is_cancelled() {
+                return Ok(None);
+            }
+            if last_progress.elapsed() >= PROGRESS_INTERVAL {
+                tx.send(Action::UpdateAppState(AppState::Working(format!(
+                    "Searching the index... {entry_count} entries"
+                ))))?;
+                if streamed < matches.len() {
+                    tx.send(Action::SearchChunk(matches[streamed..].to_vec(), vec![]))?;
+                    streamed = matches.len();
+                }
+                last_progress = Instant::now();
+            }
+            entry_count += 1;
+
+            let path = PathBuf::from(row.get::<_, String>(0)?);
+            let is_dir: bool = row.get(1)?;
+            let dev: Option<i64> = row.get(2)?;
+
+            let Ok(relative) = path.strip_prefix(&query.cwd) else {
+                continue;
+            };
+            let depth = relative.components().count();
+            if depth > query.depth
+                || (!pruned.is_empty()
+                    && path
+                        .ancestors()
+                        .skip(1)
+                        .take(depth - 1)
+                        .any(|ancestor| pruned.contains(ancestor)))
+            {
+                continue;
+            }
+            if exclude.is_excluded(&path, depth) {
+                if is_dir {
+                    pruned.insert(path);
+                }
+                continue;
+            }
+            // like during the walk, a directory on another filesystem is listed, but not entered
+            if is_dir && query.same_file_system && dev.is_some() && dev != cwd_dev {
+                pruned.insert(path.clone());
+            }
+
+            let name = relative
+                .file_name()
+                .map(|name| name.to_string_lossy().to_string())
+                .unwrap_or_default();
+            if !matcher.is_entry_match(&name, &path, &query.cwd) {
+                continue;
+            }
+            // the entry may have been deleted since the last update
+            let Some(entry) = IndexedEntry::read(path) else {
+                continue;
+            };
+            if !filter.matches(&entry) {
+                continue;
+            }
+
+            let is_dir = entry.metadata.is_dir();
+            matches.push(DiskEntry {
+                file_metadata: if is_dir {
+                    None
+                } else {
+                    read_file_metadata(&entry)
+                },
+                name,
+                path: entry.path,
+                is_dir,
+            });
+        }
+
+        if matches.is_empty() {
+            return Ok(None);
+        }
+
+        let mut result = SearchResult {
+            items: matches,
+            indexed_at: Some(root.updated_at),
+            ..SearchResult::for_query(query)
+        };
+        result.sort_items();
+        Ok(Some(result))
+    }
+}
+
+/// Answers a name search from the index, if the search directory is inside an indexed directory.<br>
+/// A search, that follows symbolic links, always walks the directory tree, because the index doesn't follow them.
+///
+/// # Returns
+/// `None`, if the search has to walk the directory tree instead
+pub fn find_indexed_entries(
+    tx: &mpsc::UnboundedSender<Action>,
+    query: &SearchQuery,
+    cancellation_token: &CancellationToken,
+) -> Option<Result<Option<SearchResult>>> {
+    if query.follow_sym_links {
+        return None;
+    }
+
+    let lookup = FileIndex::open_existing().and_then(|index| {
+        Ok(match index {
+            Some(index) => index.root_of(&query.cwd)?.map(|root| (index, root)),
+            None => None,
+        })
+    });
+
+    match lookup {
+        Ok(Some((index, root))) => Some(index.find_entries(tx, query, &root, cancellation_token)),
+        Ok(None) => None,
+        Err(err) => {
+            log::error!(
+                "Index: Unable to read the index, the directory is walked instead - Details {err:?}"
+            );
+            None
+        }
+    }
+}
+
+/// Updates the index of the given directories and reports the indexed directories afterwards, also if the update failed
+pub fn update_index(
+    tx: &mpsc::UnboundedSender<Action>,
+    dirs: &[PathBuf],
+    cancellation_token: &CancellationToken,
+) -> Result<()> {
+    utils::create_data_dir()?;
+    let mut index = FileIndex::open(&utils::index_file())?;
+    tx.send(Action::IndexStatus(index.roots()?))?;
+
+    let started = Instant::now();
+    let state = match index.update(tx, dirs, cancellation_token) {
+        _ if cancellation_token.is_cancelled() => AppState::Done("Index update cancelled".into()),
+        Ok(_) => {
+            let entry_count: usize = index.roots()?.iter().map(|root| root.entry_count).sum();
+            AppState::Done(format!(
+                "Index updated: {} entries in {} directories ({:.1}s)",
+                entry_count,
+                index.roots()?.len(),
+                started.elapsed().as_secs_f64()
+            ))
+        }
+        Err(err) => {
+            log::error!("Index: Unable to update the index - Details {err:?}");
+            AppState::Failure(format!("Unable to update the index: {err}"))
+        }
+    };
+
+    tx.send(Action::IndexStatus(index.roots()?))?;
+    tx.send(Action::UpdateAppState(state))?;
+    Ok(())
+}
+
+/// Human readable time since the last update of the index, e.g. `5m ago`
+pub fn format_age(time: SystemTime) -> String {
+    let secs = time.elapsed().unwrap_or_default().as_secs();
+    match secs {
+        0..60 => "just now".to_string(),
+        60..3_600 => format!("{}m ago", secs / 60),
+        3_600..86_400 => format!("{}h ago", secs / 3_600),
+        _ => format!("{}d ago", secs / 86_400),
+    }
+}
+
+/// Compares the entries of the directory on disk with the indexed entries, if the directory was modified since the last update.
+///
+/// # Returns
+/// The current entries of the directory and whether they are directories
+fn update_dir_entries(transaction: &Transaction, dir: &Path) -> Result<Vec<(PathBuf, bool)>> {
+    let Some(dir_path) = dir.to_str() else {
+        return Ok(vec![]);
+    };
+    let Ok(metadata) = fs::symlink_metadata(dir) else {
+        delete_subtree(transaction, dir_path)?;
+        return Ok(vec![]);
+    };
+    let mtime = metadata.modified().ok().map(unix_nanos);
+
+    let indexed_mtime: Option<i64> = transaction
+        .prepare_cached("SELECT mtime FROM entries WHERE path = ?1")?
+        .query_row([dir_path], |row| row.get(0))
+        .optional()?
+        .flatten();
+    let indexed: HashMap<String, bool> = transaction
+        .prepare_cached("SELECT path, is_dir FROM entries WHERE parent = ?1")?
+        .query_map([dir_path], |row| Ok((row.get(0)?, row.get(1)?)))?
+        .collect::<rusqlite::Result<_>>()?;
+
+    if mtime.is_some() && indexed_mtime == mtime {
+        return Ok(indexed
+            .into_iter()
+            .map(|(path, is_dir)| (PathBuf::from(path), is_dir))
+            .collect());
+    }
+
+    let read_dir = fs::read_dir(dir)
+        .inspect_err(|err| log::warn!("Index: Unable to read {} - Details {err:?}", dir.display()))
+        .ok();
+    let is_readable = read_dir.is_some();
+    let current: HashMap<String, bool> = read_dir
+        .into_iter()
+        .flatten()
+        .flatten()
+        .filter_map(|entry| {
+            // symbolic links are not followed, like during a search walk
+            let is_dir = entry.file_type().is_ok_and(|file_type| file_type.is_dir());
+            Some((entry.path().to_str()?.to_string(), is_dir))
+        })
+        .collect();
+
+    // an entry, that was removed or changed between file and directory, is removed with everything below it
+    for (path, is_dir) in &indexed {
+        if current.get(path) != Some(is_dir) {
+            delete_subtree(transaction, path)?;
+        }
+    }
+    let mut insert = transaction
+        .prepare_cached("INSERT INTO entries (path, parent, is_dir) VALUES (?1, ?2, ?3)")?;
+    for (path, is_dir) in &current {
+        if indexed.get(path) != Some(is_dir) {
+            insert.execute(params![path, dir_path, is_dir])?;
+        }
+    }
+
+    // an unreadable directory has no modification time, so that it is read again with the next update
+    let parent = dir.parent().and_then(Path::to_str).unwrap_or_default();
+    transaction
+        .prepare_cached(
+            "INSERT INTO entries (path, parent, is_dir, mtime, dev) VALUES (?1, ?2, 1, ?3, ?4)
+             ON CONFLICT (path) DO UPDATE SET mtime = excluded.mtime, dev = excluded.dev",
+        )?
+        .execute(params![
+            dir_path,
+            parent,
+            mtime.filter(|_| is_readable),
+            device_id(&metadata)
+        ])?;
+
+    Ok(current
+        .into_iter()
+        .map(|(path, is_dir)| (PathBuf::from(path), is_dir))
+        .collect())
+}
+
+/// Removes the entry and everything below it from the index
+fn delete_subtree(transaction: &Transaction, path: &str) -> Result<()> {
+    let (lower, upper) = subtree_range(path);
+    transaction
+        .prepare_cached("DELETE FROM entries WHERE path = ?1 OR (path > ?2 AND path < ?3)")?
+        .execute(params![path, lower, upper])?;
+    Ok(())
+}
+
+/// Returns the exclusive bounds of the paths below the directory.<br>
+/// The paths are compared byte by byte, so all paths starting with the directory and a separator
+/// are between the separator and the character following it.
+fn subtree_range(dir: &str) -> (String, String) {
+    let dir = dir.trim_end_matches(MAIN_SEPARATOR);
+    let next = char::from(MAIN_SEPARATOR as u8 + 1);
+    (format!("{dir}{MAIN_SEPARATOR}"), format!("{dir}{next}"))
+}
+
+/// Removes the directories, that are inside another directory of the list, so that no entry is indexed twice
+fn disjoint_dirs(dirs: &[PathBuf]) -> Vec<PathBuf> {
+    let mut disjoint: Vec<PathBuf> = vec![];
+    for dir in dirs {
+        if !dir.is_dir() {
+            log::warn!(
+                "Index: No such directory, it is not indexed [{}]",
+                dir.display()
+            );
+            continue;
+        }
+        if dirs
+            .iter()
+            .any(|other| other != dir && dir.starts_with(other))
+        {
+            continue;
+        }
+        if !disjoint.contains(dir) {
+            disjoint.push(dir.clone());
+        }
+    }
+    disjoint
+}
+
+fn unix_secs(time: SystemTime) -> i64 {
+    time.duration_since(UNIX_EPOCH)
+        .map_or(0, |duration| duration.as_secs() as i64)
+}
+
+fn unix_nanos(time: SystemTime) -> i64 {
+    time.duration_since(UNIX_EPOCH)
+        .map_or(0, |duration| duration.as_nanos() as i64)
+}
+
+/// The device of the filesystem, only available on Unix
+#[cfg(unix)]
+fn device_id(metadata: &Metadata) -> Option<i64> {
+    use std::os::unix::fs::MetadataExt;
+    Some(metadata.dev() as i64)
+}
+
+#[cfg(not(unix))]
+fn device_id(_metadata: &Metadata) -> Option<i64> {
+    None
+}
+
+/// An indexed entry, that still exists on disk, so that the filters of the walk can be applied to it
+struct IndexedEntry {
+    path: PathBuf,
+    metadata: Metadata,
+}
+
+impl IndexedEntry {
+    fn read(path: PathBuf) -> Option<Self> {
+        let metadata = fs::symlink_metadata(&path).ok()?;
+        Some(Self { path, metadata })
+    }
+}
+
+impl WalkEntry for IndexedEntry {
+    fn path(&self) -> &Path {
+        &self.path
+    }
+
+    fn file_name(&self) -> &OsStr {
+        self.path.file_name().unwrap_or(self.path.as_os_str())
+    }
+
+    fn file_type(&self) -> Option<FileType> {
+        Some(self.metadata.file_type())
+    }
+
+    fn path_is_symlink(&self) -> bool {
+        self.metadata.file_type().is_symlink()
+    }
+
+    fn metadata(&self) -> Option<Metadata> {
+        Some(self.metadata.clone())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::FileIndex;
+    use crate::file_handling::search::{QueryMode, SearchQuery, SearchType};
+    use std::fs;
+    use tokio::sync::mpsc;
+    use tokio_util::sync::CancellationToken;
+
+    fn names(index: &FileIndex, query: &SearchQuery) -> Vec<String> {
+        let (tx, _rx) = mpsc::unbounded_channel();
+        let root = index.root_of(&query.cwd).unwrap().unwrap();
+        index
+            .find_entries(&tx, query, &root, &CancellationToken::new())
+            .unwrap()
+            .map(|result| {
+                result
+                    .items()
+                    .iter()
+                    .map(|entry| entry.name.clone())
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
+    #[test]
+    fn test_index_is_updated_incrementally() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let dir = temp_dir.path().to_path_buf();
+        fs::create_dir_all(dir.join("src/nested")).unwrap();
+        fs::create_dir_all(dir.join("target")).unwrap();
+        fs::write(dir.join("src/main.rs"), "").unwrap();
+        fs::write(dir.join("src/nested/lib.rs"), "").unwrap();
+        fs::write(dir.join("target/build.rs"), "").unwrap();
+        fs::write(dir.join("README.md"), "").unwrap();
+
+        let (tx, _rx) = mpsc::unbounded_channel();
+        let token = CancellationToken::new();
+        let mut index = FileIndex::open(&dir.join("index.db")).unwrap();
+        let root = dir.join("src");
+        index
+            .update(&tx, std::slice::from_ref(&root), &token)
+            .unwrap();
+        let mut query = SearchQuery {
+            cwd: root.clone(),
+            pattern: "*.rs".to_string(),
+            search_type: SearchType::Name,
+            query_mode: QueryMode::Glob,
+            depth: usize::MAX,
+            ..Default::default()
+        };
+        assert_eq!(names(&index, &query), ["main.rs", "lib.rs"]);
+        assert_eq!(index.roots().unwrap()[0].entry_count, 3);
+
+        // a flat search only lists the entries of the directory itself
+        query.depth = 1;
+        assert_eq!(names(&index, &query), ["main.rs"]);
+
+        // excluded directories are skipped with their entries
+        query.depth = usize::MAX;
+        query.exclude_patterns = vec!["nested".to_string()];
+        assert_eq!(names(&index, &query), ["main.rs"]);
+        query.exclude_patterns.clear();
+
+        // the modified directories are read again, the removed entries are removed from the index
+        fs::remove_dir_all(dir.join("src/nested")).unwrap();
+        fs::write(dir.join("src/util.rs"), "").unwrap();
+        index
+            .update(&tx, std::slice::from_ref(&root), &token)
+            .unwrap();
+        assert_eq!(names(&index, &query), ["main.rs", "util.rs"]);
+        assert_eq!(index.roots().unwrap()[0].entry_count, 2);
+
+        // a directory inside an indexed directory is searched in the index, the others are walked
+        index
+            .update(&tx, std::slice::from_ref(&dir), &token)
+            .unwrap();
+        assert_eq!(index.roots().unwrap().len(), 1);
+        query.cwd = dir.join("target");
+        assert_eq!(names(&index, &query), ["build.rs"]);
+        query.cwd = std::env::temp_dir();
+        assert!(index.root_of(&query.cwd).unwrap().is_none());
+    }
+}
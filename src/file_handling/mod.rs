@@ -1,6 +1,12 @@
 use anyhow::Result;
+use ignore::WalkState;
 use serde::{Deserialize, Serialize};
-use std::path::PathBuf;
+use std::{
+    collections::HashSet,
+    path::{Path, PathBuf},
+    sync::atomic::{AtomicUsize, Ordering},
+    time::{Duration, Instant, SystemTime},
+};
 
 use tokio::{sync::mpsc, task::JoinHandle};
 use tokio_util::sync::CancellationToken;
@@ -8,12 +14,46 @@ use walkdir::WalkDir;
 
 use crate::{
     app::{AppState, actions::Action},
-    file_handling::metadata::{DirMetadata, FileMetadata},
+    file_handling::{
+        bulk::run_bulk_job,
+        checksum::compute_checksum,
+        content_search::{LineMatch, find_content_matches},
+        diff::diff_files,
+        dir_compare::{compare_dirs, run_sync},
+        disk_usage::{DiskUsageCache, delete_path},
+        duplicates::{DuplicateGroup, find_duplicates, resolve_duplicates},
+        format_search::find_entries_by_format,
+        git::GitInfo,
+        index::{find_indexed_entries, update_index},
+        metadata::{DirMetadata, FileMetadata},
+        reports::{run_report, sort_report_matches},
+        result_view::{ResultOrder, ResultRow},
+        search::{NameMatcher, QueryMode, SearchQuery, SearchType},
+        walker::{PROGRESS_INTERVAL, ProgressThrottle, WalkEntry, walk_parallel_streamed},
+    },
     models::Scrollable,
+    system::load_filesystem_info,
     utils,
 };
 
+pub mod bulk;
+pub mod checksum;
+pub mod content_search;
+pub mod diff;
+pub mod dir_compare;
+pub mod disk_usage;
+pub mod duplicates;
+pub mod filters;
+pub mod format_search;
+pub mod fuzzy;
+pub mod git;
+pub mod index;
 pub mod metadata;
+pub mod refine;
+pub mod reports;
+pub mod result_view;
+pub mod search;
+pub mod walker;
 
 #[cfg(not(windows))]
 pub const SEPARATOR: &str = "/";
@@ -21,11 +61,30 @@ pub const SEPARATOR: &str = "/";
 #[cfg(target_os = "windows")]
 pub const SEPARATOR: &str = "\\";
 
+/// Maximum number of entries that are collected before they are sent as one chunk to the Explorer
+const LOAD_DIR_CHUNK_SIZE: usize = 1000;
+
+/// Maximum time to wait before the collected entries are sent to the Explorer,
+/// even if the chunk is not full yet. This ensures that the first entries of a
+/// large directory are visible immediately.
+const LOAD_DIR_FLUSH_INTERVAL: Duration = Duration::from_millis(100);
+
 /// Represents the parent directory entry in the explorer list depending on the OS and the right separator
 pub fn parent_dir_entry() -> String {
     format!("..{}", SEPARATOR)
 }
 
+/// Read the file metadata of the given walkdir entry, if any
+fn read_file_metadata<E: WalkEntry>(entry: &E) -> Option<FileMetadata> {
+    entry.metadata().map(|metadata| FileMetadata {
+        created: metadata.created().ok(),
+        last_access: metadata.accessed().ok(),
+        modified: metadata.modified().ok(),
+        size: metadata.len(),
+        read_only: metadata.permissions().readonly(),
+    })
+}
+
 /// Represents a file or directory on disk.
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
 pub struct DiskEntry {
@@ -73,6 +132,47 @@ impl DiskEntry {
     }
 }
 
+/// Represents a long running operation, which is processed on a separate blocking thread
+/// and can be cancelled at any time via its own cancellation token.
+struct BackgroundJob {
+    task: JoinHandle<()>,
+    cancellation_token: CancellationToken,
+}
+
+impl BackgroundJob {
+    /// Spawns the given operation on a blocking thread.
+    /// The operation receives the cancellation token of the job and should check it regularly.
+    fn spawn<F>(operation: F) -> Self
+    where
+        F: FnOnce(CancellationToken) + Send + 'static,
+    {
+        let cancellation_token = CancellationToken::new();
+        let job_token = cancellation_token.clone();
+        let task = tokio::task::spawn_blocking(move || operation(job_token));
+
+        Self {
+            task,
+            cancellation_token,
+        }
+    }
+
+    /// Cancels the job and waits until the operation has been stopped
+    ///
+    /// # Returns
+    /// `true`, if the operation was still running
+    async fn cancel(self) -> bool {
+        let is_running = !self.task.is_finished();
+        self.cancellation_token.cancel();
+        if let Err(err) = self.task.await {
+            log::error!(
+                "Explorer: Background job terminated abnormally - Details {:?}",
+                err
+            );
+        }
+        is_running
+    }
+}
+
 /// Represents a task that runs the explorer
 pub struct ExplorerTask {
     task: JoinHandle<()>,
@@ -102,36 +202,312 @@ impl ExplorerTask {
         self.cancellation_token = CancellationToken::new();
         let _cancellation_token = self.cancellation_token.clone();
         self.task = tokio::task::spawn(async move {
+            // The currently running directory load, if any
+            let mut load_dir_job: Option<BackgroundJob> = None;
+            // The currently running git status computation, if any
+            let mut git_status_job: Option<BackgroundJob> = None;
+            // The currently running disk usage analysis, if any
+            let mut disk_usage_job: Option<BackgroundJob> = None;
+            // The currently running search, if any
+            let mut search_job: Option<BackgroundJob> = None;
+            // The currently running directory metadata calculation, if any
+            let mut dir_metadata_job: Option<BackgroundJob> = None;
+            // The currently running checksum computation, if any
+            let mut checksum_job: Option<BackgroundJob> = None;
+            // The currently running directory comparison or synchronization, if any
+            let mut dir_compare_job: Option<BackgroundJob> = None;
+            // The currently running bulk operation on search results, if any
+            let mut bulk_job: Option<BackgroundJob> = None;
+            // The currently running update of the filesystem index, if any
+            let mut index_job: Option<BackgroundJob> = None;
+
             loop {
                 tokio::select! {
                         _ = _cancellation_token.cancelled() => {
+                            for job in [load_dir_job.take(), git_status_job.take(), disk_usage_job.take(), search_job.take(), dir_metadata_job.take(), checksum_job.take(), dir_compare_job.take(), bulk_job.take(), index_job.take()].into_iter().flatten() {
+                                job.cancellation_token.cancel();
+                            }
                             break;
                           }
                         Some(action) = explorer_receiver.recv() => {
                             match action {
                                 Action::LoadDir(p, follow_sym_links) => {
-                                    action_sender.send(Action::UpdateAppState(AppState::Working("Loading directory...".into())))
-                                        .expect("Explorer: Unable to send 'Action::UpdateExplorerState'");
-                                    let explorer = Explorer::load_directory(p, follow_sym_links);
-                                    action_sender.send(Action::LoadDirDone(explorer)).expect("Explorer: Unable to send 'Action::LoadDirDone'");
+                                    // The user navigates away, so an unfinished load of the previous directory is no longer needed.
+                                    // Wait until it is stopped, to ensure that no outdated chunks are sent after the new load has started.
+                                    if let Some(job) = load_dir_job.take() {
+                                        job.cancel().await;
+                                    }
+
+                                    let action_sender = action_sender.clone();
+                                    load_dir_job = Some(BackgroundJob::spawn(move |cancellation_token| {
+                                        if Explorer::load_directory(&action_sender, p, follow_sym_links, &cancellation_token).is_err() {
+                                            log::error!("Explorer: Unable to send 'Action::LoadDirChunk' while loading a directory. The channel may have been dropped or closed before the sending completed.");
+                                        }
+                                    }));
+                                }
+                                Action::LoadGitStatus(p) => {
+                                    // The status of a large repository cannot be interrupted, so an outdated computation
+                                    // is not awaited, only its result is discarded
+                                    if let Some(job) = git_status_job.take() {
+                                        job.cancellation_token.cancel();
+                                    }
+
+                                    let action_sender = action_sender.clone();
+                                    git_status_job = Some(BackgroundJob::spawn(move |cancellation_token| {
+                                        let git_info = GitInfo::load(&p).unwrap_or_else(|err| {
+                                            log::error!("Explorer: Unable to read the git status of {} - Details {:?}", p.display(), err);
+                                            None
+                                        });
+                                        if !cancellation_token.is_cancelled() && action_sender.send(Action::GitStatusDone(git_info)).is_err() {
+                                            log::error!("Explorer: Unable to send 'Action::GitStatusDone'. The channel may have been dropped or closed before the sending completed.");
+                                        }
+                                    }));
+                                }
+                                Action::AnalyzeDiskUsage(p, follow_sym_links) => {
+                                    if let Some(job) = disk_usage_job.take() {
+                                        job.cancel().await;
+                                    }
+
+                                    let action_sender = action_sender.clone();
+                                    disk_usage_job = Some(BackgroundJob::spawn(move |cancellation_token| {
+                                        match DiskUsageCache::analyze(&action_sender, &p, follow_sym_links, &cancellation_token) {
+                                            Ok(_) if cancellation_token.is_cancelled() => {}
+                                            Ok(disk_usage) => {
+                                                if action_sender.send(Action::DiskUsageDone(disk_usage)).is_err() {
+                                                    log::error!("Explorer: Unable to send 'Action::DiskUsageDone'. The channel may have been dropped or closed before the sending completed.");
+                                                }
+                                            }
+                                            Err(_) => {
+                                                log::error!("Explorer: Unable to send 'Action::UpdateAppState' while analyzing the disk usage. The channel may have been dropped or closed before the sending completed.");
+                                            }
+                                        }
+                                    }));
+                                }
+                                Action::CancelDiskUsage => {
+                                    if let Some(job) = disk_usage_job.take() {
+                                        job.cancel().await;
+                                    }
+                                }
+                                Action::ComputeChecksum(p, algorithm) => {
+                                    if let Some(job) = checksum_job.take() {
+                                        job.cancel().await;
+                                    }
+
+                                    let action_sender = action_sender.clone();
+                                    checksum_job = Some(BackgroundJob::spawn(move |cancellation_token| {
+                                        let action = match compute_checksum(&action_sender, &p, algorithm, &cancellation_token) {
+                                            Ok(_) if cancellation_token.is_cancelled() => return,
+                                            Ok(checksum) => Action::ChecksumDone(checksum),
+                                            Err(err) => {
+                                                log::error!("Explorer: Unable to compute the checksum of {} - Details {:?}", p.display(), err);
+                                                Action::ChecksumFailure(format!("Unable to read {}", utils::format_path_for_display(&p)))
+                                            }
+                                        };
+                                        if action_sender.send(action).is_err() {
+                                            log::error!("Explorer: Unable to send 'Action::ChecksumDone'. The channel may have been dropped or closed before the sending completed.");
+                                        }
+                                    }));
+                                }
+                                Action::CancelChecksum => {
+                                    if let Some(job) = checksum_job.take() {
+                                        job.cancel().await;
+                                    }
+                                }
+                                Action::ComputeDiff(left, right) => {
+                                    let action_sender = action_sender.clone();
+                                    tokio::task::spawn_blocking(move || {
+                                        let action = match diff_files(&left, &right) {
+                                            Ok(diff) => Action::DiffDone(diff),
+                                            Err(err) => {
+                                                log::error!("Explorer: Unable to compare {} and {} - Details {:?}", left.display(), right.display(), err);
+                                                Action::DiffFailure("Unable to compare the files".to_string())
+                                            }
+                                        };
+                                        if action_sender.send(action).is_err() {
+                                            log::error!("Explorer: Unable to send 'Action::DiffDone'. The channel may have been dropped or closed before the sending completed.");
+                                        }
+                                    });
+                                }
+                                Action::CompareDirs(left, right, follow_sym_links) => {
+                                    if let Some(job) = dir_compare_job.take() {
+                                        job.cancel().await;
+                                    }
+
+                                    let action_sender = action_sender.clone();
+                                    dir_compare_job = Some(BackgroundJob::spawn(move |cancellation_token| {
+                                        match compare_dirs(&action_sender, &left, &right, follow_sym_links, &cancellation_token) {
+                                            Ok(_) if cancellation_token.is_cancelled() => {}
+                                            Ok(comparison) => {
+                                                if action_sender.send(Action::CompareDirsDone(comparison)).is_err() {
+                                                    log::error!("Explorer: Unable to send 'Action::CompareDirsDone'. The channel may have been dropped or closed before the sending completed.");
+                                                }
+                                            }
+                                            Err(_) => {
+                                                log::error!("Explorer: Unable to send 'Action::UpdateAppState' while comparing directories. The channel may have been dropped or closed before the sending completed.");
+                                            }
+                                        }
+                                    }));
+                                }
+                                Action::SyncDirs(operations) => {
+                                    if let Some(job) = dir_compare_job.take() {
+                                        job.cancel().await;
+                                    }
+
+                                    let action_sender = action_sender.clone();
+                                    dir_compare_job = Some(BackgroundJob::spawn(move |cancellation_token| {
+                                        match run_sync(&action_sender, &operations, &cancellation_token) {
+                                            Ok(Some((copied, errors))) => {
+                                                if action_sender.send(Action::SyncDirsDone(copied, errors)).is_err() {
+                                                    log::error!("Explorer: Unable to send 'Action::SyncDirsDone'. The channel may have been dropped or closed before the sending completed.");
+                                                }
+                                            }
+                                            Ok(None) => {}
+                                            Err(_) => {
+                                                log::error!("Explorer: Unable to send 'Action::UpdateAppState' while synchronizing directories. The channel may have been dropped or closed before the sending completed.");
+                                            }
+                                        }
+                                    }));
+                                }
+                                Action::CancelDirCompare => {
+                                    if let Some(job) = dir_compare_job.take() {
+                                        job.cancel().await;
+                                    }
+                                }
+                                Action::LoadFilesystemInfo(p) => {
+                                    let action_sender = action_sender.clone();
+                                    // reading the filesystem details may block on a slow or hung network mount
+                                    tokio::task::spawn_blocking(move || {
+                                        let filesystem = load_filesystem_info(&p);
+                                        if action_sender.send(Action::FilesystemInfoDone(p, filesystem)).is_err() {
+                                            log::error!("Explorer: Unable to send 'Action::FilesystemInfoDone'. The channel may have been dropped or closed before the sending completed.");
+                                        }
+                                    });
+                                }
+                                Action::DeletePath(p) => {
+                                    let action_sender = action_sender.clone();
+                                    tokio::task::spawn_blocking(move || {
+                                        let action = match delete_path(&p) {
+                                            Ok(_) => Action::DeleteDone(p),
+                                            Err(err) => {
+                                                log::error!("Explorer: Unable to delete {} - Details {:?}", p.display(), err);
+                                                Action::DeleteFailure(format!("Unable to delete {}", utils::format_path_for_display(&p)))
+                                            }
+                                        };
+                                        if action_sender.send(action).is_err() {
+                                            log::error!("Explorer: Unable to send 'Action::DeleteDone'. The channel may have been dropped or closed before the sending completed.");
+                                        }
+                                    });
                                 }
                                 Action::LoadDirMetadata(dir_name, path, follow_sym_links) => {
-                                    // handle result, if it was not possible to send a Action over the channel, we don't want to panic
-                                    // in this case, instead we log the error
-                                    match Explorer::get_dir_metadata(action_sender.clone(), dir_name, path, follow_sym_links) {
-                                        Ok(dir_metadata) => action_sender.send(Action::LoadDirMetadataDone(dir_metadata)).expect("Explorer: Unable to send 'Action::LoadDirMetadataDone'"),
-                                        Err(_) => {
-                                            log::error!("Explorer: Unable to send 'Action::UpdateExplorerState' while processing directory metadata. The channel may have been dropped or closed before the sending completed.");
-                                        },
+                                    if let Some(job) = dir_metadata_job.take() {
+                                        job.cancel().await;
+                                    }
+
+                                    let action_sender = action_sender.clone();
+                                    dir_metadata_job = Some(BackgroundJob::spawn(move |cancellation_token| {
+                                        // handle result, if it was not possible to send a Action over the channel, we don't want to panic
+                                        // in this case, instead we log the error
+                                        match Explorer::get_dir_metadata(&action_sender, dir_name, path, follow_sym_links, &cancellation_token) {
+                                            Ok(_) if cancellation_token.is_cancelled() => {}
+                                            Ok(dir_metadata) => {
+                                                if action_sender.send(Action::LoadDirMetadataDone(dir_metadata)).is_err() {
+                                                    log::error!("Explorer: Unable to send 'Action::LoadDirMetadataDone'. The channel may have been dropped or closed before the sending completed.");
+                                                }
+                                            }
+                                            Err(_) => {
+                                                log::error!("Explorer: Unable to send 'Action::UpdateExplorerState' while processing directory metadata. The channel may have been dropped or closed before the sending completed.");
+                                            },
+                                        }
+                                    }));
+                                }
+                                Action::CancelDirMetadata => {
+                                    // reported after the job has been stopped, so that no outdated progress overwrites it
+                                    if let Some(job) = dir_metadata_job.take()
+                                        && job.cancel().await
+                                        && action_sender.send(Action::UpdateAppState(AppState::Done("Metadata calculation cancelled".into()))).is_err()
+                                    {
+                                        log::error!("Explorer: Unable to send 'Action::UpdateAppState'. The channel may have been dropped or closed before the sending completed.");
                                     }
                                 }
-                                Action::StartSearch(cwd, search_query, depth, follow_sym_links) => {
-                                    match Explorer::find_entries_by_name(action_sender.clone(), cwd, search_query, depth, follow_sym_links) {
-                                        Ok(search_result) => action_sender.send(Action::SearchDone(search_result)).expect("Explorer: Unable to send 'Action::SearchDone'"),
-                                        Err(_) => {
-                                            log::error!("Explorer: Unable to send 'Action::UpdateExplorerState' while searching for files/folders. The channel may have been dropped or closed before the sending completed.");
-                                        },
+                                Action::StartSearch(query) => {
+                                    if let Some(job) = search_job.take() {
+                                        job.cancel().await;
                                     }
+
+                                    let action_sender = action_sender.clone();
+                                    search_job = Some(BackgroundJob::spawn(move |cancellation_token| {
+                                        let search_result = match query.search_type {
+                                            SearchType::Name => find_indexed_entries(&action_sender, &query, &cancellation_token)
+                                                .unwrap_or_else(|| Explorer::find_entries_by_name(action_sender.clone(), &query, &cancellation_token)),
+                                            SearchType::Content => find_content_matches(&action_sender, &query, &cancellation_token),
+                                            SearchType::Format => find_entries_by_format(&action_sender, &query, &cancellation_token),
+                                            SearchType::Duplicates => find_duplicates(&action_sender, &query, &cancellation_token),
+                                            SearchType::Report(kind) => run_report(&action_sender, &query, kind, &cancellation_token),
+                                        };
+
+                                        match search_result {
+                                            Ok(_) if cancellation_token.is_cancelled() => {}
+                                            Ok(search_result) => {
+                                                if action_sender.send(Action::SearchDone(search_result)).is_err() {
+                                                    log::error!("Explorer: Unable to send 'Action::SearchDone'. The channel may have been dropped or closed before the sending completed.");
+                                                }
+                                            }
+                                            Err(_) => {
+                                                log::error!("Explorer: Unable to send 'Action::UpdateExplorerState' while searching for files/folders. The channel may have been dropped or closed before the sending completed.");
+                                            },
+                                        }
+                                    }));
+                                }
+                                Action::CancelSearch => {
+                                    // reported after the job has been stopped, so that no outdated progress overwrites it
+                                    if let Some(job) = search_job.take()
+                                        && job.cancel().await
+                                        && action_sender.send(Action::UpdateAppState(AppState::Done("Search cancelled".into()))).is_err()
+                                    {
+                                        log::error!("Explorer: Unable to send 'Action::UpdateAppState'. The channel may have been dropped or closed before the sending completed.");
+                                    }
+                                }
+                                Action::RunBulkJob(job) => {
+                                    if let Some(job) = bulk_job.take() {
+                                        job.cancel().await;
+                                    }
+
+                                    let action_sender = action_sender.clone();
+                                    bulk_job = Some(BackgroundJob::spawn(move |cancellation_token| {
+                                        // a cancelled job is reported too, because some entries may already be moved or deleted
+                                        let sent = run_bulk_job(&action_sender, &job, &cancellation_token)
+                                            .and_then(|report| Ok(action_sender.send(Action::BulkJobDone(report))?));
+                                        if sent.is_err() {
+                                            log::error!("Explorer: Unable to send the progress of a bulk operation. The channel may have been dropped or closed before the sending completed.");
+                                        }
+                                    }));
+                                }
+                                Action::CancelBulkJob => {
+                                    if let Some(job) = bulk_job.take() {
+                                        job.cancel().await;
+                                    }
+                                }
+                                Action::UpdateIndex(dirs) => {
+                                    // a running update is restarted, the directories read so far are not read again
+                                    if let Some(job) = index_job.take() {
+                                        job.cancel().await;
+                                    }
+
+                                    let action_sender = action_sender.clone();
+                                    index_job = Some(BackgroundJob::spawn(move |cancellation_token| {
+                                        if update_index(&action_sender, &dirs, &cancellation_token).is_err() {
+                                            log::error!("Explorer: Unable to send the progress of the index update. The channel may have been dropped or closed before the sending completed.");
+                                        }
+                                    }));
+                                }
+                                Action::ResolveDuplicates(keep, others, resolution) => {
+                                    let action_sender = action_sender.clone();
+                                    tokio::task::spawn_blocking(move || {
+                                        let (resolved, errors) = resolve_duplicates(&keep, &others, resolution);
+                                        if action_sender.send(Action::ResolveDuplicatesDone(resolved, errors)).is_err() {
+                                            log::error!("Explorer: Unable to send 'Action::ResolveDuplicatesDone'. The channel may have been dropped or closed before the sending completed.");
+                                        }
+                                    });
                                 }
                                 _ => {}
                             }
@@ -173,6 +549,8 @@ impl ExplorerTask {
 /// Represents the search results for file/directory names
 #[derive(Debug, Default, Clone, Serialize, Deserialize, PartialEq, Eq)]
 pub struct SearchResult {
+    // The applied search type
+    search_type: SearchType,
     // The shorted CWD -> used as Block title
     cwd_display_name: String,
     // The selected item (DirEntry) in the table
@@ -184,7 +562,30 @@ pub struct SearchResult {
     // The index of the first item to display on the screen
     start_index: usize,
     search_query: String,
+    // The directory in which was searched, the fuzzy mode also scores the path relative to it
+    search_root: PathBuf,
+    // How the search query was matched against the names
+    query_mode: QueryMode,
+    case_sensitive: bool,
     items: Vec<DiskEntry>,
+    // The groups of identical files, only available for a duplicates search
+    duplicate_groups: Vec<DuplicateGroup>,
+    // The matching line of each item, only available for a content search
+    line_matches: Vec<LineMatch>,
+    // The detected format of each item, only available for a format search
+    detected_formats: Vec<String>,
+    // The follow-up queries, that narrowed the result without searching again
+    refinements: Vec<String>,
+    // How the rows are sorted and grouped, the items themselves always keep the order of the search
+    order: ResultOrder,
+    // The directories, whose entries are hidden while the result is grouped by directory
+    collapsed_dirs: HashSet<PathBuf>,
+    // The sorted rows with the directory headers, empty if the items are shown in the order of the search
+    rows: Vec<ResultRow>,
+    // The time of the last index update, only available for a search answered from the index
+    indexed_at: Option<SystemTime>,
+    // The search is still running and the matches are streamed into the result
+    is_in_progress: bool,
 }
 
 impl Scrollable for SearchResult {
@@ -192,7 +593,7 @@ impl Scrollable for SearchResult {
     fn page_up_by(&mut self, height: u16) {
         let page_height = height as usize;
 
-        if page_height >= self.items.len() {
+        if page_height >= self.row_count() {
             let iterations = self.selected;
 
             for _ in 0..iterations {
@@ -215,10 +616,9 @@ impl Scrollable for SearchResult {
     fn page_down_by(&mut self, height: u16) {
         let page_height = height as usize;
 
-        if page_height >= self.items.len() {
+        if page_height >= self.row_count() {
             let iterations = self
-                .items
-                .len()
+                .row_count()
                 .saturating_sub(1)
                 .saturating_sub(self.selected);
 
@@ -226,11 +626,10 @@ impl Scrollable for SearchResult {
                 self.scroll_down();
             }
         } else {
-            let iterations = if self.selected + page_height < self.items.len() {
+            let iterations = if self.selected + page_height < self.row_count() {
                 page_height
             } else {
-                self.items
-                    .len()
+                self.row_count()
                     .saturating_sub(1)
                     .saturating_sub(self.selected)
             };
@@ -245,8 +644,8 @@ impl Scrollable for SearchResult {
     /// and `selected` indices appropriately to reflect the current view and selection.
     fn scroll_up(&mut self) {
         if self.selected == 0 {
-            self.start_index = self.items.len().saturating_sub(self.terminal_height);
-            self.selected = self.items.len().saturating_sub(1);
+            self.start_index = self.row_count().saturating_sub(self.terminal_height);
+            self.selected = self.row_count().saturating_sub(1);
         } else if self.start_index > 0 {
             self.start_index = self.start_index.saturating_sub(1);
             self.selected = self.selected.saturating_sub(1);
@@ -258,7 +657,7 @@ impl Scrollable for SearchResult {
     /// Scrolls down through the table content. Adjusts the `start_index`,
     /// and `selected` indices appropriately to reflect the current view and selection.
     fn scroll_down(&mut self) {
-        if self.selected >= self.items.len().saturating_sub(1) {
+        if self.selected >= self.row_count().saturating_sub(1) {
             self.start_index = 0;
             self.selected = 0;
         } else if self.selected >= self.terminal_height - 1 {
@@ -271,6 +670,130 @@ impl Scrollable for SearchResult {
 }
 
 impl SearchResult {
+    /// Creates an empty result with the settings of the given query
+    pub fn for_query(query: &SearchQuery) -> Self {
+        Self {
+            cwd_display_name: utils::format_path_for_display(&query.cwd),
+            search_query: query.pattern.clone(),
+            search_root: query.cwd.clone(),
+            query_mode: query.query_mode,
+            case_sensitive: query.case_sensitive,
+            search_type: query.search_type,
+            ..Default::default()
+        }
+    }
+
+    /// Creates an empty result, that is filled with the matches while the search is running
+    pub fn in_progress(query: &SearchQuery) -> Self {
+        Self {
+            is_in_progress: true,
+            ..Self::for_query(query)
+        }
+    }
+
+    pub fn is_in_progress(&self) -> bool {
+        self.is_in_progress
+    }
+
+    /// Appends the streamed matches, the line matches are empty except for a content search
+    pub fn append_matches(&mut self, items: &[DiskEntry], line_matches: &[LineMatch]) {
+        self.items.extend_from_slice(items);
+        self.line_matches.extend_from_slice(line_matches);
+    }
+
+    /// Replaces the streamed matches with the current matches of a running search, e.g. the largest files so far
+    pub fn replace_matches(&mut self, items: &[DiskEntry]) {
+        let selected = self.selected_key();
+        self.items = items.to_vec();
+        self.line_matches.clear();
+        self.select_key(selected);
+    }
+
+    /// Replaces the streamed matches with the final result of the search.<br>
+    /// The final result is sorted, so the selected entry is searched and selected again.
+    pub fn complete(&mut self, result: &SearchResult) {
+        let selected = self.selected_key();
+        let terminal_height = self.terminal_height;
+
+        *self = result.clone();
+        self.terminal_height = terminal_height;
+        self.select_key(selected);
+    }
+
+    /// Stops streaming and sorts the partial result like a completed search
+    pub fn stop(&mut self) {
+        let selected = self.selected_key();
+        self.is_in_progress = false;
+
+        if self.line_matches.is_empty() {
+            self.sort_items();
+        } else {
+            let mut matches: Vec<(DiskEntry, LineMatch)> = std::mem::take(&mut self.items)
+                .into_iter()
+                .zip(std::mem::take(&mut self.line_matches))
+                .collect();
+            matches.sort_by(|(e1, l1), (e2, l2)| {
+                e1.path
+                    .cmp(&e2.path)
+                    .then(l1.line_number.cmp(&l2.line_number))
+            });
+            (self.items, self.line_matches) = matches.into_iter().unzip();
+        }
+
+        self.select_key(selected);
+    }
+
+    /// Sorts the items by path, the same order as a sequential walk that visits the entries of a directory sorted by name.<br>
+    /// The matches of a fuzzy search are ranked by score, entries with the same score are sorted by path.
+    /// The matches of a report are sorted in the order of the report
+    fn sort_items(&mut self) {
+        if let SearchType::Report(kind) = self.search_type {
+            sort_report_matches(kind, &mut self.items);
+            return;
+        }
+        match self.name_matcher().filter(|matcher| matcher.is_fuzzy()) {
+            Some(matcher) => self.items.sort_by_cached_key(|entry| {
+                let score = matcher.entry_score(&entry.name, &entry.path, &self.search_root);
+                (std::cmp::Reverse(score), entry.path.clone())
+            }),
+            None => self.items.sort_by(|e1, e2| e1.path.cmp(&e2.path)),
+        }
+    }
+
+    /// Returns the path and the line number of the selected item, to find it again after the items were reordered
+    fn selected_key(&self) -> Option<(PathBuf, Option<usize>)> {
+        let index = self.selected_index()?;
+        let entry = &self.items[index];
+        let line_number = self
+            .line_matches
+            .get(index)
+            .map(|line_match| line_match.line_number);
+        Some((entry.path.clone(), line_number))
+    }
+
+    fn select_key(&mut self, key: Option<(PathBuf, Option<usize>)>) {
+        self.reset_state();
+        let Some((path, line_number)) = key else {
+            return;
+        };
+
+        let index = self.items.iter().enumerate().position(|(i, entry)| {
+            entry.path == path
+                && self
+                    .line_matches
+                    .get(i)
+                    .map(|line_match| line_match.line_number)
+                    == line_number
+        });
+        let row = index.and_then(|index| self.row_of(index));
+        self.select_row(row.unwrap_or_default());
+    }
+
+    /// Returns the selected entry, `None` if a directory header is selected
+    pub fn selected_item(&self) -> Option<&DiskEntry> {
+        self.items.get(self.selected_index()?)
+    }
+
     pub fn set_terminal_height(&mut self, size: u16) {
         self.terminal_height = size as usize;
     }
@@ -296,11 +819,29 @@ impl SearchResult {
         &self.search_query
     }
 
-    /// Returns a vector containing the current page of directory content to display,
-    /// based on the `start_index` and `page_size`.
-    pub fn get_content_to_draw(&self) -> Vec<DiskEntry> {
-        let end = (self.start_index + self.terminal_height).min(self.items.len());
-        self.items[self.start_index..end].to_vec()
+    /// The directory, in which the search was started
+    pub fn search_root(&self) -> &Path {
+        &self.search_root
+    }
+
+    /// The time of the last index update, if the search was answered from the index
+    pub fn indexed_at(&self) -> Option<SystemTime> {
+        self.indexed_at
+    }
+
+    pub fn search_type(&self) -> SearchType {
+        self.search_type
+    }
+
+    /// Returns the matcher of the search query, used to highlight the matched parts of the names or lines.<br>
+    /// Reports use the search query as parameter, so there is nothing to highlight.
+    pub fn name_matcher(&self) -> Option<NameMatcher> {
+        match self.search_type {
+            SearchType::Name | SearchType::Content | SearchType::Duplicates => {
+                NameMatcher::new(&self.search_query, self.query_mode, self.case_sensitive).ok()
+            }
+            SearchType::Format | SearchType::Report(_) => None,
+        }
     }
 }
 
@@ -475,96 +1016,165 @@ impl Scrollable for Explorer {
 }
 
 impl Explorer {
-    /// Load the content of the given path
-    pub fn load_directory(p: PathBuf, follow_sym_links: bool) -> Self {
-        let cwd = p;
+    /// Constructs a new, empty [`Explorer`] for the given path.
+    /// The content of the directory is added via [`Explorer::append_entries`].
+    pub fn new(cwd: PathBuf) -> Self {
         let cwd_display_name = utils::format_path_for_display(&cwd);
 
-        let parent_dir_entry = parent_dir_entry();
+        let items = match cwd.parent() {
+            Some(parent) => vec![DiskEntry {
+                name: parent_dir_entry(),
+                path: parent.to_path_buf(),
+                file_metadata: None,
+                is_dir: true,
+            }],
+            None => vec![],
+        };
+
+        Self {
+            cwd,
+            cwd_display_name,
+            items,
+            file_counter: 0,
+            dir_counter: 0,
+            selected: 0,
+            terminal_height: 0,
+            start_index: 0,
+        }
+    }
 
-        let (mut dirs, mut files): (Vec<_>, Vec<_>) = WalkDir::new(cwd.clone())
+    /// Load the content of the given path and stream it in chunks to the app components.
+    ///
+    /// First a [`Action::LoadDirStarted`] is sent with an empty [`Explorer`] for the given path,
+    /// followed by several [`Action::LoadDirChunk`] and finally a [`Action::LoadDirDone`].
+    /// If the given cancellation token is cancelled, the loading stops without sending [`Action::LoadDirDone`].
+    ///
+    /// # Returns
+    /// An error, if it was not possible to send a [`Action`] over the channel
+    fn load_directory(
+        tx: &mpsc::UnboundedSender<Action>,
+        cwd: PathBuf,
+        follow_sym_links: bool,
+        cancellation_token: &CancellationToken,
+    ) -> Result<()> {
+        tx.send(Action::LoadDirStarted(Explorer::new(cwd.clone())))?;
+        tx.send(Action::UpdateAppState(AppState::Working(
+            "Loading directory...".into(),
+        )))?;
+
+        let mut chunk = Vec::with_capacity(LOAD_DIR_CHUNK_SIZE);
+        let mut entry_counter: usize = 0;
+        let mut last_flush = Instant::now();
+
+        for entry in WalkDir::new(&cwd)
+            .min_depth(1)
             .max_depth(1)
             .follow_links(follow_sym_links)
             .into_iter()
             .filter_map(Result::ok)
-            // exclude the current working directory!!!
-            .filter(|entry| entry.path() != cwd)
-            .map(|entry| {
-                let entry_name = entry.file_name().to_string_lossy().to_string();
-                let path = entry.path().to_path_buf();
-
-                let is_dir = entry.file_type().is_dir();
-
-                let mut file_metadata: Option<FileMetadata> = None;
+        {
+            if cancellation_token.is_cancelled() {
+                return Ok(());
+            }
 
-                let name = if is_dir {
-                    format!("{}{}", entry_name, SEPARATOR)
-                } else {
-                    file_metadata = entry.metadata().ok().map(|metadata| FileMetadata {
-                        created: metadata.created().ok(),
-                        last_access: metadata.accessed().ok(),
-                        modified: metadata.modified().ok(),
-                        size: metadata.len(),
-                        read_only: metadata.permissions().readonly(),
-                    });
-                    entry_name
-                };
+            let entry_name = entry.file_name().to_string_lossy().to_string();
+            let is_dir = entry.file_type().is_dir();
 
+            let disk_entry = if is_dir {
                 DiskEntry {
-                    name,
-                    path,
-                    file_metadata,
+                    name: format!("{}{}", entry_name, SEPARATOR),
+                    path: entry.path().to_path_buf(),
+                    file_metadata: None,
                     is_dir,
                 }
-            })
-            .partition(|file_entry| file_entry.is_dir); // Separate files and folders
+            } else {
+                DiskEntry {
+                    name: entry_name,
+                    path: entry.path().to_path_buf(),
+                    file_metadata: read_file_metadata(&entry),
+                    is_dir,
+                }
+            };
 
-        dirs.sort_by(|f1, f2| f1.name.cmp(&f2.name));
-        files.sort_by(|f1, f2| f1.name.cmp(&f2.name));
+            chunk.push(disk_entry);
+            entry_counter += 1;
 
-        let file_counter = files.len();
-        let dir_counter = dirs.len();
+            if chunk.len() >= LOAD_DIR_CHUNK_SIZE || last_flush.elapsed() >= LOAD_DIR_FLUSH_INTERVAL
+            {
+                tx.send(Action::LoadDirChunk(std::mem::take(&mut chunk)))?;
+                tx.send(Action::UpdateAppState(AppState::Working(format!(
+                    "Loading directory... {} entries",
+                    entry_counter
+                ))))?;
+                last_flush = Instant::now();
+            }
+        }
 
-        let dir_content = if let Some(parent) = cwd.parent() {
-            let mut disk_items = Vec::with_capacity(1 + dirs.len() + files.len());
+        if cancellation_token.is_cancelled() {
+            return Ok(());
+        }
 
-            disk_items.push(DiskEntry {
-                name: parent_dir_entry,
-                path: parent.to_path_buf(),
-                file_metadata: None,
-                is_dir: true,
-            });
+        if !chunk.is_empty() {
+            tx.send(Action::LoadDirChunk(chunk))?;
+        }
+        tx.send(Action::LoadDirDone)?;
 
-            disk_items.extend(dirs);
-            disk_items.extend(files);
+        Ok(())
+    }
 
-            disk_items
-        } else {
-            let mut disk_items = Vec::with_capacity(dirs.len() + files.len());
+    /// Adds a chunk of loaded directory entries to the [`Explorer`].
+    ///
+    /// The items remain sorted (parent directory entry, directories, files)
+    /// and the currently selected entry stays selected.
+    pub fn append_entries(&mut self, chunk: Vec<DiskEntry>) {
+        let (mut new_dirs, mut new_files): (Vec<_>, Vec<_>) =
+            chunk.into_iter().partition(|entry| entry.is_dir);
 
-            disk_items.extend(dirs);
-            disk_items.extend(files);
+        if new_dirs.is_empty() && new_files.is_empty() {
+            return;
+        }
 
-            disk_items
-        };
+        new_dirs.sort_by(|f1, f2| f1.name.cmp(&f2.name));
+        new_files.sort_by(|f1, f2| f1.name.cmp(&f2.name));
 
-        Self {
-            cwd,
-            cwd_display_name,
-            items: dir_content,
-            file_counter,
-            dir_counter,
-            selected: 0,
-            terminal_height: 0,
-            start_index: 0,
+        let selected_path = self
+            .items
+            .get(self.selected)
+            .map(|entry| entry.path.clone());
+
+        let parent_offset = usize::from(self.cwd.parent().is_some());
+        let mut items = std::mem::take(&mut self.items);
+        let files = items.split_off(parent_offset + self.dir_counter);
+        let dirs = items.split_off(parent_offset);
+
+        self.dir_counter += new_dirs.len();
+        self.file_counter += new_files.len();
+
+        items.reserve(self.dir_counter + self.file_counter);
+        items.extend(merge_sorted_by_name(dirs, new_dirs));
+        items.extend(merge_sorted_by_name(files, new_files));
+        self.items = items;
+
+        // keep the selected entry at the same position in the field of view
+        if let Some(selected_path) = selected_path {
+            if let Some(index) = self
+                .items
+                .iter()
+                .skip(self.selected)
+                .position(|entry| entry.path == selected_path)
+            {
+                self.selected += index;
+                self.start_index += index;
+            }
         }
     }
 
     fn get_dir_metadata(
-        tx: mpsc::UnboundedSender<Action>,
+        tx: &mpsc::UnboundedSender<Action>,
         dir_name: String,
         p: PathBuf,
         follow_sym_links: bool,
+        cancellation_token: &CancellationToken,
     ) -> Result<Option<DirMetadata>> {
         let mut dir_metadata = p.metadata().ok().map(|metadata| DirMetadata {
             dir_name,
@@ -576,10 +1186,13 @@ impl Explorer {
         });
 
         if let Some(metadata) = &mut dir_metadata {
+            // the progress of every entry would flood the channel, so that the cancellation is reported with a delay
+            let progress = ProgressThrottle::new(PROGRESS_INTERVAL);
             let result: Result<()> = WalkDir::new(p.clone())
                 .max_depth(usize::MAX)
                 .follow_links(follow_sym_links)
                 .into_iter()
+                .take_while(|_| !cancellation_token.is_cancelled())
                 .filter_map(Result::ok)
                 // exclude the current working directory!!!
                 .filter(|entry| entry.path() != p)
@@ -595,10 +1208,12 @@ impl Explorer {
                     }
 
                     // Don't panic here, because we want to be able to shutdown the app without a panic report
-                    tx.send(Action::UpdateAppState(AppState::Working(format!(
-                        "Calculate metadata... {} Files, {} Dirs",
-                        metadata.file_count, metadata.dir_count
-                    ))))?;
+                    if progress.is_due() {
+                        tx.send(Action::UpdateAppState(AppState::Working(format!(
+                            "Calculate metadata... {} Files, {} Dirs",
+                            metadata.file_count, metadata.dir_count
+                        ))))?;
+                    }
 
                     Ok(())
                 });
@@ -644,92 +1259,81 @@ impl Explorer {
         }
     }
 
+    /// Searches the file/directory names with one thread per core.<br>
+    /// The matches are streamed as [`Action::SearchChunk`] with each progress update.
+    /// The final matches are sorted by their path, so the result doesn't depend on the order in which the threads find them.
+    ///
+    /// # Returns
+    /// `None`, if nothing was found or the search was cancelled
     pub fn find_entries_by_name(
         tx: mpsc::UnboundedSender<Action>,
-        cwd: PathBuf,
-        search_query: String,
-        depth: usize,
-        follow_sym_links: bool,
+        query: &SearchQuery,
+        cancellation_token: &CancellationToken,
     ) -> Result<Option<SearchResult>> {
-        let lower_case_query = search_query.to_lowercase();
-        let mut matches: Vec<DiskEntry> = vec![];
-        let mut file_counter: usize = 0;
-        let mut dir_counter: usize = 0;
-
-        let search_result: Result<()> = WalkDir::new(cwd.clone())
-            .max_depth(depth)
-            .follow_links(follow_sym_links)
-            .sort_by_file_name()
-            .into_iter()
-            .filter_map(Result::ok)
-            // exclude the current working directory!!!
-            .filter(|entry| entry.path() != cwd)
-            .try_for_each(|entry| -> Result<()> {
+        let matcher = query.name_matcher()?;
+        let filter = query.filters.compile(query.case_sensitive)?;
+        let file_counter = AtomicUsize::new(0);
+        let dir_counter = AtomicUsize::new(0);
+
+        let matches = walk_parallel_streamed(
+            &tx,
+            query,
+            cancellation_token,
+            || {
+                format!(
+                    "Search in progress... {} Files, {} Dirs",
+                    file_counter.load(Ordering::Relaxed),
+                    dir_counter.load(Ordering::Relaxed)
+                )
+            },
+            |new_matches| Some(Action::SearchChunk(new_matches, vec![])),
+            |entry| {
                 let entry_name = entry.file_name().to_string_lossy().to_string();
-                let is_dir = entry.file_type().is_dir();
+                let is_dir = entry
+                    .file_type()
+                    .is_some_and(|file_type| file_type.is_dir());
 
                 if is_dir {
-                    dir_counter += 1;
+                    dir_counter.fetch_add(1, Ordering::Relaxed);
                 } else {
-                    file_counter += 1;
+                    file_counter.fetch_add(1, Ordering::Relaxed);
                 }
 
-                if entry_name.to_lowercase().contains(&lower_case_query) {
-                    let path = entry.path().to_path_buf();
-                    let disk_entry = if is_dir {
-                        DiskEntry {
-                            name: entry_name,
-                            path,
-                            file_metadata: None,
-                            is_dir,
-                        }
-                    } else {
-                        let file_metadata = entry.metadata().ok().map(|metadata| FileMetadata {
-                            created: metadata.created().ok(),
-                            last_access: metadata.accessed().ok(),
-                            modified: metadata.modified().ok(),
-                            size: metadata.len(),
-                            read_only: metadata.permissions().readonly(),
-                        });
-
-                        DiskEntry {
-                            name: entry_name,
-                            path,
-                            file_metadata,
-                            is_dir,
-                        }
-                    };
-
-                    matches.push(disk_entry);
+                if !matcher.is_entry_match(&entry_name, entry.path(), &query.cwd)
+                    || !filter.matches(&entry)
+                {
+                    return (None, WalkState::Continue);
                 }
 
-                // Don't panic here, because we want to be able to shutdown the app without a panic report
-                tx.send(Action::UpdateAppState(AppState::Working(format!(
-                    "Search in progress... {} Files, {} Dirs",
-                    &file_counter, &dir_counter
-                ))))?;
-
-                Ok(())
-            });
+                let disk_entry = DiskEntry {
+                    file_metadata: if is_dir {
+                        None
+                    } else {
+                        read_file_metadata(&entry)
+                    },
+                    name: entry_name,
+                    path: entry.into_path(),
+                    is_dir,
+                };
+                (Some(disk_entry), WalkState::Continue)
+            },
+        )?;
 
-        match search_result {
-            Ok(_) => {
-                if !matches.is_empty() {
-                    let result = SearchResult {
-                        cwd_display_name: utils::format_path_for_display(&cwd),
-                        search_query,
-                        items: matches,
-                        selected: Default::default(),
-                        terminal_height: Default::default(),
-                        start_index: Default::default(),
-                    };
-                    Ok(Some(result))
-                } else {
-                    Ok(None)
-                }
-            }
-            Err(err) => Err(anyhow::anyhow!(err)),
+        if cancellation_token.is_cancelled() || matches.is_empty() {
+            return Ok(None);
         }
+
+        let mut result = SearchResult {
+            items: matches,
+            ..SearchResult::for_query(query)
+        };
+        result.sort_items();
+        Ok(Some(result))
+    }
+
+    /// Returns the index of the entry with the given path, if it is already loaded
+    pub fn index_of(&self, path: &Path) -> Option<usize> {
+        self.items.iter().position(|entry| entry.path == path)
     }
 
     pub fn go_to_index(&mut self, index: usize) {
@@ -781,3 +1385,22 @@ impl Explorer {
         self.items[self.start_index..end].to_vec()
     }
 }
+
+/// Merges two by name sorted lists of disk entries into one sorted list
+fn merge_sorted_by_name(left: Vec<DiskEntry>, right: Vec<DiskEntry>) -> Vec<DiskEntry> {
+    let mut merged = Vec::with_capacity(left.len() + right.len());
+    let mut left = left.into_iter().peekable();
+    let mut right = right.into_iter().peekable();
+
+    while let (Some(l), Some(r)) = (left.peek(), right.peek()) {
+        if l.name <= r.name {
+            merged.extend(left.next());
+        } else {
+            merged.extend(right.next());
+        }
+    }
+
+    merged.extend(left);
+    merged.extend(right);
+    merged
+}
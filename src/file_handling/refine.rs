@@ -0,0 +1,336 @@
+use anyhow::{Result, bail};
+use std::{
+    ffi::OsStr,
+    fs::{FileType, Metadata},
+    path::{Path, PathBuf},
+};
+
+use crate::file_handling::{
+    DiskEntry, SearchResult,
+    duplicates::DuplicateGroup,
+    filters::{EntryFilter, parse_query},
+    search::{NameMatcher, QueryMode},
+    walker::WalkEntry,
+};
+
+/// An entry of a search result together with its current state on disk, so that the filters of a refinement
+/// can narrow a result by size, type or modification time without walking the directory again.<br>
+/// Symbolic links are not followed, like with the walks of the search, so the filters apply to the link itself.
+struct RefinedEntry<'a> {
+    entry: &'a DiskEntry,
+    /// Read once for all filters, the file type is derived from it
+    metadata: Option<Metadata>,
+}
+
+impl<'a> RefinedEntry<'a> {
+    fn new(entry: &'a DiskEntry) -> Self {
+        Self {
+            entry,
+            metadata: std::fs::symlink_metadata(&entry.path).ok(),
+        }
+    }
+}
+
+impl WalkEntry for RefinedEntry<'_> {
+    fn path(&self) -> &Path {
+        &self.entry.path
+    }
+
+    fn file_name(&self) -> &OsStr {
+        OsStr::new(&self.entry.name)
+    }
+
+    fn file_type(&self) -> Option<FileType> {
+        self.metadata.as_ref().map(Metadata::file_type)
+    }
+
+    fn path_is_symlink(&self) -> bool {
+        self.file_type()
+            .is_some_and(|file_type| file_type.is_symlink())
+    }
+
+    fn metadata(&self) -> Option<Metadata> {
+        self.metadata.clone()
+    }
+}
+
+/// A follow-up query, that narrows an existing search result instead of starting a new search.<br>
+/// The query has the same syntax as the search input: the words are matched against the names
+/// in the query mode of the search, the filters like `size:>1M` or `type:file` are applied to every entry.
+#[derive(Debug, Clone)]
+pub struct Refinement {
+    matcher: Option<NameMatcher>,
+    filter: EntryFilter,
+}
+
+impl Refinement {
+    pub fn new(text: &str, query_mode: QueryMode, case_sensitive: bool) -> Result<Self> {
+        let (pattern, filters) = parse_query(text)?;
+        if pattern.is_empty() && filters.is_empty() {
+            bail!("Enter a name or a filter like size:>1M or type:file to refine the results");
+        }
+
+        let matcher = (!pattern.is_empty())
+            .then(|| NameMatcher::new(&pattern, query_mode, case_sensitive))
+            .transpose()?;
+        Ok(Self {
+            matcher,
+            filter: filters.compile(case_sensitive)?,
+        })
+    }
+
+    pub fn matches(&self, entry: &DiskEntry, root: &Path) -> bool {
+        self.matcher
+            .as_ref()
+            .is_none_or(|matcher| matcher.is_entry_match(&entry.name, &entry.path, root))
+            && self.filter.matches(&RefinedEntry::new(entry))
+    }
+}
+
+impl SearchResult {
+    /// The follow-up queries, that narrowed the result of the search, from the first to the last refinement
+    pub fn refinements(&self) -> &[String] {
+        &self.refinements
+    }
+
+    /// Narrows the result to the entries, that match the follow-up query.<br>
+    /// The order of the entries is kept, the matching lines and formats stay attached to their entries.
+    /// Duplicate groups with less than two remaining files are removed completely.
+    ///
+    /// # Returns
+    /// `None`, if no entry matches the query
+    pub fn refine(&self, text: &str) -> Result<Option<SearchResult>> {
+        let refinement = Refinement::new(text, self.query_mode, self.case_sensitive)?;
+        let is_match: Vec<bool> = self
+            .items
+            .iter()
+            .map(|entry| refinement.matches(entry, &self.search_root))
+            .collect();
+
+        let mut refined = SearchResult {
+            search_type: self.search_type,
+            cwd_display_name: self.cwd_display_name.clone(),
+            terminal_height: self.terminal_height,
+            search_query: self.search_query.clone(),
+            search_root: self.search_root.clone(),
+            query_mode: self.query_mode,
+            case_sensitive: self.case_sensitive,
+            indexed_at: self.indexed_at,
+            refinements: [self.refinements.clone(), vec![text.trim().to_string()]].concat(),
+            ..Default::default()
+        };
+
+        if self.duplicate_groups.is_empty() {
+            for index in (0..self.items.len()).filter(|index| is_match[*index]) {
+                refined.items.push(self.items[index].clone());
+                refined
+                    .line_matches
+                    .extend(self.line_matches.get(index).cloned());
+                refined
+                    .detected_formats
+                    .extend(self.detected_formats.get(index).cloned());
+            }
+        } else {
+            for group in &self.duplicate_groups {
+                let remaining: Vec<DiskEntry> = group
+                    .range()
+                    .filter(|index| is_match[*index])
+                    .map(|index| self.items[index].clone())
+                    .collect();
+
+                if remaining.len() >= 2 {
+                    refined.duplicate_groups.push(DuplicateGroup {
+                        start: refined.items.len(),
+                        len: remaining.len(),
+                        size: group.size,
+                    });
+                    refined.items.extend(remaining);
+                }
+            }
+        }
+
+        if refined.items.is_empty() {
+            return Ok(None);
+        }
+        // the refined result is shown in the same order as the result it was refined from
+        refined.order = self.order;
+        refined.collapsed_dirs = self.collapsed_dirs.clone();
+        refined.rebuild_rows();
+        Ok(Some(refined))
+    }
+
+    /// Selects the previously selected entry again, after the terminal was resized while the result was not shown
+    fn restore_view(&mut self, terminal_height: usize) {
+        let selected = self.selected_key();
+        self.terminal_height = terminal_height;
+        self.select_key(selected);
+    }
+}
+
+/// Back and forward stack of the result pages, like the history of a web browser.<br>
+/// Refining a result pushes the broader result onto the back stack, so it can be shown again.
+/// The result that is currently shown is not part of the stack.
+#[derive(Debug, Default)]
+pub struct ResultHistory {
+    back: Vec<SearchResult>,
+    forward: Vec<SearchResult>,
+}
+
+impl ResultHistory {
+    /// Remembers the current result before it is replaced by a refined result.<br>
+    /// The forward stack is discarded, because it belongs to the replaced refinement.
+    pub fn push(&mut self, current: SearchResult) {
+        self.back.push(current);
+        self.forward.clear();
+    }
+
+    /// Replaces the current result with the previous one
+    ///
+    /// # Returns
+    /// `false`, if there is no previous result
+    pub fn back(&mut self, current: &mut SearchResult) -> bool {
+        Self::swap(&mut self.back, &mut self.forward, current)
+    }
+
+    /// Replaces the current result with the next one, that was left with [`ResultHistory::back`]
+    ///
+    /// # Returns
+    /// `false`, if there is no next result
+    pub fn forward(&mut self, current: &mut SearchResult) -> bool {
+        Self::swap(&mut self.forward, &mut self.back, current)
+    }
+
+    fn swap(
+        from: &mut Vec<SearchResult>,
+        to: &mut Vec<SearchResult>,
+        current: &mut SearchResult,
+    ) -> bool {
+        let Some(mut result) = from.pop() else {
+            return false;
+        };
+        result.restore_view(current.terminal_height);
+        to.push(std::mem::replace(current, result));
+        true
+    }
+
+    pub fn can_go_back(&self) -> bool {
+        !self.back.is_empty()
+    }
+
+    pub fn can_go_forward(&self) -> bool {
+        !self.forward.is_empty()
+    }
+
+    pub fn clear(&mut self) {
+        self.back.clear();
+        self.forward.clear();
+    }
+
+    /// Removes the deleted or moved entries from all stored results, so that no page shows entries that no longer exist
+    pub fn remove_paths(&mut self, removed: &[PathBuf]) {
+        self.back
+            .iter_mut()
+            .chain(self.forward.iter_mut())
+            .for_each(|result| result.remove_paths(removed));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::ResultHistory;
+    use crate::file_handling::{
+        DiskEntry, SearchResult,
+        search::{QueryMode, SearchQuery},
+    };
+    use std::path::PathBuf;
+
+    fn result(root: &std::path::Path, names: &[&str]) -> SearchResult {
+        let query = SearchQuery {
+            cwd: root.to_path_buf(),
+            pattern: "test".to_string(),
+            query_mode: QueryMode::Glob,
+            ..Default::default()
+        };
+        SearchResult {
+            items: names
+                .iter()
+                .map(|name| DiskEntry {
+                    name: name.to_string(),
+                    path: root.join(name),
+                    file_metadata: None,
+                    is_dir: false,
+                })
+                .collect(),
+            ..SearchResult::for_query(&query)
+        }
+    }
+
+    fn names(result: &SearchResult) -> Vec<&str> {
+        result.items().iter().map(|e| e.name.as_str()).collect()
+    }
+
+    #[test]
+    fn test_refine_by_name_and_filter() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path().to_path_buf();
+        std::fs::create_dir_all(root.join("test_dir")).unwrap();
+        std::fs::write(root.join("test.rs"), "fn main() {}").unwrap();
+        std::fs::write(root.join("test.txt"), "").unwrap();
+
+        let result = result(&root, &["test.rs", "test.txt", "test_dir"]);
+        let files = result.refine("type:file").unwrap().unwrap();
+        assert_eq!(names(&files), vec!["test.rs", "test.txt"]);
+
+        // the words are matched in the query mode of the search
+        let rust_files = files.refine("*.rs").unwrap().unwrap();
+        assert_eq!(names(&rust_files), vec!["test.rs"]);
+        assert_eq!(rust_files.refinements(), ["type:file", "*.rs"]);
+        assert_eq!(rust_files.search_query(), "test");
+
+        assert!(result.refine("size:>1M").unwrap().is_none());
+        assert!(result.refine("  ").is_err());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_refine_checks_symlinks_themselves() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path().to_path_buf();
+        let target = std::fs::File::create(root.join("old.log")).unwrap();
+        target
+            .set_modified(std::time::SystemTime::now() - std::time::Duration::from_secs(30 * 86400))
+            .unwrap();
+        std::os::unix::fs::symlink(root.join("old.log"), root.join("new.log")).unwrap();
+
+        // the new link matches, although its target was modified long ago
+        let result = result(&root, &["new.log", "old.log"]);
+        let recent = result.refine("modified:<7d").unwrap().unwrap();
+        assert_eq!(names(&recent), vec!["new.log"]);
+        let links = result.refine("type:symlink").unwrap().unwrap();
+        assert_eq!(names(&links), vec!["new.log"]);
+    }
+
+    #[test]
+    fn test_result_history() {
+        let root = PathBuf::from("/tmp");
+        let broad = result(&root, &["a", "b", "c"]);
+        let narrow = result(&root, &["a"]);
+
+        let mut history = ResultHistory::default();
+        let mut current = broad.clone();
+        assert!(!history.back(&mut current));
+
+        history.push(std::mem::replace(&mut current, narrow.clone()));
+        assert!(history.can_go_back() && !history.can_go_forward());
+
+        assert!(history.back(&mut current));
+        assert_eq!(current, broad);
+        assert!(history.forward(&mut current));
+        assert_eq!(current, narrow);
+
+        // a new refinement discards the forward stack
+        assert!(history.back(&mut current));
+        history.push(std::mem::replace(&mut current, narrow));
+        assert!(!history.can_go_forward());
+    }
+}
@@ -0,0 +1,338 @@
+use anyhow::{Result, bail};
+use serde::{Deserialize, Serialize};
+use std::{
+    path::Path,
+    time::{Duration, Instant, SystemTime},
+};
+use tokio::sync::mpsc;
+use tokio_util::sync::CancellationToken;
+
+use crate::{
+    app::{AppState, actions::Action},
+    file_handling::{
+        DiskEntry, SearchResult, read_file_metadata,
+        search::SearchQuery,
+        walker::{PROGRESS_INTERVAL, walk_sequential},
+    },
+};
+
+/// Number of files listed by the largest files report, if no number was entered
+const DEFAULT_LARGEST_FILES_LIMIT: usize = 100;
+
+/// Time span of the recently modified report, if no time span was entered
+const DEFAULT_MODIFIED_WITHIN: Duration = Duration::from_secs(24 * 60 * 60);
+
+/// Represents the canned reports over the files and directories of the search directory
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ReportKind {
+    /// The N largest files, the search query contains N
+    LargestFiles,
+    /// Files modified within a time span, the search query contains the time span e.g. `12h` or `7d`
+    RecentlyModified,
+    /// Empty files and directories
+    Empty,
+    /// Symbolic links whose target does not exist
+    BrokenSymlinks,
+}
+
+impl std::fmt::Display for ReportKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ReportKind::LargestFiles => write!(f, "Largest Files"),
+            ReportKind::RecentlyModified => write!(f, "Recently Modified"),
+            ReportKind::Empty => write!(f, "Empty"),
+            ReportKind::BrokenSymlinks => write!(f, "Broken Symlinks"),
+        }
+    }
+}
+
+impl ReportKind {
+    /// Checks the search query, that is used as parameter of the report
+    pub fn validate(&self, pattern: &str) -> Result<()> {
+        match self {
+            ReportKind::LargestFiles => parse_limit(pattern).map(|_| ()),
+            ReportKind::RecentlyModified => parse_time_span(pattern).map(|_| ()),
+            ReportKind::Empty | ReportKind::BrokenSymlinks => Ok(()),
+        }
+    }
+}
+
+/// Parses the number of files for the largest files report, an empty query uses the default
+fn parse_limit(pattern: &str) -> Result<usize> {
+    let pattern = pattern.trim();
+    if pattern.is_empty() {
+        return Ok(DEFAULT_LARGEST_FILES_LIMIT);
+    }
+
+    match pattern.parse::<usize>() {
+        Ok(limit) if limit > 0 => Ok(limit),
+        _ => bail!("Enter the number of files to list, e.g. 50"),
+    }
+}
+
+/// Parses a time span like `30m`, `12h` or `7d`, a number without unit is interpreted as hours.
+/// An empty query uses the default
+fn parse_time_span(pattern: &str) -> Result<Duration> {
+    let pattern = pattern.trim();
+    if pattern.is_empty() {
+        return Ok(DEFAULT_MODIFIED_WITHIN);
+    }
+
+    match parse_duration(pattern) {
+        Some(duration) => Ok(duration),
+        None => bail!("Enter a time span like 30m, 12h or 7d"),
+    }
+}
+
+/// Parses a positive time span like `30m`, `12h` or `7d`, a number without unit is interpreted as hours
+pub fn parse_duration(text: &str) -> Option<Duration> {
+    let text = text.trim().to_lowercase();
+
+    let (value, unit_secs) = match text.char_indices().last() {
+        Some((i, 'm')) => (&text[..i], 60),
+        Some((i, 'h')) => (&text[..i], 60 * 60),
+        Some((i, 'd')) => (&text[..i], 24 * 60 * 60),
+        _ => (text.as_str(), 60 * 60),
+    };
+
+    match value.parse::<u64>() {
+        Ok(value) if value > 0 => Some(Duration::from_secs(value.saturating_mul(unit_secs))),
+        _ => None,
+    }
+}
+
+fn is_broken_symlink(path: &Path) -> bool {
+    path.symlink_metadata()
+        .is_ok_and(|metadata| metadata.file_type().is_symlink())
+        && path.metadata().is_err()
+}
+
+fn is_empty_dir(path: &Path) -> bool {
+    std::fs::read_dir(path).is_ok_and(|mut entries| entries.next().is_none())
+}
+
+/// Runs the report over the directory of the given query.<br>
+/// The matches are streamed as [`Action::SearchChunk`] with each progress update. The largest files report
+/// streams its current top N as [`Action::SearchSnapshot`] instead, because a larger file replaces a listed one.
+///
+/// # Returns
+/// `None`, if nothing was found or the report was cancelled
+pub fn run_report(
+    tx: &mpsc::UnboundedSender<Action>,
+    query: &SearchQuery,
+    kind: ReportKind,
+    cancellation_token: &CancellationToken,
+) -> Result<Option<SearchResult>> {
+    let limit = match kind {
+        ReportKind::LargestFiles => parse_limit(&query.pattern)?,
+        _ => usize::MAX,
+    };
+    let modified_after = match kind {
+        ReportKind::RecentlyModified => SystemTime::now()
+            .checked_sub(parse_time_span(&query.pattern)?)
+            .unwrap_or(SystemTime::UNIX_EPOCH),
+        _ => SystemTime::UNIX_EPOCH,
+    };
+
+    let filter = query.filters.compile(query.case_sensitive)?;
+    let mut matches: Vec<DiskEntry> = vec![];
+    // number of matches, that were already streamed to the Results page
+    let mut streamed: usize = 0;
+    let mut entry_counter: usize = 0;
+    let mut last_progress = Instant::now();
+
+    for entry in walk_sequential(query)? {
+        if cancellation_token.is_cancelled() {
+            return Ok(None);
+        }
+
+        entry_counter += 1;
+        if last_progress.elapsed() >= PROGRESS_INTERVAL {
+            tx.send(Action::UpdateAppState(AppState::Working(format!(
+                "Creating report... {} entries scanned, {} matches",
+                entry_counter,
+                matches.len().min(limit)
+            ))))?;
+            if streamed < matches.len() {
+                if kind == ReportKind::LargestFiles {
+                    sort_report_matches(kind, &mut matches);
+                    matches.truncate(limit);
+                    tx.send(Action::SearchSnapshot(matches.clone()))?;
+                } else {
+                    tx.send(Action::SearchChunk(matches[streamed..].to_vec(), vec![]))?;
+                }
+                streamed = matches.len();
+            }
+            last_progress = Instant::now();
+        }
+
+        let entry = match entry {
+            Ok(entry) => entry,
+            // a broken symlink can't be followed, so the walk reports it as error
+            Err(err) => {
+                if kind == ReportKind::BrokenSymlinks
+                    && let Some(path) = err.path()
+                    && is_broken_symlink(path)
+                {
+                    matches.push(DiskEntry {
+                        name: path
+                            .file_name()
+                            .map(|name| name.to_string_lossy().to_string())
+                            .unwrap_or_default(),
+                        path: path.to_path_buf(),
+                        file_metadata: None,
+                        is_dir: false,
+                    });
+                }
+                continue;
+            }
+        };
+
+        let path = entry.path();
+        let is_dir = entry.file_type().is_dir();
+
+        let is_match = match kind {
+            ReportKind::LargestFiles | ReportKind::RecentlyModified => entry.file_type().is_file(),
+            ReportKind::Empty => {
+                if is_dir {
+                    is_empty_dir(path)
+                } else {
+                    entry.file_type().is_file()
+                        && entry.metadata().is_ok_and(|metadata| metadata.len() == 0)
+                }
+            }
+            ReportKind::BrokenSymlinks => entry.path_is_symlink() && is_broken_symlink(path),
+        };
+
+        if !is_match || !filter.matches(&entry) {
+            continue;
+        }
+
+        let file_metadata = if is_dir {
+            None
+        } else {
+            read_file_metadata(&entry)
+        };
+
+        if kind == ReportKind::RecentlyModified
+            && file_metadata
+                .as_ref()
+                .and_then(|metadata| metadata.modified)
+                .is_none_or(|modified| modified < modified_after)
+        {
+            continue;
+        }
+
+        matches.push(DiskEntry {
+            name: entry.file_name().to_string_lossy().to_string(),
+            path: path.to_path_buf(),
+            file_metadata,
+            is_dir,
+        });
+
+        // only keep the largest files, to limit the memory usage in huge directory trees
+        if kind == ReportKind::LargestFiles && matches.len() >= limit.saturating_mul(2) {
+            sort_report_matches(kind, &mut matches);
+            matches.truncate(limit);
+            // the listed files may have changed, so the next snapshot is always sent
+            streamed = 0;
+        }
+    }
+
+    sort_report_matches(kind, &mut matches);
+    matches.truncate(limit);
+
+    if matches.is_empty() {
+        return Ok(None);
+    }
+
+    Ok(Some(SearchResult {
+        items: matches,
+        ..SearchResult::for_query(query)
+    }))
+}
+
+/// Sorts the matches in the order of the report, also used for the partial result of a stopped report
+pub(super) fn sort_report_matches(kind: ReportKind, entries: &mut [DiskEntry]) {
+    match kind {
+        // the largest files first
+        ReportKind::LargestFiles => entries.sort_by_key(|entry| {
+            std::cmp::Reverse(
+                entry
+                    .file_metadata
+                    .as_ref()
+                    .map(|metadata| metadata.size)
+                    .unwrap_or_default(),
+            )
+        }),
+        // the most recently modified files first
+        ReportKind::RecentlyModified => entries.sort_by(|e1, e2| {
+            let modified1 = e1.file_metadata.as_ref().and_then(|m| m.modified);
+            let modified2 = e2.file_metadata.as_ref().and_then(|m| m.modified);
+            modified2.cmp(&modified1)
+        }),
+        ReportKind::Empty | ReportKind::BrokenSymlinks => {
+            entries.sort_by(|e1, e2| e1.path.cmp(&e2.path))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::file_handling::{metadata::FileMetadata, search::SearchType};
+    use std::path::PathBuf;
+
+    #[test]
+    fn test_parse_limit() {
+        assert_eq!(parse_limit("").unwrap(), DEFAULT_LARGEST_FILES_LIMIT);
+        assert_eq!(parse_limit(" 25 ").unwrap(), 25);
+        assert!(parse_limit("0").is_err());
+        assert!(parse_limit("ten").is_err());
+    }
+
+    #[test]
+    fn test_stopped_report_keeps_the_report_order() {
+        let file = |name: &str, size| DiskEntry {
+            name: name.to_string(),
+            path: PathBuf::from(name),
+            file_metadata: Some(FileMetadata {
+                created: None,
+                last_access: None,
+                modified: None,
+                read_only: false,
+                size,
+            }),
+            is_dir: false,
+        };
+        let mut result = SearchResult {
+            search_type: SearchType::Report(ReportKind::LargestFiles),
+            ..SearchResult::default()
+        };
+        result.replace_matches(&[file("a", 10), file("b", 30)]);
+        result.append_matches(&[file("c", 20)], &[]);
+
+        result.stop();
+        let names: Vec<&str> = result.items().iter().map(|e| e.name.as_str()).collect();
+        assert_eq!(names, vec!["b", "c", "a"]);
+    }
+
+    #[test]
+    fn test_parse_time_span() {
+        assert_eq!(parse_time_span("").unwrap(), DEFAULT_MODIFIED_WITHIN);
+        assert_eq!(
+            parse_time_span("30m").unwrap(),
+            Duration::from_secs(30 * 60)
+        );
+        assert_eq!(
+            parse_time_span("12").unwrap(),
+            Duration::from_secs(12 * 60 * 60)
+        );
+        assert_eq!(
+            parse_time_span("7D").unwrap(),
+            Duration::from_secs(7 * 24 * 60 * 60)
+        );
+        assert!(parse_time_span("d").is_err());
+        assert!(parse_time_span("1w").is_err());
+    }
+}
@@ -0,0 +1,400 @@
+use serde::{Deserialize, Serialize};
+use std::{
+    cmp::Reverse,
+    path::{Path, PathBuf},
+};
+
+use crate::file_handling::SearchResult;
+
+/// The column, by which the Results page is sorted
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum SortKey {
+    /// The order of the search: sorted by path, ranked by score for a fuzzy search
+    #[default]
+    Search,
+    Name,
+    Path,
+    Size,
+    Modified,
+    /// The detected format of a format search, the extension for all other search types
+    Format,
+}
+
+impl std::fmt::Display for SortKey {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SortKey::Search => write!(f, "Search order"),
+            SortKey::Name => write!(f, "Name"),
+            SortKey::Path => write!(f, "Path"),
+            SortKey::Size => write!(f, "Size"),
+            SortKey::Modified => write!(f, "Modified"),
+            SortKey::Format => write!(f, "Format"),
+        }
+    }
+}
+
+impl SortKey {
+    pub fn next(self) -> Self {
+        match self {
+            SortKey::Search => SortKey::Name,
+            SortKey::Name => SortKey::Path,
+            SortKey::Path => SortKey::Size,
+            SortKey::Size => SortKey::Modified,
+            SortKey::Modified => SortKey::Format,
+            SortKey::Format => SortKey::Search,
+        }
+    }
+}
+
+/// How the entries of the Results page are ordered and grouped
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ResultOrder {
+    pub key: SortKey,
+    pub descending: bool,
+    /// The entries are grouped under a header for their parent directory
+    pub group_by_dir: bool,
+}
+
+impl ResultOrder {
+    /// The entries are shown in the order of the search, without any rows in between
+    pub fn is_search_order(&self) -> bool {
+        *self == Self::default()
+    }
+}
+
+/// The header of the entries of one directory, if the result is grouped by directory
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DirHeader {
+    pub path: PathBuf,
+    /// Number of entries in the directory, also if the directory is collapsed
+    pub len: usize,
+    /// Total size of the files in the directory
+    pub size: u64,
+    pub is_collapsed: bool,
+}
+
+/// A row of the Results page
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ResultRow {
+    /// The index of the entry in the items of the result
+    Entry(usize),
+    Dir(DirHeader),
+}
+
+/// Identifies the selected row, to select it again after the rows have been rebuilt
+enum RowKey {
+    Entry(usize),
+    Dir(PathBuf),
+}
+
+/// The parent directory of an entry, which is the group of the entry
+fn parent_of(path: &Path) -> &Path {
+    path.parent().unwrap_or(path)
+}
+
+/// Sorts the indices by the key, equal keys keep their order
+fn sort_indices<K: Ord>(indices: &mut [usize], descending: bool, key: impl Fn(usize) -> K) {
+    if descending {
+        indices.sort_by_cached_key(|&index| Reverse(key(index)));
+    } else {
+        indices.sort_by_cached_key(|&index| key(index));
+    }
+}
+
+impl SearchResult {
+    pub fn order(&self) -> ResultOrder {
+        self.order
+    }
+
+    /// Orders the shown rows, the selected entry stays selected
+    pub fn set_order(&mut self, order: ResultOrder) {
+        let selected = self.selected_row_key();
+        self.order = order;
+        if !order.group_by_dir {
+            self.collapsed_dirs.clear();
+        }
+        self.rebuild_rows();
+        self.select_row_key(selected);
+    }
+
+    /// Collapses or expands the directory of the selected row, if the result is grouped by directory.<br>
+    /// Collapsing selects the header of the directory, so it can be expanded again.
+    pub fn set_selected_dir_collapsed(&mut self, is_collapsed: bool) {
+        if !self.order.group_by_dir {
+            return;
+        }
+        let dir = match self.selected_row_key() {
+            Some(RowKey::Dir(path)) => path,
+            Some(RowKey::Entry(index)) => parent_of(&self.items[index].path).to_path_buf(),
+            None => return,
+        };
+
+        if is_collapsed {
+            self.collapsed_dirs.insert(dir.clone());
+        } else {
+            self.collapsed_dirs.remove(&dir);
+        }
+        let selected = if is_collapsed {
+            Some(RowKey::Dir(dir))
+        } else {
+            self.selected_row_key()
+        };
+        self.rebuild_rows();
+        self.select_row_key(selected);
+    }
+
+    /// Returns the header of the selected row, if the selected row is a directory header
+    pub fn selected_dir_header(&self) -> Option<&DirHeader> {
+        match self.rows.get(self.selected) {
+            Some(ResultRow::Dir(header)) if !self.order.is_search_order() => Some(header),
+            _ => None,
+        }
+    }
+
+    /// Number of shown rows, the entries of collapsed directories are not shown
+    pub fn row_count(&self) -> usize {
+        if self.order.is_search_order() {
+            self.items.len()
+        } else {
+            self.rows.len()
+        }
+    }
+
+    /// Returns the index of the entry in the given row, `None` for a directory header
+    pub fn item_index_at(&self, row: usize) -> Option<usize> {
+        if self.order.is_search_order() {
+            return (row < self.items.len()).then_some(row);
+        }
+        match self.rows.get(row)? {
+            ResultRow::Entry(index) => Some(*index),
+            ResultRow::Dir(_) => None,
+        }
+    }
+
+    /// Returns the index of the selected entry, `None` if a directory header is selected
+    pub fn selected_index(&self) -> Option<usize> {
+        self.item_index_at(self.selected)
+    }
+
+    /// Returns the rows of the current page, only the rows of the page are cloned
+    pub fn get_content_to_draw(&self) -> Vec<ResultRow> {
+        let end = (self.start_index + self.terminal_height).min(self.row_count());
+        if self.order.is_search_order() {
+            (self.start_index..end).map(ResultRow::Entry).collect()
+        } else {
+            self.rows[self.start_index.min(end)..end].to_vec()
+        }
+    }
+
+    /// Sorts the indices of the entries and inserts the directory headers.<br>
+    /// Only the indices are sorted, the entries and their matching lines and formats keep their position.
+    pub(super) fn rebuild_rows(&mut self) {
+        self.rows.clear();
+        if self.order.is_search_order() {
+            return;
+        }
+
+        let items = &self.items;
+        let mut indices: Vec<usize> = (0..items.len()).collect();
+        let descending = self.order.descending;
+        match self.order.key {
+            SortKey::Search if descending => indices.reverse(),
+            SortKey::Search => {}
+            SortKey::Name => {
+                sort_indices(&mut indices, descending, |i| items[i].name.to_lowercase())
+            }
+            SortKey::Path => sort_indices(&mut indices, descending, |i| items[i].path.clone()),
+            SortKey::Size => sort_indices(&mut indices, descending, |i| {
+                items[i]
+                    .file_metadata
+                    .as_ref()
+                    .map(|metadata| metadata.size)
+            }),
+            SortKey::Modified => sort_indices(&mut indices, descending, |i| {
+                items[i]
+                    .file_metadata
+                    .as_ref()
+                    .and_then(|metadata| metadata.modified)
+            }),
+            SortKey::Format => sort_indices(&mut indices, descending, |i| {
+                match self.detected_formats.get(i) {
+                    Some(format) => format.to_lowercase(),
+                    None => items[i]
+                        .path
+                        .extension()
+                        .map(|ext| ext.to_string_lossy().to_lowercase())
+                        .unwrap_or_default(),
+                }
+            }),
+        }
+
+        if !self.order.group_by_dir {
+            self.rows = indices.into_iter().map(ResultRow::Entry).collect();
+            return;
+        }
+
+        // the directories are sorted by path, the sort order applies to the entries of each directory
+        indices.sort_by(|a, b| parent_of(&items[*a].path).cmp(parent_of(&items[*b].path)));
+        for group in
+            indices.chunk_by(|a, b| parent_of(&items[*a].path) == parent_of(&items[*b].path))
+        {
+            let path = parent_of(&items[group[0]].path).to_path_buf();
+            let is_collapsed = self.collapsed_dirs.contains(&path);
+            let size = group
+                .iter()
+                .filter_map(|index| items[*index].file_metadata.as_ref())
+                .map(|metadata| metadata.size)
+                .sum();
+
+            self.rows.push(ResultRow::Dir(DirHeader {
+                path,
+                len: group.len(),
+                size,
+                is_collapsed,
+            }));
+            if !is_collapsed {
+                self.rows
+                    .extend(group.iter().copied().map(ResultRow::Entry));
+            }
+        }
+    }
+
+    fn selected_row_key(&self) -> Option<RowKey> {
+        match self.selected_dir_header() {
+            Some(header) => Some(RowKey::Dir(header.path.clone())),
+            None => self.selected_index().map(RowKey::Entry),
+        }
+    }
+
+    fn select_row_key(&mut self, key: Option<RowKey>) {
+        let row = match key {
+            Some(RowKey::Entry(index)) => self.row_of(index),
+            Some(RowKey::Dir(path)) => self
+                .rows
+                .iter()
+                .position(|row| matches!(row, ResultRow::Dir(header) if header.path == path)),
+            None => None,
+        };
+        self.select_row(row.unwrap_or_default());
+    }
+
+    /// Returns the row of the entry, or the row of the header if the directory of the entry is collapsed
+    pub(super) fn row_of(&self, index: usize) -> Option<usize> {
+        if self.order.is_search_order() {
+            return (index < self.items.len()).then_some(index);
+        }
+        let dir = parent_of(&self.items.get(index)?.path);
+        self.rows.iter().position(|row| match row {
+            ResultRow::Entry(i) => *i == index,
+            ResultRow::Dir(header) => header.is_collapsed && header.path == dir,
+        })
+    }
+
+    /// Selects the row and scrolls it into view, like scrolling down to it from the first row
+    pub(super) fn select_row(&mut self, row: usize) {
+        self.selected = row.min(self.row_count().saturating_sub(1));
+        self.start_index = self
+            .selected
+            .saturating_sub(self.terminal_height.saturating_sub(1));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{ResultOrder, ResultRow, SortKey};
+    use crate::file_handling::{
+        DiskEntry, SearchResult, metadata::FileMetadata, search::SearchQuery,
+    };
+    use std::path::PathBuf;
+
+    fn entry(path: &str, size: u64) -> DiskEntry {
+        let path = PathBuf::from(path);
+        DiskEntry {
+            name: path.file_name().unwrap().to_string_lossy().to_string(),
+            path,
+            file_metadata: Some(FileMetadata {
+                created: None,
+                last_access: None,
+                modified: None,
+                read_only: false,
+                size,
+            }),
+            is_dir: false,
+        }
+    }
+
+    fn result() -> SearchResult {
+        let mut result = SearchResult {
+            items: vec![
+                entry("/data/a/b.txt", 30),
+                entry("/data/a/c.rs", 10),
+                entry("/data/z/A.md", 20),
+            ],
+            ..SearchResult::for_query(&SearchQuery::default())
+        };
+        result.set_terminal_height(10);
+        result
+    }
+
+    fn names(result: &SearchResult) -> Vec<String> {
+        result
+            .get_content_to_draw()
+            .into_iter()
+            .map(|row| match row {
+                ResultRow::Entry(index) => result.items()[index].name.clone(),
+                ResultRow::Dir(header) => format!("[{}]", header.path.display()),
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_sort_keeps_selection() {
+        let mut result = result();
+        result.select_row(1);
+
+        result.set_order(ResultOrder {
+            key: SortKey::Size,
+            descending: true,
+            ..Default::default()
+        });
+        assert_eq!(names(&result), ["b.txt", "A.md", "c.rs"]);
+        assert_eq!(result.selected_item().unwrap().name, "c.rs");
+
+        result.set_order(ResultOrder {
+            key: SortKey::Name,
+            ..Default::default()
+        });
+        assert_eq!(names(&result), ["A.md", "b.txt", "c.rs"]);
+
+        result.set_order(ResultOrder::default());
+        assert_eq!(names(&result), ["b.txt", "c.rs", "A.md"]);
+        assert_eq!(result.selected(), 1);
+    }
+
+    #[test]
+    fn test_group_by_dir() {
+        let mut result = result();
+        result.set_order(ResultOrder {
+            key: SortKey::Format,
+            group_by_dir: true,
+            ..Default::default()
+        });
+        assert_eq!(
+            names(&result),
+            ["[/data/a]", "c.rs", "b.txt", "[/data/z]", "A.md"]
+        );
+        // the selected entry stays selected below its directory header
+        assert_eq!(result.selected(), 2);
+        assert_eq!(result.selected_item().unwrap().name, "b.txt");
+
+        // collapsing the directory of an entry selects the header of the directory
+        result.set_selected_dir_collapsed(true);
+        assert_eq!(names(&result), ["[/data/a]", "[/data/z]", "A.md"]);
+        let header = result.selected_dir_header().unwrap();
+        assert_eq!((header.len, header.size), (2, 40));
+
+        result.set_selected_dir_collapsed(false);
+        assert_eq!(result.row_count(), 5);
+        assert_eq!(result.selected_index(), None);
+    }
+}
@@ -0,0 +1,455 @@
+use anyhow::{Result, anyhow, bail};
+use regex::{Regex, RegexBuilder};
+use serde::{Deserialize, Serialize};
+use std::{
+    ops::Range,
+    path::{Path, PathBuf},
+};
+
+use crate::file_handling::{
+    filters::SearchFilters,
+    format_search::FormatMatcher,
+    fuzzy::{fold_case, fuzzy_match},
+    reports::ReportKind,
+    walker::ExcludeFilter,
+};
+
+/// Bonus per pattern character of a fuzzy match in the name, so that a match in the name is ranked above a match in the path
+const FUZZY_NAME_BONUS: i64 = 16;
+
+/// Represents the kind of search that is applied to the current directory
+#[derive(Default, Debug, Copy, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum SearchType {
+    /// Search for file/directory names that match the search query
+    #[default]
+    Name,
+    /// Search for lines in the content of text files that match the search query
+    Content,
+    /// Search for files whose format detected from the content matches the search query, e.g. `pdf`, `image` or `video/*`
+    Format,
+    /// Search for files with identical content, optionally only files whose name contains the search query
+    Duplicates,
+    /// Canned report over the directory, the search query is used as parameter of the report
+    Report(ReportKind),
+}
+
+impl std::fmt::Display for SearchType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SearchType::Name => write!(f, "Name"),
+            SearchType::Content => write!(f, "Content"),
+            SearchType::Format => write!(f, "Format"),
+            SearchType::Duplicates => write!(f, "Duplicates"),
+            SearchType::Report(kind) => write!(f, "{}", kind),
+        }
+    }
+}
+
+impl SearchType {
+    /// Get the next available search type
+    pub fn next(self) -> Self {
+        match self {
+            SearchType::Name => SearchType::Content,
+            SearchType::Content => SearchType::Format,
+            SearchType::Format => SearchType::Duplicates,
+            SearchType::Duplicates => SearchType::Report(ReportKind::LargestFiles),
+            SearchType::Report(ReportKind::LargestFiles) => {
+                SearchType::Report(ReportKind::RecentlyModified)
+            }
+            SearchType::Report(ReportKind::RecentlyModified) => {
+                SearchType::Report(ReportKind::Empty)
+            }
+            SearchType::Report(ReportKind::Empty) => SearchType::Report(ReportKind::BrokenSymlinks),
+            SearchType::Report(ReportKind::BrokenSymlinks) => SearchType::Name,
+        }
+    }
+
+    /// Indicates whether a search query is required to start the search
+    pub fn requires_query(&self) -> bool {
+        match self {
+            SearchType::Name | SearchType::Content | SearchType::Format => true,
+            SearchType::Duplicates | SearchType::Report(_) => false,
+        }
+    }
+
+    /// The matches of the name and content search and of the reports are streamed into the Results page
+    /// while the search is running. Duplicates are only known, when all files were visited.
+    pub fn is_streamed(&self) -> bool {
+        match self {
+            SearchType::Name | SearchType::Content | SearchType::Report(_) => true,
+            SearchType::Format | SearchType::Duplicates => false,
+        }
+    }
+}
+
+/// Represents how the search query is matched against the file/directory names
+#[derive(Default, Debug, Copy, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum QueryMode {
+    /// The name must contain the query
+    #[default]
+    Plain,
+    /// The query is a regular expression, that must match a part of the name
+    Regex,
+    /// The query is a glob pattern like `*.rs`, that must match the whole name
+    Glob,
+    /// The characters of the query must appear in the name or the path in the same order, e.g. `srw` matches `search_widget.rs`.
+    /// The matches are ranked by score instead of path
+    Fuzzy,
+}
+
+impl std::fmt::Display for QueryMode {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            QueryMode::Plain => write!(f, "Plain"),
+            QueryMode::Regex => write!(f, "Regex"),
+            QueryMode::Glob => write!(f, "Glob"),
+            QueryMode::Fuzzy => write!(f, "Fuzzy"),
+        }
+    }
+}
+
+impl QueryMode {
+    /// Get the next available query mode
+    pub fn next(self) -> Self {
+        match self {
+            QueryMode::Plain => QueryMode::Regex,
+            QueryMode::Regex => QueryMode::Glob,
+            QueryMode::Glob => QueryMode::Fuzzy,
+            QueryMode::Fuzzy => QueryMode::Plain,
+        }
+    }
+}
+
+/// Matches file/directory names against the search query.<br>
+/// The plain, regex and glob query modes are compiled into a single regular expression,
+/// so the matched parts of a name can be highlighted the same way.
+/// The fuzzy mode matches the characters of the query one by one.
+#[derive(Debug, Clone)]
+pub struct NameMatcher {
+    kind: MatcherKind,
+}
+
+#[derive(Debug, Clone)]
+enum MatcherKind {
+    Regex(Regex),
+    Fuzzy {
+        /// The characters of the query without whitespaces, lowercased if the match is case insensitive
+        pattern: Vec<char>,
+        case_sensitive: bool,
+    },
+}
+
+impl NameMatcher {
+    /// Compiles the query in the given mode
+    ///
+    /// # Returns
+    /// An error with a single line description, if the query is not a valid regex or glob pattern
+    pub fn new(pattern: &str, mode: QueryMode, case_sensitive: bool) -> Result<Self> {
+        if mode == QueryMode::Fuzzy {
+            let pattern = pattern
+                .chars()
+                .filter(|c| !c.is_whitespace())
+                .map(|c| if case_sensitive { c } else { fold_case(c) })
+                .collect();
+            return Ok(Self {
+                kind: MatcherKind::Fuzzy {
+                    pattern,
+                    case_sensitive,
+                },
+            });
+        }
+
+        let expression = match mode {
+            QueryMode::Plain | QueryMode::Fuzzy => regex::escape(pattern),
+            QueryMode::Regex => pattern.to_string(),
+            QueryMode::Glob => {
+                glob_to_regex(pattern).map_err(|err| anyhow!("Invalid glob pattern: {err}"))?
+            }
+        };
+
+        let regex = RegexBuilder::new(&expression)
+            .case_insensitive(!case_sensitive)
+            .build()
+            .map_err(|err| anyhow!("Invalid {} pattern: {}", mode, regex_error_reason(&err)))?;
+
+        Ok(Self {
+            kind: MatcherKind::Regex(regex),
+        })
+    }
+
+    pub fn is_match(&self, name: &str) -> bool {
+        match &self.kind {
+            MatcherKind::Regex(regex) => regex.is_match(name),
+            MatcherKind::Fuzzy {
+                pattern,
+                case_sensitive,
+            } => fuzzy_match(pattern, name, *case_sensitive).is_some(),
+        }
+    }
+
+    pub fn is_fuzzy(&self) -> bool {
+        matches!(self.kind, MatcherKind::Fuzzy { .. })
+    }
+
+    /// Returns the byte ranges of all non-empty matches in the given name.<br>
+    /// A fuzzy match returns the ranges of the individual matched characters, consecutive characters are merged
+    pub fn match_ranges(&self, name: &str) -> Vec<Range<usize>> {
+        match &self.kind {
+            MatcherKind::Regex(regex) => regex
+                .find_iter(name)
+                .map(|m| m.range())
+                .filter(|range| !range.is_empty())
+                .collect(),
+            MatcherKind::Fuzzy {
+                pattern,
+                case_sensitive,
+            } => {
+                let Some(fuzzy) = fuzzy_match(pattern, name, *case_sensitive) else {
+                    return vec![];
+                };
+                let char_ranges: Vec<Range<usize>> = name
+                    .char_indices()
+                    .map(|(i, c)| i..i + c.len_utf8())
+                    .collect();
+
+                let mut ranges: Vec<Range<usize>> = vec![];
+                for range in fuzzy.positions.iter().map(|&p| char_ranges[p].clone()) {
+                    match ranges.last_mut() {
+                        Some(last) if last.end == range.start => last.end = range.end,
+                        _ => ranges.push(range),
+                    }
+                }
+                ranges
+            }
+        }
+    }
+
+    /// Matches an entry found below the search root.<br>
+    /// The fuzzy mode also matches the path relative to the root, if the name alone does not match
+    pub fn is_entry_match(&self, name: &str, path: &Path, root: &Path) -> bool {
+        self.entry_score(name, path, root).is_some()
+    }
+
+    /// Scores an entry found below the search root, higher is better.<br>
+    /// A fuzzy match in the name gets a bonus, so it is ranked above a match that only exists in the path.
+    /// All other query modes have no ranking and score `0`.
+    ///
+    /// # Returns
+    /// `None` if the entry does not match
+    pub fn entry_score(&self, name: &str, path: &Path, root: &Path) -> Option<i64> {
+        let MatcherKind::Fuzzy {
+            pattern,
+            case_sensitive,
+        } = &self.kind
+        else {
+            return self.is_match(name).then_some(0);
+        };
+
+        if let Some(fuzzy) = fuzzy_match(pattern, name, *case_sensitive) {
+            return Some(fuzzy.score + FUZZY_NAME_BONUS * pattern.len() as i64);
+        }
+        let relative_path = path.strip_prefix(root).unwrap_or(path);
+        fuzzy_match(pattern, &relative_path.to_string_lossy(), *case_sensitive)
+            .map(|fuzzy| fuzzy.score)
+    }
+}
+
+/// Extracts the reason of a regex error, the full message spans several lines and points to the faulty position
+fn regex_error_reason(err: &regex::Error) -> String {
+    let message = err.to_string();
+    message
+        .lines()
+        .find_map(|line| line.strip_prefix("error: "))
+        .unwrap_or(message.lines().last().unwrap_or_default())
+        .to_string()
+}
+
+/// Translates a glob pattern into an anchored regular expression.<br>
+/// Supports `*`, `?`, character classes like `[a-z]` or `[!0-9]`, alternatives like `{rs,toml}` and `\` as escape.
+fn glob_to_regex(pattern: &str) -> std::result::Result<String, &'static str> {
+    let mut expression = String::from("^");
+    let mut open_braces = 0;
+    let mut chars = pattern.chars();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '*' => expression.push_str(".*"),
+            '?' => expression.push('.'),
+            '\\' => match chars.next() {
+                Some(escaped) => expression.push_str(&regex::escape(&escaped.to_string())),
+                None => return Err("dangling escape at the end"),
+            },
+            '[' => {
+                expression.push('[');
+                let mut is_first = true;
+                let mut closed = false;
+                for c in chars.by_ref() {
+                    match c {
+                        ']' if !is_first => {
+                            closed = true;
+                            break;
+                        }
+                        '!' | '^' if is_first => expression.push('^'),
+                        '\\' | '[' | '&' | '~' | '^' | ']' => {
+                            expression.push('\\');
+                            expression.push(c);
+                        }
+                        _ => expression.push(c),
+                    }
+                    is_first = false;
+                }
+                if !closed {
+                    return Err("unclosed character class");
+                }
+                expression.push(']');
+            }
+            '{' => {
+                open_braces += 1;
+                expression.push_str("(?:");
+            }
+            '}' if open_braces > 0 => {
+                open_braces -= 1;
+                expression.push(')');
+            }
+            ',' if open_braces > 0 => expression.push('|'),
+            _ => expression.push_str(&regex::escape(&c.to_string())),
+        }
+    }
+
+    if open_braces > 0 {
+        return Err("unclosed alternative");
+    }
+
+    expression.push('$');
+    Ok(expression)
+}
+
+/// Represents all parameters of a search submitted on the Search-Page
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SearchQuery {
+    /// The directory in which to search
+    pub cwd: PathBuf,
+    /// The text entered by the user, without the filters
+    pub pattern: String,
+    /// The filters entered together with the text, e.g. `size:>100M`
+    pub filters: SearchFilters,
+    pub search_type: SearchType,
+    /// How the text is matched against the names
+    pub query_mode: QueryMode,
+    pub case_sensitive: bool,
+    /// Maximum depth of the directory walk
+    pub depth: usize,
+    pub follow_sym_links: bool,
+    /// Names, globs or absolute paths of the subtrees, that are skipped during the walk
+    pub exclude_patterns: Vec<String>,
+    /// Whether the walk stays on the filesystem of the search directory, so that e.g. mounted drives are skipped
+    pub same_file_system: bool,
+}
+
+impl SearchQuery {
+    /// Checks the entered text, before the search is started
+    pub fn validate(&self) -> Result<()> {
+        self.filters.compile(self.case_sensitive)?;
+        self.exclude_filter()?;
+        // without a query every line would match
+        if self.search_type == SearchType::Content && self.pattern.trim().is_empty() {
+            bail!("The content search requires a search query besides the filters");
+        }
+        // nearly every long line contains the characters of a short query
+        if self.search_type == SearchType::Content && self.query_mode == QueryMode::Fuzzy {
+            bail!("The fuzzy query mode is not available for the content search");
+        }
+        match self.search_type {
+            SearchType::Name | SearchType::Content | SearchType::Duplicates => {
+                self.name_matcher().map(|_| ())
+            }
+            SearchType::Format => FormatMatcher::new(&self.pattern).map(|_| ()),
+            SearchType::Report(kind) => kind.validate(&self.pattern),
+        }
+    }
+
+    /// Compiles the entered text into a matcher for the file/directory names, or the lines of a content search
+    pub fn name_matcher(&self) -> Result<NameMatcher> {
+        NameMatcher::new(&self.pattern, self.query_mode, self.case_sensitive)
+    }
+
+    pub fn exclude_filter(&self) -> Result<ExcludeFilter> {
+        ExcludeFilter::new(&self.exclude_patterns)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{NameMatcher, QueryMode};
+    use std::path::Path;
+
+    fn matcher(pattern: &str, mode: QueryMode, case_sensitive: bool) -> NameMatcher {
+        NameMatcher::new(pattern, mode, case_sensitive).unwrap()
+    }
+
+    #[test]
+    fn test_plain_matcher_escapes_the_query() {
+        let m = matcher("a.b", QueryMode::Plain, false);
+        assert!(m.is_match("xA.Bx"));
+        assert!(!m.is_match("axb"));
+
+        let m = matcher("Readme", QueryMode::Plain, true);
+        assert!(m.is_match("Readme.md"));
+        assert!(!m.is_match("README.md"));
+    }
+
+    #[test]
+    fn test_regex_matcher_ranges() {
+        let m = matcher(r"\d+", QueryMode::Regex, false);
+        assert_eq!(m.match_ranges("log_12_345.txt"), vec![4..6, 7..10]);
+        assert!(m.match_ranges("log.txt").is_empty());
+    }
+
+    #[test]
+    fn test_glob_matcher() {
+        let m = matcher("*.{rs,toml}", QueryMode::Glob, false);
+        assert!(m.is_match("main.rs"));
+        assert!(m.is_match("Cargo.TOML"));
+        assert!(!m.is_match("main.rs.bak"));
+
+        let m = matcher("file_[!0-9]?.txt", QueryMode::Glob, true);
+        assert!(m.is_match("file_ab.txt"));
+        assert!(!m.is_match("file_1b.txt"));
+        assert!(!m.is_match("FILE_ab.txt"));
+    }
+
+    #[test]
+    fn test_fuzzy_matcher_prefers_the_name() {
+        let m = matcher("mod rs", QueryMode::Fuzzy, false);
+        let root = Path::new("/project");
+        let name_score = m.entry_score("mod.rs", Path::new("/project/src/ui/mod.rs"), root);
+        let path_score = m.entry_score("main.rs", Path::new("/project/src/mod/main.rs"), root);
+        assert!(name_score.unwrap() > path_score.unwrap());
+        assert!(m.is_entry_match("main.rs", Path::new("/project/mod/main.rs"), root));
+        // the path above the search root is not matched
+        assert!(!m.is_entry_match(
+            "lib.rs",
+            Path::new("/mod/project/lib.rs"),
+            Path::new("/mod/project")
+        ));
+        assert_eq!(m.match_ranges("mod.rs"), vec![0..3, 4..6]);
+
+        let m = matcher("abc", QueryMode::Plain, false);
+        assert_eq!(m.entry_score("xabcx", Path::new("/xabcx"), root), Some(0));
+    }
+
+    #[test]
+    fn test_invalid_patterns() {
+        let err = NameMatcher::new("(abc", QueryMode::Regex, false).unwrap_err();
+        assert_eq!(err.to_string(), "Invalid Regex pattern: unclosed group");
+
+        let err = NameMatcher::new("[abc", QueryMode::Glob, false).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Invalid glob pattern: unclosed character class"
+        );
+
+        assert!(NameMatcher::new("{a,b", QueryMode::Glob, false).is_err());
+        assert!(NameMatcher::new("(abc", QueryMode::Plain, false).is_ok());
+    }
+}
@@ -0,0 +1,428 @@
+use anyhow::{Result, anyhow, bail};
+use ignore::{WalkBuilder, WalkState};
+use std::{
+    ffi::OsStr,
+    fs::{FileType, Metadata},
+    path::{Path, PathBuf},
+    sync::{
+        Mutex,
+        atomic::{AtomicBool, AtomicU64, Ordering},
+    },
+    time::{Duration, Instant},
+};
+use tokio::sync::mpsc;
+use tokio_util::sync::CancellationToken;
+
+use crate::{
+    app::{AppState, actions::Action},
+    file_handling::search::{NameMatcher, QueryMode, SearchQuery},
+};
+
+/// Minimum time between two progress updates of a long running job
+pub const PROGRESS_INTERVAL: Duration = Duration::from_millis(100);
+
+/// Common interface of the entries of the sequential (walkdir) and the parallel (ignore) directory walk,
+/// so that the filters can be applied to both
+pub trait WalkEntry {
+    fn path(&self) -> &Path;
+    fn file_name(&self) -> &OsStr;
+    /// `None` only for the standard input, which is never walked
+    fn file_type(&self) -> Option<FileType>;
+    fn path_is_symlink(&self) -> bool;
+    fn metadata(&self) -> Option<Metadata>;
+}
+
+impl WalkEntry for walkdir::DirEntry {
+    fn path(&self) -> &Path {
+        walkdir::DirEntry::path(self)
+    }
+
+    fn file_name(&self) -> &OsStr {
+        walkdir::DirEntry::file_name(self)
+    }
+
+    fn file_type(&self) -> Option<FileType> {
+        Some(walkdir::DirEntry::file_type(self))
+    }
+
+    fn path_is_symlink(&self) -> bool {
+        walkdir::DirEntry::path_is_symlink(self)
+    }
+
+    fn metadata(&self) -> Option<Metadata> {
+        walkdir::DirEntry::metadata(self).ok()
+    }
+}
+
+impl WalkEntry for ignore::DirEntry {
+    fn path(&self) -> &Path {
+        ignore::DirEntry::path(self)
+    }
+
+    fn file_name(&self) -> &OsStr {
+        ignore::DirEntry::file_name(self)
+    }
+
+    fn file_type(&self) -> Option<FileType> {
+        ignore::DirEntry::file_type(self)
+    }
+
+    fn path_is_symlink(&self) -> bool {
+        ignore::DirEntry::path_is_symlink(self)
+    }
+
+    fn metadata(&self) -> Option<Metadata> {
+        ignore::DirEntry::metadata(self).ok()
+    }
+}
+
+/// Subtrees, that are skipped during the directory walk.<br>
+/// A pattern is either an absolute path like `/proc`, or a glob for the name of a file or directory like `node_modules` or `*.bak`.
+#[derive(Debug, Clone, Default)]
+pub struct ExcludeFilter {
+    paths: Vec<PathBuf>,
+    names: Vec<NameMatcher>,
+}
+
+impl ExcludeFilter {
+    pub fn new(patterns: &[String]) -> Result<Self> {
+        let mut filter = Self::default();
+        for pattern in patterns {
+            if Path::new(pattern).is_absolute() {
+                filter.paths.push(PathBuf::from(pattern));
+            } else {
+                filter.names.push(
+                    NameMatcher::new(pattern, QueryMode::Glob, true)
+                        .map_err(|err| anyhow!("Exclude pattern '{pattern}': {err}"))?,
+                );
+            }
+        }
+        Ok(filter)
+    }
+
+    /// Checks if the entry and everything below it is excluded.
+    /// The start directory of a walk is never excluded, so that the user can search inside an excluded directory.
+    pub fn is_excluded(&self, path: &Path, depth: usize) -> bool {
+        depth > 0
+            && (self.paths.iter().any(|excluded| excluded == path)
+                || path.file_name().is_some_and(|name| {
+                    let name = name.to_string_lossy();
+                    self.names.iter().any(|matcher| matcher.is_match(&name))
+                }))
+    }
+}
+
+/// Walks the directory of the query sequentially, sorted by file name, except the directory itself.<br>
+/// The walk has the same depth, exclude and filesystem options as [`walk_parallel`].
+pub fn walk_sequential(
+    query: &SearchQuery,
+) -> Result<impl Iterator<Item = walkdir::Result<walkdir::DirEntry>>> {
+    let exclude = query.exclude_filter()?;
+
+    Ok(walkdir::WalkDir::new(&query.cwd)
+        .max_depth(query.depth)
+        .min_depth(1)
+        .follow_links(query.follow_sym_links)
+        .same_file_system(query.same_file_system)
+        .sort_by_file_name()
+        .into_iter()
+        // excluded directories are pruned, instead of filtering their content afterwards
+        .filter_entry(move |entry| !exclude.is_excluded(entry.path(), entry.depth())))
+}
+
+/// Walks the directory of the query with one thread per core and calls `visit` for every entry,
+/// except the directory itself.<br>
+/// Hidden and ignored files are not skipped, so the same entries as with walkdir are visited, but in no particular order.
+/// The walk stops, as soon as `visit` returns [`WalkState::Quit`].
+pub fn walk_parallel<F>(query: &SearchQuery, visit: F) -> Result<()>
+where
+    F: Fn(ignore::DirEntry) -> WalkState + Sync,
+{
+    let exclude = query.exclude_filter()?;
+
+    WalkBuilder::new(&query.cwd)
+        .standard_filters(false)
+        .follow_links(query.follow_sym_links)
+        .max_depth(Some(query.depth))
+        .same_file_system(query.same_file_system)
+        // excluded directories are pruned, instead of filtering their content afterwards
+        .filter_entry(move |entry| !exclude.is_excluded(entry.path(), entry.depth()))
+        // 0 -> number of threads is chosen by the available cores
+        .threads(0)
+        .build_parallel()
+        .run(|| {
+            let visit = &visit;
+            Box::new(move |entry| match entry {
+                Ok(entry) if entry.depth() > 0 => visit(entry),
+                // unreadable entries are skipped like with walkdir
+                _ => WalkState::Continue,
+            })
+        });
+    Ok(())
+}
+
+/// Walks the directory of the query like [`walk_parallel`] and collects the matches returned by `visit`.<br>
+/// At most every [`PROGRESS_INTERVAL`] the text of `progress_message` is sent as progress, followed by the
+/// matches found since the last update, which `to_chunk` converts into an action, e.g. [`Action::SearchChunk`].
+/// A search, that is not streamed, returns `None` from `to_chunk`.
+///
+/// # Returns
+/// The matches in the order in which the threads found them, an error if the channel is closed
+pub fn walk_parallel_streamed<T, V, M, C>(
+    tx: &mpsc::UnboundedSender<Action>,
+    query: &SearchQuery,
+    cancellation_token: &CancellationToken,
+    progress_message: M,
+    to_chunk: C,
+    visit: V,
+) -> Result<Vec<T>>
+where
+    T: Clone + Send,
+    V: Fn(ignore::DirEntry) -> (Option<T>, WalkState) + Sync,
+    M: Fn() -> String + Sync,
+    C: Fn(Vec<T>) -> Option<Action> + Sync,
+{
+    let matches: MatchCollector<T> = MatchCollector::default();
+    let progress = ProgressThrottle::new(PROGRESS_INTERVAL);
+    let is_channel_closed = AtomicBool::new(false);
+
+    walk_parallel(query, |entry| {
+        if cancellation_token.is_cancelled() {
+            return WalkState::Quit;
+        }
+
+        if progress.is_due() {
+            let new_matches = matches.take_new();
+            let is_sent = tx
+                .send(Action::UpdateAppState(
+                    AppState::Working(progress_message()),
+                ))
+                .is_ok()
+                && (new_matches.is_empty()
+                    || to_chunk(new_matches).is_none_or(|chunk| tx.send(chunk).is_ok()));
+
+            if !is_sent {
+                // Don't panic here, because we want to be able to shutdown the app without a panic report
+                is_channel_closed.store(true, Ordering::Relaxed);
+                return WalkState::Quit;
+            }
+        }
+
+        let (item, walk_state) = visit(entry);
+        if let Some(item) = item {
+            matches.push(item);
+        }
+        walk_state
+    })?;
+
+    if is_channel_closed.load(Ordering::Relaxed) {
+        bail!("Unable to send the search progress, the channel is closed");
+    }
+    matches.into_inner()
+}
+
+/// Limits the progress updates of the parallel walk to one per interval, independent of the number of threads
+#[derive(Debug)]
+pub struct ProgressThrottle {
+    start: Instant,
+    interval: Duration,
+    /// Milliseconds since start of the last progress update
+    last_update: AtomicU64,
+}
+
+impl ProgressThrottle {
+    pub fn new(interval: Duration) -> Self {
+        Self {
+            start: Instant::now(),
+            interval,
+            last_update: AtomicU64::new(0),
+        }
+    }
+
+    /// Returns `true` for exactly one caller per interval
+    pub fn is_due(&self) -> bool {
+        let now = self.start.elapsed().as_millis() as u64;
+        let last_update = self.last_update.load(Ordering::Relaxed);
+
+        now >= last_update + self.interval.as_millis() as u64
+            && self
+                .last_update
+                .compare_exchange(last_update, now, Ordering::Relaxed, Ordering::Relaxed)
+                .is_ok()
+    }
+}
+
+/// Collects the matches of the parallel walk and keeps track of the matches,
+/// that were not yet streamed to the Results page
+#[derive(Debug)]
+pub struct MatchCollector<T> {
+    /// All matches and the number of already streamed matches
+    matches: Mutex<(Vec<T>, usize)>,
+}
+
+impl<T> Default for MatchCollector<T> {
+    fn default() -> Self {
+        Self {
+            matches: Mutex::new((vec![], 0)),
+        }
+    }
+}
+
+impl<T: Clone> MatchCollector<T> {
+    pub fn push(&self, item: T) {
+        if let Ok(mut matches) = self.matches.lock() {
+            matches.0.push(item);
+        }
+    }
+
+    /// Returns the matches, that were added since the last call
+    pub fn take_new(&self) -> Vec<T> {
+        match self.matches.lock() {
+            Ok(mut matches) => {
+                let (items, streamed) = &mut *matches;
+                let new_items = items[*streamed..].to_vec();
+                *streamed = items.len();
+                new_items
+            }
+            Err(_) => vec![],
+        }
+    }
+
+    pub fn into_inner(self) -> Result<Vec<T>> {
+        self.matches
+            .into_inner()
+            .map(|(items, _)| items)
+            .map_err(|_| anyhow!("A search thread panicked"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        ExcludeFilter, MatchCollector, ProgressThrottle, walk_parallel, walk_parallel_streamed,
+        walk_sequential,
+    };
+    use crate::file_handling::search::SearchQuery;
+    use ignore::WalkState;
+    use std::{path::Path, time::Duration};
+    use tokio_util::sync::CancellationToken;
+
+    #[test]
+    fn test_progress_throttle() {
+        let throttle = ProgressThrottle::new(Duration::from_millis(50));
+        // the first update is due after the first interval
+        assert!(!throttle.is_due());
+
+        std::thread::sleep(Duration::from_millis(60));
+        assert!(throttle.is_due());
+        assert!(!throttle.is_due());
+    }
+
+    #[test]
+    fn test_match_collector_streams_new_matches_once() {
+        let collector = MatchCollector::default();
+        collector.push(1);
+        collector.push(2);
+        assert_eq!(collector.take_new(), vec![1, 2]);
+        assert!(collector.take_new().is_empty());
+
+        collector.push(3);
+        assert_eq!(collector.take_new(), vec![3]);
+        assert_eq!(collector.into_inner().unwrap(), vec![1, 2, 3]);
+    }
+
+    #[test]
+    fn test_streamed_walk_collects_the_matches() {
+        let dir = tempfile::tempdir().unwrap();
+        for name in ["a.rs", "b.txt", "c.rs"] {
+            std::fs::write(dir.path().join(name), "").unwrap();
+        }
+        let query = SearchQuery {
+            cwd: dir.path().to_path_buf(),
+            depth: usize::MAX,
+            ..Default::default()
+        };
+        let (tx, _rx) = tokio::sync::mpsc::unbounded_channel();
+        let token = CancellationToken::new();
+        let visit = |entry: ignore::DirEntry| {
+            let name = entry.file_name().to_string_lossy().to_string();
+            let is_match = name.ends_with(".rs");
+            (is_match.then_some(name), WalkState::Continue)
+        };
+
+        let mut matches =
+            walk_parallel_streamed(&tx, &query, &token, String::new, |_| None, visit).unwrap();
+        matches.sort();
+        assert_eq!(matches, vec!["a.rs", "c.rs"]);
+
+        // a cancelled walk stops before the first entry
+        token.cancel();
+        let matches =
+            walk_parallel_streamed(&tx, &query, &token, String::new, |_| None, visit).unwrap();
+        assert!(matches.is_empty());
+    }
+
+    #[test]
+    fn test_exclude_filter() {
+        let filter = ExcludeFilter::new(&[
+            "node_modules".to_string(),
+            "*.bak".to_string(),
+            "/proc".to_string(),
+        ])
+        .unwrap();
+
+        assert!(filter.is_excluded(Path::new("/home/user/app/node_modules"), 2));
+        assert!(filter.is_excluded(Path::new("/home/user/notes.bak"), 1));
+        assert!(filter.is_excluded(Path::new("/proc"), 1));
+        assert!(!filter.is_excluded(Path::new("/home/user/proc"), 2));
+        assert!(!filter.is_excluded(Path::new("/home/user/node_modules_old"), 1));
+        // the start directory is searched, even if it is excluded
+        assert!(!filter.is_excluded(Path::new("/home/user/app/node_modules"), 0));
+
+        assert!(ExcludeFilter::new(&["[abc".to_string()]).is_err());
+    }
+
+    #[test]
+    fn test_walks_prune_excluded_directories() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path().to_path_buf();
+        std::fs::create_dir_all(root.join("app/node_modules/lib")).unwrap();
+        std::fs::create_dir_all(root.join("app/src")).unwrap();
+        std::fs::write(root.join("app/node_modules/lib/index.js"), "").unwrap();
+        std::fs::write(root.join("app/src/main.js"), "").unwrap();
+
+        let query = SearchQuery {
+            cwd: root.clone(),
+            depth: usize::MAX,
+            exclude_patterns: vec!["node_modules".to_string()],
+            ..Default::default()
+        };
+        let relative = |path: &Path| path.strip_prefix(&root).unwrap().to_path_buf();
+        let expected = vec![
+            Path::new("app").to_path_buf(),
+            Path::new("app/src").to_path_buf(),
+            Path::new("app/src/main.js").to_path_buf(),
+        ];
+
+        let sequential: Vec<_> = walk_sequential(&query)
+            .unwrap()
+            .filter_map(Result::ok)
+            .map(|entry| relative(entry.path()))
+            .collect();
+        assert_eq!(sequential, expected);
+
+        let parallel = std::sync::Mutex::new(vec![]);
+        walk_parallel(&query, |entry| {
+            parallel.lock().unwrap().push(relative(entry.path()));
+            WalkState::Continue
+        })
+        .unwrap();
+        let mut parallel = parallel.into_inner().unwrap();
+        parallel.sort();
+        assert_eq!(parallel, expected);
+
+        // the depth limits the walk as well
+        let query = SearchQuery { depth: 1, ..query };
+        assert_eq!(walk_sequential(&query).unwrap().count(), 1);
+    }
+}
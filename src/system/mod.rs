@@ -1,10 +1,31 @@
 #![allow(dead_code)]
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+
 use sysinfo::{CpuRefreshKind, Disks, MemoryRefreshKind, RefreshKind, System};
 
 use crate::utils;
 
 const MAX_VALUE_LENGTH: usize = 20;
 
+/// Represents the number of total and free inodes of a filesystem
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct InodeUsage {
+    pub total: u64,
+    pub free: u64,
+}
+
+/// Represents the filesystem which contains a specific path
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FilesystemInfo {
+    pub mount_point: PathBuf,
+    pub fs_type: String,
+    pub total_space: u64,
+    pub available_space: u64,
+    /// Not available on all platforms and filesystems
+    pub inodes: Option<InodeUsage>,
+}
+
 /// Represents the specific System details of the underlying machine
 #[derive(Debug, Default)]
 pub struct SystemDetails {
@@ -21,8 +42,9 @@ pub struct SystemDetails {
     pub cpu_cores: usize,
     pub cpu_arch: String,
     pub cpu_usage: f32,
+    /// The filesystem of the current working directory, if any
+    pub filesystem: Option<FilesystemInfo>,
     system: System,
-    disks: Disks,
 }
 
 impl SystemDetails {
@@ -57,18 +79,13 @@ impl SystemDetails {
         let cpu_arch =
             utils::reduce_string_and_fill_with_dots(&System::cpu_arch(), MAX_VALUE_LENGTH);
 
-        let disks = Disks::new_with_refreshed_list();
-
-        let (total_space, available_space) = Self::get_local_disk_space(&disks);
-        let used_space = total_space - available_space;
-
         Self {
             total_memory: system.total_memory(),
             used_memory: system.used_memory(),
             total_swap: system.total_swap(),
             used_swap: system.used_swap(),
-            total_space,
-            used_space,
+            total_space: 0,
+            used_space: 0,
             system_name,
             kernel_version,
             os_version,
@@ -76,37 +93,123 @@ impl SystemDetails {
             cpu_cores: system.cpus().len(),
             cpu_usage: system.global_cpu_usage(),
             cpu_arch,
+            filesystem: None,
             system,
-            disks,
         }
     }
 
-    /// Refresh the CPU, Memory/Swap and disk usage
+    /// Refresh the CPU and Memory/Swap usage
     pub fn refresh(&mut self) {
-        self.disks.refresh(true);
         self.system.refresh_cpu_all();
         self.system.refresh_memory();
 
-        let (total_space, available_space) = Self::get_local_disk_space(&self.disks);
-        let used_space = total_space - available_space;
-
-        self.total_space = total_space;
-        self.used_space = used_space;
         self.used_memory = self.system.used_memory();
         self.used_swap = self.system.used_swap();
         self.cpu_usage = self.system.global_cpu_usage();
     }
 
-    /// Get the total and the available disk space of the first local disk
+    /// Set the details of the filesystem which contains the current working directory, see [`load_filesystem_info`]
+    pub fn set_filesystem(&mut self, filesystem: Option<FilesystemInfo>) {
+        (self.total_space, self.used_space) = Self::get_disk_space(filesystem.as_ref());
+        self.filesystem = filesystem;
+    }
+
+    /// Get the total and the used space of the given filesystem
     /// # Returns
-    /// - A tuple which contains two [u64] values. First the total disk space, second the available disk space
-    /// - If no local disk was found, this function returns a tuple of zero [u64] values
-    fn get_local_disk_space(disks: &Disks) -> (u64, u64) {
-        disks
-            .iter()
-            .find(|disk| !disk.is_removable())
-            .map_or((0_u64, 0_u64), |disk| {
-                (disk.total_space(), disk.available_space())
-            })
+    /// - A tuple which contains two [u64] values. First the total disk space, second the used disk space
+    /// - If no filesystem is available, this function returns a tuple of zero [u64] values
+    fn get_disk_space(filesystem: Option<&FilesystemInfo>) -> (u64, u64) {
+        filesystem.map_or((0_u64, 0_u64), |filesystem| {
+            (
+                filesystem.total_space,
+                filesystem
+                    .total_space
+                    .saturating_sub(filesystem.available_space),
+            )
+        })
+    }
+}
+
+/// Get the details of the filesystem which contains the given path.<br>
+/// The list of disks is read again, so that drives mounted after the start are found.
+/// Reading the details may block on a slow or hung network mount, so this must not be called on the UI thread.
+pub fn load_filesystem_info(path: &Path) -> Option<FilesystemInfo> {
+    // resolve symbolic links, otherwise the path may point to another filesystem
+    let path = path.canonicalize().unwrap_or_else(|_| path.to_path_buf());
+
+    let disks = Disks::new_with_refreshed_list();
+    let index = find_mount_point(disks.iter().map(|disk| disk.mount_point()), &path)?;
+    let disk = &disks.list()[index];
+
+    Some(FilesystemInfo {
+        mount_point: disk.mount_point().to_path_buf(),
+        fs_type: disk.file_system().to_string_lossy().to_string(),
+        total_space: disk.total_space(),
+        available_space: disk.available_space(),
+        inodes: inode_usage(disk.mount_point()),
+    })
+}
+
+/// Finds the mount point which contains the given path, i.e. the longest mount point that is a prefix of the path
+/// # Returns
+/// The index of the mount point, if any
+fn find_mount_point<'a>(
+    mount_points: impl Iterator<Item = &'a Path>,
+    path: &Path,
+) -> Option<usize> {
+    mount_points
+        .enumerate()
+        .filter(|(_, mount_point)| path.starts_with(mount_point))
+        .max_by_key(|(_, mount_point)| mount_point.components().count())
+        .map(|(index, _)| index)
+}
+
+#[cfg(unix)]
+fn inode_usage(mount_point: &Path) -> Option<InodeUsage> {
+    let stat = nix::sys::statvfs::statvfs(mount_point).ok()?;
+    // some filesystems (e.g. btrfs) have no fixed number of inodes and report zero
+    if stat.files() == 0 {
+        return None;
+    }
+    Some(InodeUsage {
+        total: stat.files() as u64,
+        free: stat.files_free() as u64,
+    })
+}
+
+#[cfg(not(unix))]
+fn inode_usage(_mount_point: &Path) -> Option<InodeUsage> {
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_find_mount_point_prefers_longest_match() {
+        let mount_points = [
+            Path::new("/"),
+            Path::new("/home"),
+            Path::new("/home/user/data"),
+            Path::new("/home/user/database"),
+        ];
+
+        let find = |path: &str| find_mount_point(mount_points.iter().copied(), Path::new(path));
+
+        assert_eq!(find("/etc"), Some(0));
+        assert_eq!(find("/home/user"), Some(1));
+        assert_eq!(find("/home/user/data/projects"), Some(2));
+        assert_eq!(find("/home/user/databases"), Some(1));
+        assert_eq!(find("/home"), Some(1));
+    }
+
+    #[test]
+    fn test_find_mount_point_without_match() {
+        let mount_points = [Path::new("C:\\"), Path::new("/mnt")];
+        assert_eq!(
+            find_mount_point(mount_points.iter().copied(), Path::new("/home")),
+            None
+        );
     }
 }
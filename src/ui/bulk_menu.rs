@@ -0,0 +1,94 @@
+use ratatui::{prelude::*, widgets::*};
+
+use crate::{
+    file_handling::bulk::BulkOperation,
+    ui::{HIGHLIGHT_SYMBOL, ThemeColor, centered_rect_fixed_height},
+};
+
+/// Popup on the Results page, that lists the operations for the selected entries
+#[derive(Debug, Default)]
+pub struct BulkMenu {
+    /// Number of entries the chosen operation is applied to, shown in the title
+    entry_count: usize,
+    list_state: ListState,
+    is_visible: bool,
+}
+
+impl BulkMenu {
+    /// Shows the menu for the given number of entries, the first operation is selected
+    pub fn open(&mut self, entry_count: usize) {
+        self.entry_count = entry_count;
+        self.list_state.select(Some(0));
+        self.is_visible = true;
+    }
+
+    pub fn close(&mut self) {
+        self.is_visible = false;
+    }
+
+    pub fn is_visible(&self) -> bool {
+        self.is_visible
+    }
+
+    pub fn selected(&self) -> Option<BulkOperation> {
+        self.list_state
+            .selected()
+            .and_then(|index| BulkOperation::ALL.get(index).copied())
+    }
+
+    pub fn next(&mut self) {
+        let next = self
+            .list_state
+            .selected()
+            .map_or(0, |i| (i + 1) % BulkOperation::ALL.len());
+        self.list_state.select(Some(next));
+    }
+
+    pub fn previous(&mut self) {
+        let previous = self.list_state.selected().map_or(0, |i| {
+            i.checked_sub(1).unwrap_or(BulkOperation::ALL.len() - 1)
+        });
+        self.list_state.select(Some(previous));
+    }
+
+    pub fn render(&mut self, f: &mut Frame, area: Rect, theme_colors: &ThemeColor) {
+        let entries_str = if self.entry_count == 1 {
+            "entry"
+        } else {
+            "entries"
+        };
+        let block = Block::default()
+            .title_top(format!(" {} selected {entries_str} ", self.entry_count))
+            .title_bottom(Line::from(vec![
+                " <Enter>".fg(theme_colors.main_text_fg),
+                " Apply ".fg(theme_colors.main_fg),
+                "|".fg(theme_colors.main_fg),
+                " <Esc>".fg(theme_colors.main_text_fg),
+                " Close ".fg(theme_colors.main_fg),
+            ]))
+            .title_alignment(Alignment::Center)
+            .borders(Borders::ALL)
+            .border_type(BorderType::Rounded)
+            .border_style(Style::new().bold().fg(theme_colors.alt_fg))
+            .style(Style::new().bg(theme_colors.alt_bg));
+
+        let items: Vec<ListItem> = BulkOperation::ALL
+            .iter()
+            .map(|operation| {
+                ListItem::new(Span::styled(
+                    operation.to_string(),
+                    Style::new().fg(theme_colors.main_text_fg),
+                ))
+            })
+            .collect();
+
+        let list = List::new(items)
+            .block(block)
+            .highlight_style(Style::new().fg(theme_colors.selected_color))
+            .highlight_symbol(HIGHLIGHT_SYMBOL);
+
+        let popup_area = centered_rect_fixed_height(40, BulkOperation::ALL.len() as u16 + 2, area);
+        f.render_widget(Clear, popup_area);
+        f.render_stateful_widget(list, popup_area, &mut self.list_state);
+    }
+}
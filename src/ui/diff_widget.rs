@@ -0,0 +1,633 @@
+use std::path::PathBuf;
+
+use anyhow::Result;
+use async_trait::async_trait;
+use ratatui::{prelude::*, widgets::*};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    app::{AppContext, AppState, actions::Action, config::AppConfig, key_bindings},
+    component::Component,
+    file_handling::diff::{DiffContent, DiffLine, DiffTag, FileDiff, FileSummary},
+    tui::Event,
+    ui::{Theme, ThemeColor, get_main_layout},
+    utils,
+};
+
+/// Width of the line number columns
+const LINE_NUMBER_WIDTH: usize = 5;
+
+#[derive(Default, Debug, Copy, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum DiffMode {
+    #[default]
+    Unified,
+    SideBySide,
+}
+
+impl std::fmt::Display for DiffMode {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            DiffMode::Unified => write!(f, "Unified"),
+            DiffMode::SideBySide => write!(f, "Side-by-Side"),
+        }
+    }
+}
+
+/// Represents a single row of the rendered diff
+enum DiffRow<'a> {
+    /// The header of a hunk
+    Header(String),
+    Unified(&'a DiffLine),
+    SideBySide(Option<&'a DiffLine>, Option<&'a DiffLine>),
+}
+
+/// The [`DiffWidget`] shows the differences between two files in a unified or a side-by-side view.<br>
+/// The first file is marked in the Explorer or on the Result page, selecting the second file opens the diff.
+#[derive(Debug)]
+pub struct DiffWidget {
+    app_context: AppContext,
+    /// The context of the previous active widget
+    previous_context: AppContext,
+    /// Action sender that can send actions to all other components
+    action_sender: Option<tokio::sync::mpsc::UnboundedSender<Action>>,
+    /// Associated Explorer operation sender, that can send actions to the [`crate::file_handling::Explorer`]
+    explorer_action_sender: Option<tokio::sync::mpsc::Sender<Action>>,
+    /// Flag to control the available draw area for the [`DiffWidget`]
+    /// If the [`crate::ui::info_widget::SystemOverview`] is not visible, than use the whole draw area
+    use_whole_draw_area: bool,
+    theme: Theme,
+    /// The first file of the next diff, waiting for the second one
+    marked: Option<PathBuf>,
+    diff: Option<FileDiff>,
+    mode: DiffMode,
+    /// Index of the first visible row
+    scroll: usize,
+    /// Number of visible rows, used for the PageUp and PageDown operations
+    page_height: usize,
+}
+
+impl Default for DiffWidget {
+    fn default() -> Self {
+        Self {
+            app_context: AppContext::NotActive,
+            previous_context: AppContext::Explorer,
+            action_sender: Default::default(),
+            explorer_action_sender: Default::default(),
+            use_whole_draw_area: Default::default(),
+            theme: Default::default(),
+            marked: Default::default(),
+            diff: Default::default(),
+            mode: Default::default(),
+            scroll: Default::default(),
+            page_height: Default::default(),
+        }
+    }
+}
+
+impl DiffWidget {
+    /// Helper function to send a [`Action`] to the [`crate::file_handling::Explorer`]
+    async fn send_explorer_action(&self, action: Action) -> Result<()> {
+        if let Some(sender) = &self.explorer_action_sender {
+            sender.send(action).await?;
+        }
+        Ok(())
+    }
+
+    /// Helper function to send a [`Action`] to all components
+    fn send_app_action(&self, action: Action) -> Result<()> {
+        if let Some(handler) = &self.action_sender {
+            handler.send(action)?
+        }
+        Ok(())
+    }
+
+    /// Marks the first file or compares the marked file with the given one
+    async fn select_file(&mut self, path: &PathBuf, caller_context: AppContext) -> Result<Action> {
+        let file_name = path
+            .file_name()
+            .map(|name| name.to_string_lossy().to_string())
+            .unwrap_or_default();
+
+        match self.marked.take() {
+            None => {
+                self.marked = Some(path.clone());
+                Ok(Action::UpdateAppState(AppState::Done(format!(
+                    "'{}' marked, select the second file and press Ctrl + E",
+                    file_name
+                ))))
+            }
+            Some(marked) if marked == *path => Ok(Action::UpdateAppState(AppState::Done(format!(
+                "'{}' unmarked",
+                file_name
+            )))),
+            Some(marked) => {
+                self.previous_context = caller_context;
+                self.send_explorer_action(Action::ComputeDiff(marked, path.clone()))
+                    .await?;
+                Ok(Action::UpdateAppState(AppState::Working(
+                    "Comparing files...".into(),
+                )))
+            }
+        }
+    }
+
+    fn rows(&self) -> Vec<DiffRow<'_>> {
+        let Some(FileDiff {
+            content: DiffContent::Text(hunks),
+            ..
+        }) = &self.diff
+        else {
+            return vec![];
+        };
+
+        let mut rows = vec![];
+        for hunk in hunks {
+            rows.push(DiffRow::Header(hunk.header()));
+            match self.mode {
+                DiffMode::Unified => rows.extend(hunk.lines.iter().map(DiffRow::Unified)),
+                DiffMode::SideBySide => rows.extend(
+                    hunk.side_by_side()
+                        .into_iter()
+                        .map(|(left, right)| DiffRow::SideBySide(left, right)),
+                ),
+            }
+        }
+        rows
+    }
+
+    /// Indices of the rows that start a hunk
+    fn hunk_offsets(&self) -> Vec<usize> {
+        self.rows()
+            .iter()
+            .enumerate()
+            .filter(|(_, row)| matches!(row, DiffRow::Header(_)))
+            .map(|(i, _)| i)
+            .collect()
+    }
+
+    /// Index of the hunk at the top of the view
+    fn current_hunk(&self) -> Option<usize> {
+        self.hunk_offsets()
+            .iter()
+            .rposition(|offset| *offset <= self.scroll)
+    }
+
+    fn max_scroll(&self) -> usize {
+        self.rows().len().saturating_sub(self.page_height.max(1))
+    }
+
+    fn scroll_by(&mut self, delta: isize) {
+        self.scroll = self
+            .scroll
+            .saturating_add_signed(delta)
+            .min(self.max_scroll());
+    }
+
+    fn jump_to_hunk(&mut self, forward: bool) -> Option<Action> {
+        let offsets = self.hunk_offsets();
+        let target = if forward {
+            offsets.iter().find(|offset| **offset > self.scroll)
+        } else {
+            offsets.iter().rev().find(|offset| **offset < self.scroll)
+        };
+
+        // the last hunks may already be visible, if the view can't scroll any further
+        match target.map(|offset| (*offset).min(self.max_scroll())) {
+            Some(offset) if offset != self.scroll => {
+                self.scroll = offset;
+                None
+            }
+            _ => Action::UpdateAppState(AppState::Done(if forward {
+                "Last hunk reached".to_string()
+            } else {
+                "First hunk reached".to_string()
+            }))
+            .into(),
+        }
+    }
+
+    fn switch_mode(&mut self) {
+        // keep the current hunk in view
+        let hunk = self.current_hunk();
+        self.mode = match self.mode {
+            DiffMode::Unified => DiffMode::SideBySide,
+            DiffMode::SideBySide => DiffMode::Unified,
+        };
+        self.scroll = hunk
+            .and_then(|hunk| self.hunk_offsets().get(hunk).copied())
+            .unwrap_or_default()
+            .min(self.max_scroll());
+    }
+
+    fn line_style(tag: DiffTag, colors: &ThemeColor) -> Style {
+        match tag {
+            DiffTag::Equal => Style::new().fg(colors.main_text_fg),
+            DiffTag::Delete => Style::new()
+                .fg(colors.main_text_fg)
+                .bg(colors.diff_delete_bg),
+            DiffTag::Insert => Style::new()
+                .fg(colors.main_text_fg)
+                .bg(colors.diff_insert_bg),
+        }
+    }
+
+    fn line_number(number: Option<usize>) -> String {
+        number.map_or(" ".repeat(LINE_NUMBER_WIDTH), |number| {
+            format!("{:>width$}", number, width = LINE_NUMBER_WIDTH)
+        })
+    }
+
+    fn unified_line(line: &DiffLine, colors: &ThemeColor) -> Line<'static> {
+        let sign = match line.tag {
+            DiffTag::Equal => ' ',
+            DiffTag::Delete => '-',
+            DiffTag::Insert => '+',
+        };
+        Line::from(vec![
+            Span::from(format!(
+                "{} {} │",
+                Self::line_number(line.old_line),
+                Self::line_number(line.new_line)
+            ))
+            .fg(colors.main_fg),
+            Span::from(format!("{}{}", sign, line.text.replace('\t', "    "))),
+        ])
+        .style(Self::line_style(line.tag, colors))
+    }
+
+    fn side_line(line: Option<&DiffLine>, is_left: bool, colors: &ThemeColor) -> Line<'static> {
+        match line {
+            Some(line) => {
+                let number = if is_left {
+                    line.old_line
+                } else {
+                    line.new_line
+                };
+                Line::from(vec![
+                    Span::from(format!("{} │", Self::line_number(number))).fg(colors.main_fg),
+                    Span::from(line.text.replace('\t', "    ")),
+                ])
+                .style(Self::line_style(line.tag, colors))
+            }
+            None => Line::from(format!("{} │", " ".repeat(LINE_NUMBER_WIDTH))).fg(colors.main_fg),
+        }
+    }
+
+    fn render_binary(
+        f: &mut ratatui::Frame<'_>,
+        area: Rect,
+        diff: &FileDiff,
+        left: &FileSummary,
+        right: &FileSummary,
+        colors: &ThemeColor,
+    ) {
+        let summary_line = |path: &PathBuf, summary: &FileSummary| {
+            Line::from(vec![
+                Span::from(format!("{}: ", utils::format_path_for_display(path)))
+                    .fg(colors.main_fg),
+                Span::from(format!(
+                    "{} - BLAKE3 {}",
+                    utils::convert_bytes_to_human_readable(summary.size),
+                    summary.hash
+                ))
+                .fg(colors.main_text_fg),
+            ])
+        };
+
+        let result = if diff.is_identical() {
+            "The files are identical"
+        } else {
+            "The files differ"
+        };
+
+        let text = vec![
+            Line::from(" "),
+            Line::from("Binary or very large files, only size and hash are compared")
+                .fg(colors.main_text_fg),
+            Line::from(" "),
+            summary_line(&diff.left, left),
+            summary_line(&diff.right, right),
+            Line::from(" "),
+            Line::from(result).fg(colors.alt_fg).bold(),
+        ];
+
+        f.render_widget(
+            Paragraph::new(text)
+                .alignment(Alignment::Center)
+                .wrap(Wrap { trim: true }),
+            area,
+        );
+    }
+
+    fn render_rows(&self, f: &mut ratatui::Frame<'_>, area: Rect, colors: &ThemeColor) {
+        let rows = self.rows();
+        let visible_rows = rows
+            .iter()
+            .skip(self.scroll)
+            .take(area.height as usize)
+            .collect::<Vec<_>>();
+
+        let header_line = |header: &String| Line::from(header.clone()).fg(colors.alt_fg).bold();
+
+        match self.mode {
+            DiffMode::Unified => {
+                let lines = visible_rows
+                    .iter()
+                    .map(|row| match row {
+                        DiffRow::Header(header) => header_line(header),
+                        DiffRow::Unified(line) => Self::unified_line(line, colors),
+                        DiffRow::SideBySide(_, _) => Line::default(),
+                    })
+                    .collect::<Vec<Line>>();
+                f.render_widget(Paragraph::new(lines), area);
+            }
+            DiffMode::SideBySide => {
+                let [left_area, right_area] =
+                    Layout::horizontal([Constraint::Fill(1), Constraint::Fill(1)])
+                        .spacing(1)
+                        .areas(area);
+
+                let (left_lines, right_lines): (Vec<Line>, Vec<Line>) = visible_rows
+                    .iter()
+                    .map(|row| match row {
+                        DiffRow::Header(header) => (header_line(header), header_line(header)),
+                        DiffRow::SideBySide(left, right) => (
+                            Self::side_line(*left, true, colors),
+                            Self::side_line(*right, false, colors),
+                        ),
+                        DiffRow::Unified(_) => (Line::default(), Line::default()),
+                    })
+                    .unzip();
+
+                f.render_widget(Paragraph::new(left_lines), left_area);
+                f.render_widget(Paragraph::new(right_lines), right_area);
+            }
+        }
+    }
+}
+
+#[async_trait(?Send)]
+impl Component for DiffWidget {
+    fn register_component_action_sender(
+        &mut self,
+        tx: tokio::sync::mpsc::UnboundedSender<Action>,
+    ) -> Result<()> {
+        self.action_sender = Some(tx);
+        Ok(())
+    }
+
+    fn register_explorer_action_sender(
+        &mut self,
+        tx: tokio::sync::mpsc::Sender<Action>,
+    ) -> Result<()> {
+        self.explorer_action_sender = Some(tx);
+        Ok(())
+    }
+
+    fn register_config_handler(&mut self, config: AppConfig) -> Result<()> {
+        self.theme = config.theme();
+        Ok(())
+    }
+
+    fn should_handle_events(&self) -> bool {
+        self.app_context == AppContext::Diff
+    }
+
+    fn should_render(&self) -> bool {
+        self.app_context == AppContext::Diff
+    }
+
+    async fn handle_events(&mut self, event: Option<crate::tui::Event>) -> Result<Option<Action>> {
+        if let Some(event) = event {
+            match event {
+                Event::Key(key_event) => {
+                    if self.should_handle_events() {
+                        let cmd_desc =
+                            key_bindings::get_command_description(&key_event, &self.app_context)
+                                .to_owned();
+                        self.send_app_action(Action::SetCommandDescription(cmd_desc))?;
+                        return self.handle_key_events(key_event).await;
+                    }
+                }
+                _ => {
+                    return Ok(None);
+                }
+            }
+        }
+
+        Ok(None)
+    }
+
+    async fn handle_key_events(
+        &mut self,
+        key: crossterm::event::KeyEvent,
+    ) -> Result<Option<Action>> {
+        match key.code {
+            crossterm::event::KeyCode::Up
+                if key.modifiers == crossterm::event::KeyModifiers::NONE =>
+            {
+                self.scroll_by(-1);
+            }
+            crossterm::event::KeyCode::Down
+                if key.modifiers == crossterm::event::KeyModifiers::NONE =>
+            {
+                self.scroll_by(1);
+            }
+            crossterm::event::KeyCode::PageUp
+                if key.modifiers == crossterm::event::KeyModifiers::NONE =>
+            {
+                self.scroll_by(-(self.page_height as isize));
+            }
+            crossterm::event::KeyCode::PageDown
+                if key.modifiers == crossterm::event::KeyModifiers::NONE =>
+            {
+                self.scroll_by(self.page_height as isize);
+            }
+            crossterm::event::KeyCode::Char('n')
+                if key.modifiers == crossterm::event::KeyModifiers::NONE =>
+            {
+                return Ok(self.jump_to_hunk(true));
+            }
+            crossterm::event::KeyCode::Char('p')
+                if key.modifiers == crossterm::event::KeyModifiers::NONE =>
+            {
+                return Ok(self.jump_to_hunk(false));
+            }
+            crossterm::event::KeyCode::Tab
+                if key.modifiers == crossterm::event::KeyModifiers::NONE =>
+            {
+                self.switch_mode();
+            }
+            crossterm::event::KeyCode::Char('o')
+                if key.modifiers == crossterm::event::KeyModifiers::CONTROL =>
+            {
+                return Ok(Action::HideOrShowSystemOverview.into());
+            }
+            crossterm::event::KeyCode::Char('t')
+                if key.modifiers == crossterm::event::KeyModifiers::CONTROL =>
+            {
+                self.theme = self.theme.toggle_theme();
+                return Ok(Action::ToggleTheme(self.theme).into());
+            }
+            crossterm::event::KeyCode::F(1)
+                if key.modifiers == crossterm::event::KeyModifiers::NONE =>
+            {
+                self.app_context = AppContext::NotActive;
+                return Ok(Action::ShowHelp(AppContext::Diff).into());
+            }
+            crossterm::event::KeyCode::F(2)
+                if key.modifiers == crossterm::event::KeyModifiers::NONE =>
+            {
+                self.app_context = AppContext::NotActive;
+                return Ok(Action::ShowAbout(AppContext::Diff).into());
+            }
+            crossterm::event::KeyCode::F(3)
+                if key.modifiers == crossterm::event::KeyModifiers::NONE =>
+            {
+                self.app_context = AppContext::NotActive;
+                return Ok(Action::ShowSettings(AppContext::Diff).into());
+            }
+            crossterm::event::KeyCode::Esc => {
+                self.app_context = AppContext::NotActive;
+                self.diff = None;
+                return Ok(Action::SwitchAppContext(self.previous_context).into());
+            }
+            _ => {}
+        }
+
+        Ok(None)
+    }
+
+    async fn update(&mut self, action: &Action) -> Result<Option<Action>> {
+        match action {
+            Action::SwitchAppContext(context) => {
+                self.app_context = *context;
+            }
+            Action::SelectDiffFile(path, caller_context) => {
+                return Ok(self.select_file(path, *caller_context).await?.into());
+            }
+            Action::DiffDone(diff) => {
+                self.diff = Some(diff.clone());
+                self.scroll = 0;
+
+                let msg = if diff.is_identical() {
+                    "The files are identical"
+                } else {
+                    "Done"
+                };
+                self.send_app_action(Action::UpdateAppState(AppState::Done(msg.into())))?;
+                return Ok(Action::SwitchAppContext(AppContext::Diff).into());
+            }
+            Action::DiffFailure(msg) => {
+                return Ok(Action::UpdateAppState(AppState::Failure(msg.clone())).into());
+            }
+            Action::ToggleTheme(theme) => {
+                self.theme = *theme;
+            }
+            Action::HideOrShowSystemOverview => {
+                self.use_whole_draw_area = !self.use_whole_draw_area;
+            }
+            _ => {}
+        }
+        Ok(None)
+    }
+
+    fn render(&mut self, f: &mut ratatui::Frame<'_>, area: Rect) -> Result<()> {
+        if !self.should_render() {
+            return Ok(());
+        }
+        let Some(diff) = &self.diff else {
+            return Ok(());
+        };
+
+        // Control the draw area dependent if the InfoWidget is showing or not
+        let draw_area = if self.use_whole_draw_area {
+            let overview_area = get_main_layout(area).overview_area;
+            overview_area.union(get_main_layout(area).main_area)
+        } else {
+            get_main_layout(area).main_area
+        };
+
+        let [top_spacer_area, draw_area] =
+            Layout::vertical([Constraint::Length(1), Constraint::Fill(1)]).areas(draw_area);
+
+        let theme_colors = self.theme.theme_colors();
+
+        let main_block_title = format!(
+            " Diff: [{}] ↔ [{}] ",
+            utils::format_path_for_display(&diff.left),
+            utils::format_path_for_display(&diff.right)
+        );
+
+        let hunk_count = self.hunk_offsets().len();
+        let (deleted, inserted) = diff.stats();
+        let inner_block_title = match &diff.content {
+            DiffContent::Text(_) if hunk_count == 0 => " The files are identical ".to_string(),
+            DiffContent::Text(_) => format!(
+                " Mode: {} - Hunk {}/{} - {} deleted, {} inserted lines ",
+                self.mode,
+                self.current_hunk().map_or(0, |hunk| hunk + 1),
+                hunk_count,
+                deleted,
+                inserted
+            ),
+            DiffContent::Binary(_, _) => " Binary comparison ".to_string(),
+        };
+
+        let help_msg = vec![
+            " <Tab>".fg(theme_colors.main_text_fg),
+            " Switch mode ".fg(theme_colors.main_fg),
+            " <n|p>".fg(theme_colors.main_text_fg),
+            " Next/Previous hunk ".fg(theme_colors.main_fg),
+            " <Esc>".fg(theme_colors.main_text_fg),
+            " Back ".fg(theme_colors.main_fg),
+        ];
+
+        let first_block = Block::default()
+            .title_top(
+                Line::from(main_block_title)
+                    .style(Style::new().fg(theme_colors.alt_fg))
+                    .left_aligned(),
+            )
+            .borders(Borders::TOP | Borders::BOTTOM)
+            .border_type(BorderType::QuadrantInside)
+            .border_style(Style::new().fg(theme_colors.alt_bg))
+            .style(Style::new().bg(theme_colors.alt_bg));
+
+        let second_block = Block::default()
+            .title_top(Line::from(inner_block_title).style(Style::new().fg(theme_colors.alt_fg)))
+            .title_bottom(Line::from(help_msg))
+            .title_alignment(Alignment::Center)
+            .borders(Borders::ALL)
+            .border_type(BorderType::Rounded)
+            .border_style(Style::new().fg(theme_colors.main_fg))
+            .padding(Padding::horizontal(1))
+            .style(Style::new().bg(theme_colors.alt_bg));
+
+        let [second_block_area] = Layout::vertical([Constraint::Fill(1)])
+            .margin(1)
+            .areas(first_block.inner(draw_area));
+
+        let content_area = second_block.inner(second_block_area);
+        self.page_height = content_area.height as usize;
+        // the visible area may have grown, e.g. after hiding the system overview
+        self.scroll = self.scroll.min(self.max_scroll());
+
+        f.render_widget(Line::from(" ").bg(theme_colors.alt_bg), top_spacer_area);
+        f.render_widget(first_block, draw_area);
+        f.render_widget(second_block, second_block_area);
+
+        match &self.diff {
+            Some(
+                diff @ FileDiff {
+                    content: DiffContent::Binary(left, right),
+                    ..
+                },
+            ) => Self::render_binary(f, content_area, diff, left, right, &theme_colors),
+            Some(_) => self.render_rows(f, content_area, &theme_colors),
+            None => {}
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,775 @@
+use std::{collections::HashSet, path::PathBuf};
+
+use anyhow::Result;
+use async_trait::async_trait;
+use ratatui::{prelude::*, widgets::*};
+
+use crate::{
+    app::{AppContext, AppState, actions::Action, config::AppConfig, key_bindings},
+    component::Component,
+    file_handling::{
+        SEPARATOR,
+        dir_compare::{
+            CompareEntry, CompareFilter, DirComparison, EntryInfo, SyncDirection, SyncOperation,
+            plan_sync,
+        },
+    },
+    models::{Scrollable, StatefulTable},
+    tui::Event,
+    ui::{HIGHLIGHT_SYMBOL, Theme, centered_rect_fixed_height, get_main_layout},
+    utils,
+};
+
+/// Maximum number of operations listed in the dry-run preview
+const MAX_PREVIEW_LINES: usize = 10;
+
+/// The background job of the [`DirCompareWidget`], that is currently running
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+enum RunningJob {
+    Compare,
+    Sync,
+}
+
+/// Represents a planned synchronization, which is shown as dry run before it is executed
+#[derive(Debug)]
+struct PendingSync {
+    direction: SyncDirection,
+    operations: Vec<SyncOperation>,
+}
+
+/// The [`DirCompareWidget`] lists the differences between two directory trees.<br>
+/// The user can filter the differences, select some of them and copy them in one direction.
+#[derive(Debug)]
+pub struct DirCompareWidget {
+    app_context: AppContext,
+    /// The context of the previous active widget
+    previous_context: AppContext,
+    /// Action sender that can send actions to all other components
+    action_sender: Option<tokio::sync::mpsc::UnboundedSender<Action>>,
+    /// Associated Explorer operation sender, that can send actions to the [`crate::file_handling::Explorer`]
+    explorer_action_sender: Option<tokio::sync::mpsc::Sender<Action>>,
+    /// Flag to control the available draw area for the [`DirCompareWidget`]
+    /// If the [`crate::ui::info_widget::SystemOverview`] is not visible, than use the whole draw area
+    use_whole_draw_area: bool,
+    theme: Theme,
+    follow_sym_links: bool,
+    /// The first directory of the next comparison, waiting for the second one
+    marked: Option<PathBuf>,
+    comparison: DirComparison,
+    filter: CompareFilter,
+    /// The entries of the comparison matching the current filter
+    entries: StatefulTable<CompareEntry>,
+    /// Relative paths of the entries selected for synchronization
+    selected_paths: HashSet<PathBuf>,
+    /// Index of the first visible entry, only the visible entries are drawn
+    start_index: usize,
+    /// Page height used to control the PageUp and PageDown operations
+    page_height: u16,
+    running_job: Option<RunningJob>,
+    /// The result of the last synchronization, shown after the directories are compared again
+    sync_summary: Option<AppState>,
+    /// The synchronization the user wants to run, shows the dry-run preview
+    pending_sync: Option<PendingSync>,
+}
+
+impl Default for DirCompareWidget {
+    fn default() -> Self {
+        Self {
+            app_context: AppContext::NotActive,
+            previous_context: AppContext::Explorer,
+            action_sender: Default::default(),
+            explorer_action_sender: Default::default(),
+            use_whole_draw_area: Default::default(),
+            theme: Default::default(),
+            follow_sym_links: Default::default(),
+            marked: Default::default(),
+            comparison: Default::default(),
+            filter: Default::default(),
+            entries: StatefulTable::new(),
+            selected_paths: Default::default(),
+            start_index: Default::default(),
+            page_height: Default::default(),
+            running_job: Default::default(),
+            sync_summary: Default::default(),
+            pending_sync: Default::default(),
+        }
+    }
+}
+
+impl DirCompareWidget {
+    /// Helper function to send a [`Action`] to the [`crate::file_handling::Explorer`]
+    async fn send_explorer_action(&self, action: Action) -> Result<()> {
+        if let Some(sender) = &self.explorer_action_sender {
+            sender.send(action).await?;
+        }
+        Ok(())
+    }
+
+    /// Helper function to send a [`Action`] to all components
+    fn send_app_action(&self, action: Action) -> Result<()> {
+        if let Some(handler) = &self.action_sender {
+            handler.send(action)?
+        }
+        Ok(())
+    }
+
+    /// Marks the first directory or compares the marked directory with the given one
+    async fn select_dir(
+        &mut self,
+        path: &PathBuf,
+        caller_context: AppContext,
+    ) -> Result<Option<Action>> {
+        let dir_name = path
+            .file_name()
+            .map(|name| name.to_string_lossy().to_string())
+            .unwrap_or_else(|| utils::format_path_for_display(path));
+
+        match self.marked.take() {
+            None => {
+                self.marked = Some(path.clone());
+                Ok(Action::UpdateAppState(AppState::Done(format!(
+                    "'{}' marked, select the second directory and press Ctrl + E",
+                    dir_name
+                )))
+                .into())
+            }
+            Some(marked) if marked == *path => Ok(Action::UpdateAppState(AppState::Done(format!(
+                "'{}' unmarked",
+                dir_name
+            )))
+            .into()),
+            Some(marked) => {
+                self.previous_context = caller_context;
+                self.comparison = DirComparison {
+                    left: marked,
+                    right: path.clone(),
+                    entries: vec![],
+                };
+                self.filter = CompareFilter::default();
+                self.selected_paths.clear();
+                self.apply_filter();
+                self.compare().await?;
+                Ok(Action::SwitchAppContext(AppContext::DirCompare).into())
+            }
+        }
+    }
+
+    /// Compares the current directories again
+    async fn compare(&mut self) -> Result<()> {
+        self.running_job = Some(RunningJob::Compare);
+        self.send_app_action(Action::UpdateAppState(AppState::Working(
+            "Comparing directories...".into(),
+        )))?;
+        self.send_explorer_action(Action::CompareDirs(
+            self.comparison.left.clone(),
+            self.comparison.right.clone(),
+            self.follow_sym_links,
+        ))
+        .await
+    }
+
+    /// Shows the entries of the comparison, which match the current filter
+    fn apply_filter(&mut self) {
+        let selected_path = self.entries.current_item().map(|entry| entry.path.clone());
+        let entries: Vec<CompareEntry> = self
+            .comparison
+            .entries
+            .iter()
+            .filter(|entry| self.filter.matches(entry.status))
+            .cloned()
+            .collect();
+
+        let index = selected_path
+            .and_then(|path| entries.iter().position(|entry| entry.path == path))
+            .unwrap_or(0);
+
+        self.entries = StatefulTable::with_items(entries);
+        self.entries.scroll_down_by(index);
+        self.start_index = 0;
+    }
+
+    fn toggle_selection(&mut self) {
+        if let Some(entry) = self.entries.current_item() {
+            let path = entry.path.clone();
+            if !self.selected_paths.remove(&path) {
+                self.selected_paths.insert(path);
+            }
+            self.entries.scroll_down_by(1);
+        }
+    }
+
+    /// Selects all visible entries, or unselects them if all of them are already selected
+    fn toggle_all(&mut self) {
+        let all_selected = self
+            .entries
+            .items
+            .iter()
+            .all(|entry| self.selected_paths.contains(&entry.path));
+
+        for entry in &self.entries.items {
+            if all_selected {
+                self.selected_paths.remove(&entry.path);
+            } else {
+                self.selected_paths.insert(entry.path.clone());
+            }
+        }
+    }
+
+    /// Plans the synchronization of the selected entries or of the current entry, if nothing is selected
+    fn preview_sync(&mut self, direction: SyncDirection) -> Option<Action> {
+        let entries: Vec<&CompareEntry> = if self.selected_paths.is_empty() {
+            self.entries.current_item().into_iter().collect()
+        } else {
+            self.comparison
+                .entries
+                .iter()
+                .filter(|entry| self.selected_paths.contains(&entry.path))
+                .collect()
+        };
+
+        let operations = plan_sync(&self.comparison, &entries, direction);
+        if operations.is_empty() {
+            return Action::UpdateAppState(AppState::Failure(format!(
+                "Nothing to copy {}",
+                direction
+            )))
+            .into();
+        }
+
+        self.pending_sync = Some(PendingSync {
+            direction,
+            operations,
+        });
+        None
+    }
+
+    /// Handles the key events of the dry-run preview
+    async fn handle_sync_confirmation(
+        &mut self,
+        key: crossterm::event::KeyEvent,
+    ) -> Result<Option<Action>> {
+        match key.code {
+            crossterm::event::KeyCode::Char('y') | crossterm::event::KeyCode::Char('Y') => {
+                if let Some(pending_sync) = self.pending_sync.take() {
+                    self.running_job = Some(RunningJob::Sync);
+                    self.send_app_action(Action::UpdateAppState(AppState::Working(
+                        "Synchronizing...".into(),
+                    )))?;
+                    self.send_explorer_action(Action::SyncDirs(pending_sync.operations))
+                        .await?;
+                }
+            }
+            crossterm::event::KeyCode::Char('n')
+            | crossterm::event::KeyCode::Char('N')
+            | crossterm::event::KeyCode::Esc => {
+                self.pending_sync = None;
+            }
+            _ => {}
+        }
+        Ok(None)
+    }
+
+    fn side_info(info: Option<&EntryInfo>) -> String {
+        match info {
+            Some(info) if info.is_dir => "<DIR>".into(),
+            Some(info) => utils::convert_bytes_to_human_readable(info.size),
+            None => " - ".into(),
+        }
+    }
+
+    fn render_sync_preview(&self, f: &mut ratatui::Frame<'_>, area: Rect, pending: &PendingSync) {
+        let overwrite_count = pending
+            .operations
+            .iter()
+            .filter(|operation| operation.overwrite)
+            .count();
+
+        let newer_count = pending
+            .operations
+            .iter()
+            .filter(|operation| operation.overwrites_newer)
+            .count();
+
+        let mut lines = vec![
+            Line::from(format!(
+                "{} entries will be copied, {} existing entries will be overwritten",
+                pending.operations.len(),
+                overwrite_count
+            ))
+            .centered(),
+        ];
+        if newer_count > 0 {
+            lines.push(
+                Line::from(format!(
+                    "⚠ {newer_count} of them are newer than the copied entries and marked with !"
+                ))
+                .fg(Color::LightRed)
+                .centered(),
+            );
+        }
+        lines.push(Line::from(" "));
+
+        lines.extend(
+            pending
+                .operations
+                .iter()
+                .take(MAX_PREVIEW_LINES)
+                .map(|operation| {
+                    let (sign, color) = if operation.overwrites_newer {
+                        ("!", Color::LightRed)
+                    } else if operation.overwrite {
+                        ("~", Color::Yellow)
+                    } else {
+                        ("+", Color::LightGreen)
+                    };
+                    let suffix = if operation.is_dir { SEPARATOR } else { "" };
+                    Line::from(vec![
+                        Span::from(format!("{} ", sign)).fg(color),
+                        Span::from(format!("{}{}", operation.path.display(), suffix)),
+                    ])
+                }),
+        );
+
+        if pending.operations.len() > MAX_PREVIEW_LINES {
+            lines.push(Line::from(format!(
+                "... and {} more",
+                pending.operations.len() - MAX_PREVIEW_LINES
+            )));
+        }
+
+        let block = Block::default()
+            .title_top(format!(" Dry run: copy {} ", pending.direction))
+            .title_bottom(Line::from(vec![
+                " <y>".fg(Color::Yellow),
+                " Copy ".into(),
+                " <n|Esc>".fg(Color::Yellow),
+                " Cancel ".into(),
+            ]))
+            .title_alignment(Alignment::Center)
+            .borders(Borders::ALL)
+            .border_type(BorderType::Rounded)
+            .border_style(Style::new().bold().fg(Color::Yellow))
+            .padding(Padding::horizontal(1))
+            .style(Style::new().bg(Color::default()));
+
+        let centered_area = centered_rect_fixed_height(70, lines.len() as u16 + 2, area);
+
+        f.render_widget(Clear, centered_area);
+        f.render_widget(
+            Paragraph::new(lines)
+                .style(Style::new().fg(Color::White))
+                .block(block),
+            centered_area,
+        );
+    }
+}
+
+#[async_trait(?Send)]
+impl Component for DirCompareWidget {
+    fn init_area(&mut self, area: Rect) -> Result<()> {
+        self.page_height = area.height;
+        Ok(())
+    }
+
+    fn register_component_action_sender(
+        &mut self,
+        tx: tokio::sync::mpsc::UnboundedSender<Action>,
+    ) -> Result<()> {
+        self.action_sender = Some(tx);
+        Ok(())
+    }
+
+    fn register_explorer_action_sender(
+        &mut self,
+        tx: tokio::sync::mpsc::Sender<Action>,
+    ) -> Result<()> {
+        self.explorer_action_sender = Some(tx);
+        Ok(())
+    }
+
+    fn register_config_handler(&mut self, config: AppConfig) -> Result<()> {
+        self.theme = config.theme();
+        self.follow_sym_links = config.follow_sym_links();
+        Ok(())
+    }
+
+    fn should_handle_events(&self) -> bool {
+        self.app_context == AppContext::DirCompare
+    }
+
+    fn should_render(&self) -> bool {
+        self.app_context == AppContext::DirCompare
+    }
+
+    async fn handle_events(&mut self, event: Option<crate::tui::Event>) -> Result<Option<Action>> {
+        if let Some(event) = event {
+            match event {
+                Event::Key(key_event) => {
+                    if self.should_handle_events() {
+                        let cmd_desc =
+                            key_bindings::get_command_description(&key_event, &self.app_context)
+                                .to_owned();
+                        self.send_app_action(Action::SetCommandDescription(cmd_desc))?;
+                        return self.handle_key_events(key_event).await;
+                    }
+                }
+                _ => {
+                    return Ok(None);
+                }
+            }
+        }
+
+        Ok(None)
+    }
+
+    async fn handle_key_events(
+        &mut self,
+        key: crossterm::event::KeyEvent,
+    ) -> Result<Option<Action>> {
+        if self.pending_sync.is_some() {
+            return self.handle_sync_confirmation(key).await;
+        }
+
+        if key.code == crossterm::event::KeyCode::Esc {
+            match self.running_job.take() {
+                // a cancelled synchronization may have copied some entries, so compare again
+                Some(RunningJob::Sync) => {
+                    self.send_explorer_action(Action::CancelDirCompare).await?;
+                    self.sync_summary = Some(AppState::Done("Synchronization cancelled".into()));
+                    self.compare().await?;
+                    return Ok(None);
+                }
+                Some(RunningJob::Compare) => {
+                    self.send_explorer_action(Action::CancelDirCompare).await?;
+                    self.send_app_action(Action::UpdateAppState(AppState::Done(
+                        "Cancelled".into(),
+                    )))?;
+                }
+                None => {}
+            }
+            self.app_context = AppContext::NotActive;
+            return Ok(Action::SwitchAppContext(self.previous_context).into());
+        }
+
+        // only leaving the view is possible, while a job is running
+        if self.running_job.is_some() {
+            return Ok(None);
+        }
+
+        match key.code {
+            crossterm::event::KeyCode::Up
+                if key.modifiers == crossterm::event::KeyModifiers::NONE =>
+            {
+                self.entries.scroll_up_by(1);
+            }
+            crossterm::event::KeyCode::Down
+                if key.modifiers == crossterm::event::KeyModifiers::NONE =>
+            {
+                self.entries.scroll_down_by(1);
+            }
+            crossterm::event::KeyCode::PageUp
+                if key.modifiers == crossterm::event::KeyModifiers::NONE =>
+            {
+                self.entries.scroll_up_by(self.page_height as usize);
+            }
+            crossterm::event::KeyCode::PageDown
+                if key.modifiers == crossterm::event::KeyModifiers::NONE =>
+            {
+                self.entries.scroll_down_by(self.page_height as usize);
+            }
+            crossterm::event::KeyCode::Char(' ')
+                if key.modifiers == crossterm::event::KeyModifiers::NONE =>
+            {
+                self.toggle_selection();
+            }
+            crossterm::event::KeyCode::Char('a')
+                if key.modifiers == crossterm::event::KeyModifiers::NONE =>
+            {
+                self.toggle_all();
+            }
+            crossterm::event::KeyCode::Tab
+                if key.modifiers == crossterm::event::KeyModifiers::NONE =>
+            {
+                self.filter = self.filter.next();
+                self.apply_filter();
+            }
+            crossterm::event::KeyCode::Right
+                if key.modifiers == crossterm::event::KeyModifiers::NONE =>
+            {
+                return Ok(self.preview_sync(SyncDirection::LeftToRight));
+            }
+            crossterm::event::KeyCode::Left
+                if key.modifiers == crossterm::event::KeyModifiers::NONE =>
+            {
+                return Ok(self.preview_sync(SyncDirection::RightToLeft));
+            }
+            crossterm::event::KeyCode::F(5) => {
+                self.compare().await?;
+            }
+            crossterm::event::KeyCode::Char('o')
+                if key.modifiers == crossterm::event::KeyModifiers::CONTROL =>
+            {
+                return Ok(Action::HideOrShowSystemOverview.into());
+            }
+            crossterm::event::KeyCode::Char('t')
+                if key.modifiers == crossterm::event::KeyModifiers::CONTROL =>
+            {
+                self.theme = self.theme.toggle_theme();
+                return Ok(Action::ToggleTheme(self.theme).into());
+            }
+            crossterm::event::KeyCode::F(1)
+                if key.modifiers == crossterm::event::KeyModifiers::NONE =>
+            {
+                self.app_context = AppContext::NotActive;
+                return Ok(Action::ShowHelp(AppContext::DirCompare).into());
+            }
+            crossterm::event::KeyCode::F(2)
+                if key.modifiers == crossterm::event::KeyModifiers::NONE =>
+            {
+                self.app_context = AppContext::NotActive;
+                return Ok(Action::ShowAbout(AppContext::DirCompare).into());
+            }
+            crossterm::event::KeyCode::F(3)
+                if key.modifiers == crossterm::event::KeyModifiers::NONE =>
+            {
+                self.app_context = AppContext::NotActive;
+                return Ok(Action::ShowSettings(AppContext::DirCompare).into());
+            }
+            _ => {}
+        }
+
+        Ok(None)
+    }
+
+    async fn update(&mut self, action: &Action) -> Result<Option<Action>> {
+        match action {
+            Action::SwitchAppContext(context) => {
+                self.app_context = *context;
+            }
+            Action::SelectCompareDir(path, caller_context) => {
+                return self.select_dir(path, *caller_context).await;
+            }
+            Action::CompareDirsDone(comparison) if self.running_job.is_some() => {
+                self.running_job = None;
+                let Some(comparison) = comparison else {
+                    return Ok(Action::UpdateAppState(AppState::Failure(
+                        "Unable to compare the directories".into(),
+                    ))
+                    .into());
+                };
+
+                self.comparison = comparison.clone();
+                // forget the selection of entries, which are no longer different
+                self.selected_paths
+                    .retain(|path| comparison.entries.iter().any(|entry| entry.path == *path));
+                self.apply_filter();
+
+                let state = self.sync_summary.take().unwrap_or_else(|| {
+                    if comparison.entries.is_empty() {
+                        AppState::Done("The directories are identical".into())
+                    } else {
+                        AppState::Done(format!("{} differences", comparison.entries.len()))
+                    }
+                });
+                return Ok(Action::UpdateAppState(state).into());
+            }
+            Action::SyncDirsDone(copied, errors) if self.running_job.is_some() => {
+                self.selected_paths.clear();
+                self.sync_summary = Some(match errors.first() {
+                    None => AppState::Done(format!("{} entries copied", copied)),
+                    Some(first_error) => AppState::Failure(format!(
+                        "{} entries copied, unable to copy {} entries e.g. {}",
+                        copied,
+                        errors.len(),
+                        first_error
+                    )),
+                });
+                // show the remaining differences
+                self.compare().await?;
+            }
+            Action::Resize(_, _) => {
+                self.start_index = 0;
+            }
+            Action::ToggleTheme(theme) => {
+                self.theme = *theme;
+            }
+            Action::HideOrShowSystemOverview => {
+                self.use_whole_draw_area = !self.use_whole_draw_area;
+            }
+            Action::ApplyAppSettings(c) => {
+                self.follow_sym_links = c.follow_sym_links();
+            }
+            _ => {}
+        }
+        Ok(None)
+    }
+
+    fn render(&mut self, f: &mut ratatui::Frame<'_>, area: Rect) -> Result<()> {
+        if self.should_render() {
+            // Control the draw area dependent if the InfoWidget is showing or not
+            let draw_area = if self.use_whole_draw_area {
+                let overview_area = get_main_layout(area).overview_area;
+                overview_area.union(get_main_layout(area).main_area)
+            } else {
+                get_main_layout(area).main_area
+            };
+
+            let [top_spacer_area, draw_area] =
+                Layout::vertical([Constraint::Length(1), Constraint::Fill(1)]).areas(draw_area);
+
+            let theme_colors = self.theme.theme_colors();
+
+            let main_block_title = format!(
+                " Dir-Compare: [{}] ↔ [{}] ",
+                utils::format_path_for_display(&self.comparison.left),
+                utils::format_path_for_display(&self.comparison.right)
+            );
+
+            let inner_block_title = format!(
+                " Filter: {} - Differences: {} - Selected: {} ",
+                self.filter,
+                self.comparison.entries.len(),
+                self.selected_paths.len()
+            );
+
+            let selected_hint = format!(
+                " {}/{} ",
+                (self.entries.selected_item + 1).min(self.entries.items.len()),
+                self.entries.items.len()
+            );
+
+            let help_msg = vec![
+                " <Space|a>".fg(theme_colors.main_text_fg),
+                " Select ".fg(theme_colors.main_fg),
+                " <Tab>".fg(theme_colors.main_text_fg),
+                " Filter ".fg(theme_colors.main_fg),
+                " <→|←>".fg(theme_colors.main_text_fg),
+                " Copy to right/left ".fg(theme_colors.main_fg),
+                " <F5>".fg(theme_colors.main_text_fg),
+                " Refresh ".fg(theme_colors.main_fg),
+                " <Esc>".fg(theme_colors.main_text_fg),
+                " Back ".fg(theme_colors.main_fg),
+            ];
+
+            let first_block = Block::default()
+                .title_top(
+                    Line::from(main_block_title)
+                        .style(Style::new().fg(theme_colors.alt_fg))
+                        .left_aligned(),
+                )
+                .borders(Borders::TOP | Borders::BOTTOM)
+                .border_type(BorderType::QuadrantInside)
+                .border_style(Style::new().fg(theme_colors.alt_bg))
+                .style(Style::new().bg(theme_colors.alt_bg));
+
+            let second_block = Block::default()
+                .title_top(
+                    Line::from(inner_block_title).style(Style::new().fg(theme_colors.alt_fg)),
+                )
+                .title_top(Line::from(selected_hint).right_aligned())
+                .title_bottom(Line::from(help_msg))
+                .title_alignment(Alignment::Center)
+                .borders(Borders::ALL)
+                .border_type(BorderType::Rounded)
+                .border_style(Style::new().fg(theme_colors.main_fg))
+                .style(Style::new().bg(theme_colors.alt_bg));
+
+            let [second_block_area] = Layout::vertical([Constraint::Fill(1)])
+                .margin(1)
+                .areas(first_block.inner(draw_area));
+
+            let [table_area] = Layout::vertical([Constraint::Fill(1)])
+                .areas(second_block.inner(second_block_area));
+
+            // only the visible rows are drawn, header and padding are not available for rows
+            let visible_rows = table_area.height.saturating_sub(2).max(1) as usize;
+            self.page_height = visible_rows as u16;
+
+            let selected = self.entries.selected_item;
+            if selected < self.start_index {
+                self.start_index = selected;
+            } else if selected >= self.start_index + visible_rows {
+                self.start_index = selected + 1 - visible_rows;
+            }
+
+            let header_style = Style::default()
+                .fg(theme_colors.header_fg)
+                .bg(theme_colors.header_bg);
+
+            let header = ["", "Status", "Path", "Left", "Right"]
+                .into_iter()
+                .map(Cell::from)
+                .collect::<Row>()
+                .style(header_style)
+                .height(1);
+
+            let table_widths = [
+                Constraint::Length(2),
+                Constraint::Length(14),
+                Constraint::Fill(1),
+                Constraint::Length(12),
+                Constraint::Length(12),
+            ];
+
+            let rows = self
+                .entries
+                .items
+                .iter()
+                .skip(self.start_index)
+                .take(visible_rows)
+                .map(|entry| {
+                    let (path, path_color) = if entry.is_dir() {
+                        (
+                            format!("{}{}", entry.path.display(), SEPARATOR),
+                            theme_colors.dir_color,
+                        )
+                    } else {
+                        (entry.path.display().to_string(), theme_colors.file_color)
+                    };
+
+                    let mark = if self.selected_paths.contains(&entry.path) {
+                        "✔"
+                    } else {
+                        " "
+                    };
+
+                    Row::new(vec![
+                        Cell::from(mark).fg(theme_colors.selected_color),
+                        Cell::from(entry.status.to_string()).fg(theme_colors.main_fg),
+                        Cell::from(path).fg(path_color),
+                        Cell::from(Self::side_info(entry.left.as_ref())).fg(theme_colors.alt_fg),
+                        Cell::from(Self::side_info(entry.right.as_ref())).fg(theme_colors.alt_fg),
+                    ])
+                })
+                .collect::<Vec<Row>>();
+
+            let compare_table = Table::new(rows, table_widths)
+                .header(header)
+                .block(Block::new().padding(Padding {
+                    left: 0,
+                    right: 0,
+                    top: 1,
+                    bottom: 0,
+                }))
+                .row_highlight_style(Style::new().fg(theme_colors.selected_color))
+                .highlight_symbol(HIGHLIGHT_SYMBOL)
+                .bg(theme_colors.alt_bg)
+                .highlight_spacing(HighlightSpacing::Always);
+
+            let mut table_state = TableState::default();
+            if !self.entries.items.is_empty() {
+                table_state.select(Some(selected - self.start_index));
+            }
+
+            f.render_widget(Line::from(" ").bg(theme_colors.alt_bg), top_spacer_area);
+            f.render_widget(first_block, draw_area);
+            f.render_widget(second_block, second_block_area);
+            f.render_stateful_widget(compare_table, table_area, &mut table_state);
+
+            if let Some(pending_sync) = &self.pending_sync {
+                self.render_sync_preview(f, area, pending_sync);
+            }
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,623 @@
+use std::path::{Path, PathBuf};
+
+use anyhow::Result;
+use async_trait::async_trait;
+use ratatui::{prelude::*, widgets::*};
+
+use crate::{
+    app::{AppContext, AppState, actions::Action, config::AppConfig, key_bindings},
+    component::Component,
+    file_handling::{
+        SEPARATOR,
+        disk_usage::{DiskUsageCache, UsageEntry},
+    },
+    models::{Scrollable, StatefulTable},
+    tui::Event,
+    ui::{HIGHLIGHT_SYMBOL, Theme, centered_rect_fixed_height, get_main_layout},
+    utils,
+};
+
+/// Number of characters used to draw the percentage bar
+const USAGE_BAR_WIDTH: usize = 15;
+
+/// The [`DiskUsageWidget`] shows the recursive size of all entries of a directory, sorted by size.<br>
+/// The user can drill down into subdirectories, go back to the parent directory and delete entries.
+#[derive(Debug)]
+pub struct DiskUsageWidget {
+    app_context: AppContext,
+    /// Action sender that can send actions to all other components
+    action_sender: Option<tokio::sync::mpsc::UnboundedSender<Action>>,
+    /// Associated Explorer operation sender, that can send actions to the [`crate::file_handling::Explorer`]
+    explorer_action_sender: Option<tokio::sync::mpsc::Sender<Action>>,
+    /// Flag to control the available draw area for the [`DiskUsageWidget`]
+    /// If the [`crate::ui::info_widget::SystemOverview`] is not visible, than use the whole draw area
+    use_whole_draw_area: bool,
+    theme: Theme,
+    follow_sym_links: bool,
+    /// All directories analyzed so far, so returning to an already visited directory is instant
+    cache: DiskUsageCache,
+    /// The directory whose content is currently shown
+    current_dir: PathBuf,
+    /// Total size and item count of the current directory
+    current_size: u64,
+    current_item_count: u64,
+    entries: StatefulTable<UsageEntry>,
+    /// Index of the first visible entry, only the visible entries are drawn
+    start_index: usize,
+    /// Page height used to control the PageUp and PageDown operations
+    page_height: u16,
+    /// The directory which is currently analyzed and the entry to select when it is done, if any
+    pending_analysis: Option<(PathBuf, Option<PathBuf>)>,
+    /// The entry the user wants to delete, shows a confirmation dialog
+    pending_delete: Option<UsageEntry>,
+    /// Flag to control the receiving of the key events
+    /// If the widget is working, then only `Esc` is handled to leave the view
+    is_working: bool,
+}
+
+impl Default for DiskUsageWidget {
+    fn default() -> Self {
+        Self {
+            app_context: AppContext::NotActive,
+            action_sender: Default::default(),
+            explorer_action_sender: Default::default(),
+            use_whole_draw_area: Default::default(),
+            theme: Default::default(),
+            follow_sym_links: Default::default(),
+            cache: Default::default(),
+            current_dir: Default::default(),
+            current_size: Default::default(),
+            current_item_count: Default::default(),
+            entries: StatefulTable::new(),
+            start_index: Default::default(),
+            page_height: Default::default(),
+            pending_analysis: Default::default(),
+            pending_delete: Default::default(),
+            is_working: Default::default(),
+        }
+    }
+}
+
+impl DiskUsageWidget {
+    /// Helper function to send a [`Action`] to the [`crate::file_handling::Explorer`]
+    /// Set the `is_working` flag to true
+    async fn send_explorer_action(&mut self, action: Action) -> Result<()> {
+        if let Some(sender) = &self.explorer_action_sender {
+            self.is_working = true;
+            sender.send(action).await?;
+        }
+        Ok(())
+    }
+
+    /// Helper function to send a [`Action`] to all components
+    fn send_app_action(&self, action: Action) -> Result<()> {
+        if let Some(handler) = &self.action_sender {
+            handler.send(action)?
+        }
+        Ok(())
+    }
+
+    /// Shows the given directory from the cache or starts a new analysis, if it was not analyzed yet.
+    /// The entry with the given path will be selected, if any
+    async fn open_dir(&mut self, dir: PathBuf, select: Option<PathBuf>) -> Result<()> {
+        if self.cache.get(&dir).is_some() {
+            self.show_dir(dir, select.as_deref());
+            self.send_app_action(Action::UpdateAppState(AppState::done_empty()))?;
+        } else {
+            self.analyze(dir, select).await?;
+        }
+        Ok(())
+    }
+
+    /// Starts a new analysis of the given directory, independent of the cache
+    async fn analyze(&mut self, dir: PathBuf, select: Option<PathBuf>) -> Result<()> {
+        self.pending_analysis = Some((dir.clone(), select));
+        self.send_app_action(Action::UpdateAppState(AppState::Working(
+            "Analyzing disk usage...".into(),
+        )))?;
+        self.send_explorer_action(Action::AnalyzeDiskUsage(dir, self.follow_sym_links))
+            .await
+    }
+
+    /// Shows the cached content of the given directory
+    fn show_dir(&mut self, dir: PathBuf, select: Option<&Path>) {
+        let Some(dir_usage) = self.cache.get(&dir) else {
+            return;
+        };
+
+        self.current_size = dir_usage.size;
+        self.current_item_count = dir_usage.item_count;
+
+        let index = select
+            .and_then(|select| {
+                dir_usage
+                    .entries
+                    .iter()
+                    .position(|entry| entry.path == select)
+            })
+            .unwrap_or(0);
+
+        self.entries = StatefulTable::with_items(dir_usage.entries.clone());
+        self.entries.scroll_down_by(index);
+        self.start_index = 0;
+        self.current_dir = dir;
+    }
+
+    fn selected_entry(&self) -> Option<&UsageEntry> {
+        self.entries.current_item()
+    }
+
+    /// Handles the key events of the confirmation dialog to delete a entry
+    async fn handle_delete_confirmation(
+        &mut self,
+        key: crossterm::event::KeyEvent,
+    ) -> Result<Option<Action>> {
+        match key.code {
+            crossterm::event::KeyCode::Char('y') | crossterm::event::KeyCode::Char('Y') => {
+                if let Some(entry) = self.pending_delete.take() {
+                    self.send_app_action(Action::UpdateAppState(AppState::Working(
+                        "Deleting...".into(),
+                    )))?;
+                    self.send_explorer_action(Action::DeletePath(entry.path))
+                        .await?;
+                }
+            }
+            crossterm::event::KeyCode::Char('n')
+            | crossterm::event::KeyCode::Char('N')
+            | crossterm::event::KeyCode::Esc => {
+                self.pending_delete = None;
+            }
+            _ => {}
+        }
+        Ok(None)
+    }
+
+    fn usage_bar(ratio: f64) -> String {
+        let filled = (ratio * USAGE_BAR_WIDTH as f64).round() as usize;
+        format!(
+            "{}{} {:>5.1}%",
+            "█".repeat(filled),
+            "░".repeat(USAGE_BAR_WIDTH.saturating_sub(filled)),
+            ratio * 100.0
+        )
+    }
+
+    fn render_delete_confirmation(
+        &self,
+        f: &mut ratatui::Frame<'_>,
+        area: Rect,
+        entry: &UsageEntry,
+    ) {
+        let block = Block::default()
+            .title_top(" Delete permanently ")
+            .title_bottom(Line::from(vec![
+                " <y>".fg(Color::Yellow),
+                " Delete ".into(),
+                " <n|Esc>".fg(Color::Yellow),
+                " Cancel ".into(),
+            ]))
+            .title_alignment(Alignment::Center)
+            .borders(Borders::ALL)
+            .border_type(BorderType::Rounded)
+            .border_style(Style::new().bold().fg(Color::LightRed))
+            .style(Style::new().bg(Color::default()));
+
+        let centered_area = centered_rect_fixed_height(65, 5, area);
+
+        let [_, text_area, _] = Layout::vertical([
+            Constraint::Length(1),
+            Constraint::Length(1),
+            Constraint::Length(1),
+        ])
+        .areas(block.inner(centered_area));
+
+        let object_type = if entry.is_dir { "directory" } else { "file" };
+        let text = Paragraph::new(format!(
+            "Delete the {} '{}' ({})?",
+            object_type,
+            entry.name,
+            utils::convert_bytes_to_human_readable(entry.size)
+        ))
+        .style(Style::new().fg(Color::White))
+        .alignment(Alignment::Center);
+
+        f.render_widget(Clear, centered_area);
+        f.render_widget(block, centered_area);
+        f.render_widget(text, text_area);
+    }
+}
+
+#[async_trait(?Send)]
+impl Component for DiskUsageWidget {
+    fn init_area(&mut self, area: Rect) -> Result<()> {
+        self.page_height = area.height;
+        Ok(())
+    }
+
+    fn register_component_action_sender(
+        &mut self,
+        tx: tokio::sync::mpsc::UnboundedSender<Action>,
+    ) -> Result<()> {
+        self.action_sender = Some(tx);
+        Ok(())
+    }
+
+    fn register_explorer_action_sender(
+        &mut self,
+        tx: tokio::sync::mpsc::Sender<Action>,
+    ) -> Result<()> {
+        self.explorer_action_sender = Some(tx);
+        Ok(())
+    }
+
+    fn register_config_handler(&mut self, config: AppConfig) -> Result<()> {
+        self.theme = config.theme();
+        self.follow_sym_links = config.follow_sym_links();
+        Ok(())
+    }
+
+    fn should_handle_events(&self) -> bool {
+        self.app_context == AppContext::DiskUsage
+    }
+
+    fn should_render(&self) -> bool {
+        self.app_context == AppContext::DiskUsage
+    }
+
+    async fn handle_events(&mut self, event: Option<crate::tui::Event>) -> Result<Option<Action>> {
+        if let Some(event) = event {
+            match event {
+                Event::Key(key_event) => {
+                    if self.should_handle_events() {
+                        let cmd_desc =
+                            key_bindings::get_command_description(&key_event, &self.app_context)
+                                .to_owned();
+                        self.send_app_action(Action::SetCommandDescription(cmd_desc))?;
+                        return self.handle_key_events(key_event).await;
+                    }
+                }
+                _ => {
+                    return Ok(None);
+                }
+            }
+        }
+
+        Ok(None)
+    }
+
+    async fn handle_key_events(
+        &mut self,
+        key: crossterm::event::KeyEvent,
+    ) -> Result<Option<Action>> {
+        // Leave the view at any time, even if a analysis is running
+        if key.code == crossterm::event::KeyCode::Esc && self.pending_delete.is_none() {
+            if self.pending_analysis.take().is_some() {
+                self.send_explorer_action(Action::CancelDiskUsage).await?;
+                self.send_app_action(Action::UpdateAppState(AppState::Done("Cancelled".into())))?;
+            }
+            self.is_working = false;
+            self.app_context = AppContext::NotActive;
+            return Ok(Action::SwitchAppContext(AppContext::Explorer).into());
+        }
+
+        if self.is_working {
+            return Ok(None);
+        }
+
+        if self.pending_delete.is_some() {
+            return self.handle_delete_confirmation(key).await;
+        }
+
+        match key.code {
+            crossterm::event::KeyCode::Up
+                if key.modifiers == crossterm::event::KeyModifiers::NONE =>
+            {
+                self.entries.scroll_up_by(1);
+            }
+            crossterm::event::KeyCode::Down
+                if key.modifiers == crossterm::event::KeyModifiers::NONE =>
+            {
+                self.entries.scroll_down_by(1);
+            }
+            crossterm::event::KeyCode::PageUp
+                if key.modifiers == crossterm::event::KeyModifiers::NONE =>
+            {
+                self.entries.scroll_up_by(self.page_height as usize);
+            }
+            crossterm::event::KeyCode::PageDown
+                if key.modifiers == crossterm::event::KeyModifiers::NONE =>
+            {
+                self.entries.scroll_down_by(self.page_height as usize);
+            }
+            // Enter key -> Drill down into the selected directory
+            crossterm::event::KeyCode::Enter => {
+                if let Some(entry) = self.selected_entry().filter(|entry| entry.is_dir) {
+                    let dir = entry.path.clone();
+                    self.open_dir(dir, None).await?;
+                }
+            }
+            // Backspace key -> Go to the parent directory and select the directory we came from
+            crossterm::event::KeyCode::Backspace => match self.current_dir.parent() {
+                Some(parent) => {
+                    let parent = parent.to_path_buf();
+                    let current_dir = self.current_dir.clone();
+                    self.open_dir(parent, Some(current_dir)).await?;
+                }
+                None => {
+                    return Ok(Action::UpdateAppState(AppState::Failure(
+                        "No parent directory available".to_string(),
+                    ))
+                    .into());
+                }
+            },
+            // Refresh the current directory
+            crossterm::event::KeyCode::F(5) => {
+                let select = self.selected_entry().map(|entry| entry.path.clone());
+                self.analyze(self.current_dir.clone(), select).await?;
+            }
+            crossterm::event::KeyCode::Delete => {
+                self.pending_delete = self.selected_entry().cloned();
+            }
+            crossterm::event::KeyCode::Char('o')
+                if key.modifiers == crossterm::event::KeyModifiers::CONTROL =>
+            {
+                return Ok(Action::HideOrShowSystemOverview.into());
+            }
+            crossterm::event::KeyCode::Char('t')
+                if key.modifiers == crossterm::event::KeyModifiers::CONTROL =>
+            {
+                self.theme = self.theme.toggle_theme();
+                return Ok(Action::ToggleTheme(self.theme).into());
+            }
+            crossterm::event::KeyCode::F(1)
+                if key.modifiers == crossterm::event::KeyModifiers::NONE =>
+            {
+                self.app_context = AppContext::NotActive;
+                return Ok(Action::ShowHelp(AppContext::DiskUsage).into());
+            }
+            crossterm::event::KeyCode::F(2)
+                if key.modifiers == crossterm::event::KeyModifiers::NONE =>
+            {
+                self.app_context = AppContext::NotActive;
+                return Ok(Action::ShowAbout(AppContext::DiskUsage).into());
+            }
+            crossterm::event::KeyCode::F(3)
+                if key.modifiers == crossterm::event::KeyModifiers::NONE =>
+            {
+                self.app_context = AppContext::NotActive;
+                return Ok(Action::ShowSettings(AppContext::DiskUsage).into());
+            }
+            _ => {}
+        }
+
+        Ok(None)
+    }
+
+    async fn update(&mut self, action: &Action) -> Result<Option<Action>> {
+        match action {
+            Action::SwitchAppContext(context) => {
+                self.app_context = *context;
+            }
+            Action::ShowDiskUsage(dir) => {
+                self.entries = StatefulTable::new();
+                self.current_dir = dir.clone();
+                self.open_dir(dir.clone(), None).await?;
+            }
+            Action::DiskUsageDone(disk_usage) => {
+                self.is_working = false;
+                if let Some((dir, select)) = self.pending_analysis.take() {
+                    match disk_usage {
+                        Some(disk_usage) => {
+                            self.cache.extend(disk_usage.clone());
+                            self.show_dir(dir, select.as_deref());
+                            return Ok(Action::UpdateAppState(AppState::Done("Done".into())).into());
+                        }
+                        None => {
+                            return Ok(Action::UpdateAppState(AppState::Failure(
+                                "Unable to analyze the directory".into(),
+                            ))
+                            .into());
+                        }
+                    }
+                }
+            }
+            Action::DeleteDone(path) => {
+                self.cache.remove(path);
+                if self.app_context == AppContext::DiskUsage {
+                    self.is_working = false;
+                    let index = self.entries.selected_item;
+                    self.show_dir(self.current_dir.clone(), None);
+                    // select the entry, which has moved up to the position of the deleted one
+                    self.entries.scroll_down_by(index);
+                    return Ok(Action::UpdateAppState(AppState::Done("Deleted".into())).into());
+                }
+            }
+            Action::DeleteFailure(msg) if self.app_context == AppContext::DiskUsage => {
+                self.is_working = false;
+                return Ok(Action::UpdateAppState(AppState::Failure(msg.clone())).into());
+            }
+            Action::Resize(_, _) => {
+                self.start_index = 0;
+            }
+            Action::ToggleTheme(theme) => {
+                self.theme = *theme;
+            }
+            Action::HideOrShowSystemOverview => {
+                self.use_whole_draw_area = !self.use_whole_draw_area;
+            }
+            Action::ApplyAppSettings(c) => {
+                self.follow_sym_links = c.follow_sym_links();
+            }
+            _ => {}
+        }
+        Ok(None)
+    }
+
+    fn render(&mut self, f: &mut ratatui::Frame<'_>, area: Rect) -> Result<()> {
+        if self.should_render() {
+            // Control the draw area dependent if the InfoWidget is showing or not
+            let draw_area = if self.use_whole_draw_area {
+                let overview_area = get_main_layout(area).overview_area;
+                overview_area.union(get_main_layout(area).main_area)
+            } else {
+                get_main_layout(area).main_area
+            };
+
+            let [top_spacer_area, draw_area] =
+                Layout::vertical([Constraint::Length(1), Constraint::Fill(1)]).areas(draw_area);
+
+            let theme_colors = self.theme.theme_colors();
+
+            let main_block_title = format!(
+                " Disk-Usage: [{}] ",
+                utils::format_path_for_display(&self.current_dir)
+            );
+
+            let inner_block_title = format!(
+                " Total: {} - Items: {} ",
+                utils::convert_bytes_to_human_readable(self.current_size),
+                self.current_item_count
+            );
+
+            let selected_hint = format!(
+                " {}/{} ",
+                (self.entries.selected_item + 1).min(self.entries.items.len()),
+                self.entries.items.len()
+            );
+
+            let help_msg = vec![
+                " <Enter>".fg(theme_colors.main_text_fg),
+                " Open ".fg(theme_colors.main_fg),
+                " <Backspace>".fg(theme_colors.main_text_fg),
+                " Parent ".fg(theme_colors.main_fg),
+                " <Del>".fg(theme_colors.main_text_fg),
+                " Delete ".fg(theme_colors.main_fg),
+                " <F5>".fg(theme_colors.main_text_fg),
+                " Refresh ".fg(theme_colors.main_fg),
+                " <Esc>".fg(theme_colors.main_text_fg),
+                " Back to explorer ".fg(theme_colors.main_fg),
+            ];
+
+            let first_block = Block::default()
+                .title_top(
+                    Line::from(main_block_title)
+                        .style(Style::new().fg(theme_colors.alt_fg))
+                        .left_aligned(),
+                )
+                .borders(Borders::TOP | Borders::BOTTOM)
+                .border_type(BorderType::QuadrantInside)
+                .border_style(Style::new().fg(theme_colors.alt_bg))
+                .style(Style::new().bg(theme_colors.alt_bg));
+
+            let second_block = Block::default()
+                .title_top(
+                    Line::from(inner_block_title).style(Style::new().fg(theme_colors.alt_fg)),
+                )
+                .title_top(Line::from(selected_hint).right_aligned())
+                .title_bottom(Line::from(help_msg))
+                .title_alignment(Alignment::Center)
+                .borders(Borders::ALL)
+                .border_type(BorderType::Rounded)
+                .border_style(Style::new().fg(theme_colors.main_fg))
+                .style(Style::new().bg(theme_colors.alt_bg));
+
+            let [second_block_area] = Layout::vertical([Constraint::Fill(1)])
+                .margin(1)
+                .areas(first_block.inner(draw_area));
+
+            let [table_area] = Layout::vertical([Constraint::Fill(1)])
+                .areas(second_block.inner(second_block_area));
+
+            // only the visible rows are drawn, header and padding are not available for rows
+            let visible_rows = table_area.height.saturating_sub(2).max(1) as usize;
+            self.page_height = visible_rows as u16;
+
+            let selected = self.entries.selected_item;
+            if selected < self.start_index {
+                self.start_index = selected;
+            } else if selected >= self.start_index + visible_rows {
+                self.start_index = selected + 1 - visible_rows;
+            }
+
+            let header_style = Style::default()
+                .fg(theme_colors.header_fg)
+                .bg(theme_colors.header_bg);
+
+            let header = ["Size", "Share", "Name", "Items"]
+                .into_iter()
+                .map(Cell::from)
+                .collect::<Row>()
+                .style(header_style)
+                .height(1);
+
+            let table_widths = [
+                Constraint::Length(12),
+                Constraint::Length(USAGE_BAR_WIDTH as u16 + 8),
+                Constraint::Fill(1),
+                Constraint::Length(10),
+            ];
+
+            let current_size = self.current_size;
+            let rows = self
+                .entries
+                .items
+                .iter()
+                .skip(self.start_index)
+                .take(visible_rows)
+                .map(|entry| {
+                    let ratio = if current_size == 0 {
+                        0.0
+                    } else {
+                        entry.size as f64 / current_size as f64
+                    };
+
+                    let (name, name_color, items) = if entry.is_dir {
+                        (
+                            format!("{}{}", entry.name, SEPARATOR),
+                            theme_colors.dir_color,
+                            entry.item_count.to_string(),
+                        )
+                    } else {
+                        (entry.name.clone(), theme_colors.file_color, " - ".into())
+                    };
+
+                    Row::new(vec![
+                        Cell::from(utils::convert_bytes_to_human_readable(entry.size))
+                            .fg(theme_colors.alt_fg),
+                        Cell::from(Self::usage_bar(ratio)).fg(theme_colors.main_fg),
+                        Cell::from(name).fg(name_color),
+                        Cell::from(items).fg(theme_colors.alt_fg),
+                    ])
+                })
+                .collect::<Vec<Row>>();
+
+            let usage_table = Table::new(rows, table_widths)
+                .header(header)
+                .block(Block::new().padding(Padding {
+                    left: 0,
+                    right: 0,
+                    top: 1,
+                    bottom: 0,
+                }))
+                .row_highlight_style(Style::new().fg(theme_colors.selected_color))
+                .highlight_symbol(HIGHLIGHT_SYMBOL)
+                .bg(theme_colors.alt_bg)
+                .highlight_spacing(HighlightSpacing::Always);
+
+            let mut table_state = TableState::default();
+            if !self.entries.items.is_empty() {
+                table_state.select(Some(selected - self.start_index));
+            }
+
+            f.render_widget(Line::from(" ").bg(theme_colors.alt_bg), top_spacer_area);
+            f.render_widget(first_block, draw_area);
+            f.render_widget(second_block, second_block_area);
+            f.render_stateful_widget(usage_table, table_area, &mut table_state);
+
+            if let Some(entry) = &self.pending_delete {
+                self.render_delete_confirmation(f, area, entry);
+            }
+        }
+        Ok(())
+    }
+}
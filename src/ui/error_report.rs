@@ -0,0 +1,86 @@
+use ratatui::{prelude::*, widgets::*};
+
+use crate::ui::{HIGHLIGHT_SYMBOL, ThemeColor, centered_rect};
+
+/// Popup, that lists the entries a long running operation failed for, one line per entry
+#[derive(Debug, Default)]
+pub struct ErrorReport {
+    title: String,
+    errors: Vec<String>,
+    list_state: ListState,
+    is_visible: bool,
+}
+
+impl ErrorReport {
+    /// Shows the report with the given errors, the first error is selected
+    pub fn open(&mut self, title: String, errors: Vec<String>) {
+        self.list_state.select((!errors.is_empty()).then_some(0));
+        self.title = title;
+        self.errors = errors;
+        self.is_visible = true;
+    }
+
+    pub fn close(&mut self) {
+        self.is_visible = false;
+        self.errors.clear();
+    }
+
+    pub fn is_visible(&self) -> bool {
+        self.is_visible
+    }
+
+    pub fn next(&mut self) {
+        if !self.errors.is_empty() {
+            let next = self
+                .list_state
+                .selected()
+                .map_or(0, |i| (i + 1).min(self.errors.len() - 1));
+            self.list_state.select(Some(next));
+        }
+    }
+
+    pub fn previous(&mut self) {
+        let previous = self
+            .list_state
+            .selected()
+            .map_or(0, |i| i.saturating_sub(1));
+        self.list_state.select(Some(previous));
+    }
+
+    pub fn render(&mut self, f: &mut Frame, area: Rect, theme_colors: &ThemeColor) {
+        let block = Block::default()
+            .title_top(format!(" {} ", self.title))
+            .title_bottom(Line::from(vec![
+                " <↑↓>".fg(theme_colors.main_text_fg),
+                " Scroll ".fg(theme_colors.main_fg),
+                "|".fg(theme_colors.main_fg),
+                " <Enter|Esc>".fg(theme_colors.main_text_fg),
+                " Close ".fg(theme_colors.main_fg),
+            ]))
+            .title_alignment(Alignment::Center)
+            .borders(Borders::ALL)
+            .border_type(BorderType::Rounded)
+            .border_style(Style::new().bold().fg(Color::LightRed))
+            .style(Style::new().bg(theme_colors.alt_bg));
+
+        let items: Vec<ListItem> = self
+            .errors
+            .iter()
+            .map(|error| {
+                ListItem::new(Span::styled(
+                    error.as_str(),
+                    Style::new().fg(theme_colors.main_text_fg),
+                ))
+            })
+            .collect();
+
+        let list = List::new(items)
+            .block(block)
+            .highlight_style(Style::new().fg(theme_colors.selected_color))
+            .highlight_symbol(HIGHLIGHT_SYMBOL);
+
+        let popup_area = centered_rect(70, 60, area);
+        f.render_widget(Clear, popup_area);
+        f.render_stateful_widget(list, popup_area, &mut self.list_state);
+    }
+}
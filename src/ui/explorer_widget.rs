@@ -3,10 +3,10 @@ use std::path::PathBuf;
 use crate::{
     app::{AppContext, AppState, actions::Action, config::AppConfig, key_bindings},
     component::Component,
-    file_handling::{Explorer, FilteredEntries, parent_dir_entry},
+    file_handling::{Explorer, FilteredEntries, git::GitInfo, parent_dir_entry},
     models::Scrollable,
     tui::Event,
-    ui::{HIGHLIGHT_SYMBOL, Theme, get_main_layout},
+    ui::{HIGHLIGHT_SYMBOL, Theme, get_main_layout, git_status_span},
     utils,
 };
 use anyhow::Result;
@@ -39,17 +39,28 @@ pub struct ExplorerWidget {
     /// Flag to control the receiving of the key events for the explorer widget
     /// If the widget is working, then incoming key events are ignored
     is_working: bool,
+    /// Flag that indicates that the content of the current directory is still loading.
+    /// In contrast to `is_working` the user can still navigate through the already loaded entries
+    is_loading: bool,
+    /// Flag that indicates that the metadata of the selected directory is calculated, which can be cancelled with `Esc`
+    is_calculating_metadata: bool,
     /// Indicates if the Metadata PopUp widget is showing, if it is the case the `ExplorerWidget` still drawn
     is_metadata_pop_up: bool,
     list_state: ListState,
     follow_sym_links: bool,
+    /// Git status of the work tree that contains the current directory, if any
+    git_info: Option<GitInfo>,
+    /// Entry revealed from the Results page, that is selected as soon as it is loaded
+    reveal_target: Option<PathBuf>,
+    /// The Results page keeps its result after revealing an entry, so that `Ctrl + r` can return to it
+    has_revealed_result: bool,
 }
 
 impl ExplorerWidget {
     pub fn new(p: PathBuf, follow_sym_links: bool) -> Self {
         Self {
             app_context: Default::default(),
-            explorer: Explorer::load_directory(p, follow_sym_links),
+            explorer: Explorer::new(p),
             theme: Default::default(),
             use_whole_draw_area: Default::default(),
             action_sender: Default::default(),
@@ -58,9 +69,14 @@ impl ExplorerWidget {
             page_height: Default::default(),
             filtered_entries: Default::default(),
             is_working: Default::default(),
+            is_loading: Default::default(),
+            is_calculating_metadata: Default::default(),
             is_metadata_pop_up: Default::default(),
             list_state: Default::default(),
             follow_sym_links,
+            git_info: Default::default(),
+            reveal_target: Default::default(),
+            has_revealed_result: false,
         }
     }
     /// Helper function to send a [`Action`] to the [`Explorer`]
@@ -72,6 +88,36 @@ impl ExplorerWidget {
         }
         Ok(())
     }
+
+    /// Cancels the running metadata calculation, the Explorer reports the cancellation in the footer
+    async fn cancel_dir_metadata(&mut self) -> Result<Option<Action>> {
+        self.send_explorer_action(Action::CancelDirMetadata).await?;
+        self.is_working = false;
+        self.is_calculating_metadata = false;
+        Ok(None)
+    }
+    /// Helper function to load the content of the given directory.
+    /// Set the `is_loading` flag to true
+    ///
+    /// The git status is only reloaded if the directory is not inside the already known work tree,
+    /// or if it is inside a nested repository or submodule
+    async fn load_dir(&mut self, p: PathBuf) -> Result<()> {
+        let reload_git_status = !self
+            .git_info
+            .as_ref()
+            .is_some_and(|git_info| git_info.is_status_known(&p));
+
+        if let Some(sender) = &self.explorer_action_sender {
+            self.is_loading = true;
+            sender
+                .send(Action::LoadDir(p.clone(), self.follow_sym_links))
+                .await?;
+            if reload_git_status {
+                sender.send(Action::LoadGitStatus(p)).await?;
+            }
+        }
+        Ok(())
+    }
     /// Helper function to send a [`Action`] to all components
     fn send_app_action(&self, action: Action) -> Result<()> {
         if let Some(handler) = &self.action_sender {
@@ -80,6 +126,20 @@ impl ExplorerWidget {
         Ok(())
     }
 
+    /// Selects the entry to reveal, if it is part of the loaded entries
+    fn select_reveal_target(&mut self) {
+        let Some(index) = self
+            .reveal_target
+            .as_ref()
+            .and_then(|target| self.explorer.index_of(target))
+        else {
+            return;
+        };
+        self.explorer.go_to_index(index);
+        self.list_state.select(self.explorer.selected().into());
+        self.reveal_target = None;
+    }
+
     fn get_entries_by_initial_letter(&mut self, c: char) -> AppState {
         if self.filtered_entries.matches_letter(c) {
             // If the letter matches, find the next entry
@@ -139,6 +199,13 @@ impl Component for ExplorerWidget {
         &mut self,
         tx: tokio::sync::mpsc::Sender<Action>,
     ) -> Result<()> {
+        // load the content and the git status of the start directory
+        tx.try_send(Action::LoadDir(
+            self.explorer.cwd().clone(),
+            self.follow_sym_links,
+        ))?;
+        tx.try_send(Action::LoadGitStatus(self.explorer.cwd().clone()))?;
+        self.is_loading = true;
         self.explorer_action_sender = Some(tx);
         Ok(())
     }
@@ -160,6 +227,12 @@ impl Component for ExplorerWidget {
         if let Some(event) = event {
             match event {
                 Event::Key(key_event) => {
+                    if self.is_calculating_metadata
+                        && self.app_context == AppContext::Explorer
+                        && key_event.code == crossterm::event::KeyCode::Esc
+                    {
+                        return self.cancel_dir_metadata().await;
+                    }
                     if self.should_handle_events() {
                         let cmd_desc =
                             key_bindings::get_command_description(&key_event, &self.app_context)
@@ -229,11 +302,9 @@ impl Component for ExplorerWidget {
             // Refresh the CWD
             crossterm::event::KeyCode::F(5) => {
                 if self.explorer.cwd().is_dir() {
-                    self.send_explorer_action(Action::LoadDir(
-                        self.explorer.cwd().clone(),
-                        self.follow_sym_links,
-                    ))
-                    .await?;
+                    // always refresh the git status, e.g. after the user has committed some changes
+                    self.git_info = None;
+                    self.load_dir(self.explorer.cwd().clone()).await?;
                 } else {
                     return Ok(Action::UpdateAppState(AppState::Failure(
                         "The current directory no longer exists".to_string(),
@@ -244,15 +315,17 @@ impl Component for ExplorerWidget {
             }
             // Enter key -> Go into a directory, if any
             crossterm::event::KeyCode::Enter => {
-                let selected_entry = &self.explorer.items()[self.explorer.selected()];
+                let Some(selected_entry) = self.explorer.items().get(self.explorer.selected())
+                else {
+                    return Ok(None);
+                };
 
                 if !selected_entry.path.is_file() {
                     if selected_entry.path.is_dir() {
                         let new_dir = selected_entry.path.clone();
 
                         // send the explorer operation to change the directory
-                        self.send_explorer_action(Action::LoadDir(new_dir, self.follow_sym_links))
-                            .await?;
+                        self.load_dir(new_dir).await?;
                     } else {
                         return Ok(Action::UpdateAppState(AppState::Failure(
                             "The selected directory no longer exists".to_string(),
@@ -268,11 +341,7 @@ impl Component for ExplorerWidget {
                 // check if the current working directory has a parent directory
                 match self.explorer.cwd().parent() {
                     Some(parent_dir) => {
-                        self.send_explorer_action(Action::LoadDir(
-                            parent_dir.to_path_buf(),
-                            self.follow_sym_links,
-                        ))
-                        .await?;
+                        self.load_dir(parent_dir.to_path_buf()).await?;
                     }
                     None => {
                         self.send_app_action(Action::UpdateAppState(AppState::Failure(
@@ -287,7 +356,10 @@ impl Component for ExplorerWidget {
             crossterm::event::KeyCode::Char('c')
                 if key.modifiers == crossterm::event::KeyModifiers::CONTROL =>
             {
-                let selected_entry = &self.explorer.items()[self.explorer.selected()];
+                let Some(selected_entry) = self.explorer.items().get(self.explorer.selected())
+                else {
+                    return Ok(None);
+                };
 
                 if !selected_entry.name.starts_with(&parent_dir_entry()) {
                     let path_to_copy = utils::absolute_path_as_string(&selected_entry.path);
@@ -322,6 +394,33 @@ impl Component for ExplorerWidget {
                     .into());
                 }
             }
+            // Ctrl + r -> Return to the Results page, after one of the results was revealed
+            crossterm::event::KeyCode::Char('r')
+                if key.modifiers == crossterm::event::KeyModifiers::CONTROL =>
+            {
+                if !self.has_revealed_result {
+                    return Ok(Action::UpdateAppState(AppState::Failure(
+                        "No revealed search result to return to".to_string(),
+                    ))
+                    .into());
+                }
+                self.has_revealed_result = false;
+                Ok(Action::SwitchAppContext(AppContext::Results).into())
+            }
+            // Ctrl + d -> Analyze the disk usage of the current directory
+            crossterm::event::KeyCode::Char('d')
+                if key.modifiers == crossterm::event::KeyModifiers::CONTROL =>
+            {
+                if self.explorer.cwd().is_dir() {
+                    self.send_app_action(Action::SwitchAppContext(AppContext::DiskUsage))?;
+                    return Ok(Action::ShowDiskUsage(self.explorer.cwd().clone()).into());
+                } else {
+                    return Ok(Action::UpdateAppState(AppState::Failure(
+                        "The current directory no longer exists".to_string(),
+                    ))
+                    .into());
+                }
+            }
             // Ctrl + u -> Go to home directory
             crossterm::event::KeyCode::Char('u')
                 if key.modifiers == crossterm::event::KeyModifiers::CONTROL =>
@@ -331,11 +430,7 @@ impl Component for ExplorerWidget {
                     Some(home_dir) => {
                         // switch to home dir, only if the CWD is not the home dir
                         if *self.explorer.cwd() != home_dir {
-                            self.send_explorer_action(Action::LoadDir(
-                                home_dir,
-                                self.follow_sym_links,
-                            ))
-                            .await?;
+                            self.load_dir(home_dir).await?;
                         } else {
                             self.send_app_action(Action::UpdateAppState(AppState::Done(
                                 "Already in home directory".to_string(),
@@ -351,11 +446,47 @@ impl Component for ExplorerWidget {
 
                 Ok(None)
             }
+            // Ctrl + e -> Mark the selected file or directory for comparison or compare it with the marked one
+            crossterm::event::KeyCode::Char('e')
+                if key.modifiers == crossterm::event::KeyModifiers::CONTROL =>
+            {
+                let Some(selected_entry) = self.explorer.items().get(self.explorer.selected())
+                else {
+                    return Ok(None);
+                };
+
+                // IMPORTANT: exclude the entry to go to the parent directory [e.g. ..\, ../]
+                if selected_entry.name.starts_with(&parent_dir_entry()) {
+                    return Ok(None);
+                }
+
+                if selected_entry.path.is_file() {
+                    return Ok(Action::SelectDiffFile(
+                        selected_entry.path.clone(),
+                        AppContext::Explorer,
+                    )
+                    .into());
+                } else if selected_entry.path.is_dir() {
+                    return Ok(Action::SelectCompareDir(
+                        selected_entry.path.clone(),
+                        AppContext::Explorer,
+                    )
+                    .into());
+                } else {
+                    return Ok(Action::UpdateAppState(AppState::Failure(
+                        "The selected object no longer exists".to_string(),
+                    ))
+                    .into());
+                }
+            }
             // Ctrl + a -> Display metadata for the selected object, if any
             crossterm::event::KeyCode::Char('a')
                 if key.modifiers == crossterm::event::KeyModifiers::CONTROL =>
             {
-                let selected_entry = &self.explorer.items()[self.explorer.selected()];
+                let Some(selected_entry) = self.explorer.items().get(self.explorer.selected())
+                else {
+                    return Ok(None);
+                };
 
                 // IMPORTANT: exclude the entry to go to the parent directory [e.g. ..\, ../]
                 if !selected_entry.name.starts_with(&parent_dir_entry()) {
@@ -388,6 +519,7 @@ impl Component for ExplorerWidget {
                             self.follow_sym_links,
                         ))
                         .await?;
+                        self.is_calculating_metadata = true;
                     }
                 }
 
@@ -439,16 +571,59 @@ impl Component for ExplorerWidget {
             Action::SwitchAppContext(context) => {
                 self.app_context = *context;
             }
-            Action::LoadDirDone(explorer) => {
-                self.is_working = false;
+            Action::LoadDirStarted(explorer) => {
                 self.explorer = explorer.clone();
                 self.filtered_entries.reset();
                 self.list_state.select(self.explorer.selected().into());
                 self.explorer.set_terminal_height(self.terminal_height);
+                // the user navigated to another directory, before the revealed entry was loaded
+                if self
+                    .reveal_target
+                    .as_ref()
+                    .is_some_and(|target| target.parent() != Some(self.explorer.cwd().as_path()))
+                {
+                    self.reveal_target = None;
+                }
+            }
+            Action::LoadDirChunk(chunk) => {
+                self.explorer.append_entries(chunk.clone());
+                // the indices of previous matches are no longer valid
+                self.filtered_entries.reset();
+                self.list_state.select(self.explorer.selected().into());
+                self.select_reveal_target();
+            }
+            // load the parent directory of a search hit and select the hit, once it is loaded
+            Action::RevealInExplorer(path) => {
+                let Some(parent_dir) = path.parent() else {
+                    return Ok(None);
+                };
+                self.reveal_target = Some(path.clone());
+                self.has_revealed_result = true;
+                self.load_dir(parent_dir.to_path_buf()).await?;
+            }
+            // the revealed result is replaced by the new search
+            Action::ShowResultsPage(_, _) => self.has_revealed_result = false,
+            // reload the current directory, if one of its entries was deleted in another view
+            Action::DeleteDone(path) if path.parent() == Some(self.explorer.cwd().as_path()) => {
+                self.git_info = None;
+                self.load_dir(self.explorer.cwd().clone()).await?;
+            }
+            Action::GitStatusDone(git_info) => {
+                self.git_info = git_info.clone();
+            }
+            Action::LoadDirDone => {
+                self.is_loading = false;
                 self.send_app_action(Action::UpdateAppState(AppState::Done("Done".to_string())))?;
+                if self.reveal_target.take().is_some() {
+                    return Ok(Action::UpdateAppState(AppState::Failure(
+                        "The revealed entry no longer exists".to_string(),
+                    ))
+                    .into());
+                }
             }
             Action::LoadDirMetadataDone(metadata) => {
                 self.is_working = false;
+                self.is_calculating_metadata = false;
                 match metadata {
                     Some(metadata) => {
                         self.is_metadata_pop_up = true;
@@ -507,11 +682,19 @@ impl Component for ExplorerWidget {
 
             let block_title_top = format!(" Cwd: [{}] ", self.explorer.cwd_display_name());
 
-            let block_title_bottom = format!(
-                " Dirs: {} - Files: {} ",
-                self.explorer.dir_counter(),
-                self.explorer.file_counter(),
-            );
+            let block_title_bottom = if self.is_loading {
+                format!(
+                    " Loading... Dirs: {} - Files: {} ",
+                    self.explorer.dir_counter(),
+                    self.explorer.file_counter(),
+                )
+            } else {
+                format!(
+                    " Dirs: {} - Files: {} ",
+                    self.explorer.dir_counter(),
+                    self.explorer.file_counter(),
+                )
+            };
 
             let list = List::new(
                 self.explorer
@@ -523,7 +706,23 @@ impl Component for ExplorerWidget {
                         } else {
                             theme_colors.file_color
                         };
-                        Text::from(file_entry.name.clone()).fg(item_color)
+                        let name = Span::from(file_entry.name.clone()).fg(item_color);
+
+                        // show the git status markers only inside a work tree, but not for the parent directory entry
+                        match &self.git_info {
+                            Some(git_info) => {
+                                let status = if file_entry.name == parent_dir_entry() {
+                                    None
+                                } else {
+                                    git_info.status_of(&file_entry.path)
+                                };
+                                Text::from(Line::from(vec![
+                                    git_status_span(status, &theme_colors),
+                                    name,
+                                ]))
+                            }
+                            None => Text::from(name),
+                        }
                     }),
             )
             .highlight_spacing(HighlightSpacing::Always)
@@ -10,7 +10,7 @@ use crate::{
 };
 
 const APP_CONTEXT_TITLE: &str = "Context: ";
-const APP_CONTEXT_LENGTH: u16 = 17;
+const APP_CONTEXT_LENGTH: u16 = 19;
 const KEYSTROKE_TITLE: &str = "Last Key-Press: ";
 const THEME_HINT_TITLE: &str = "Theme: ";
 const THEME_HINT_LENGTH: u16 = 14;
@@ -94,7 +94,12 @@ impl Component for Footer {
     fn should_handle_events(&self) -> bool {
         matches!(
             self.app_context,
-            AppContext::Explorer | AppContext::Search | AppContext::Results
+            AppContext::Explorer
+                | AppContext::Search
+                | AppContext::Results
+                | AppContext::DiskUsage
+                | AppContext::Diff
+                | AppContext::DirCompare
         )
     }
 
@@ -2,6 +2,10 @@
 use anyhow::Result;
 use async_trait::async_trait;
 use ratatui::{prelude::*, style::palette::tailwind, widgets::*};
+use std::{
+    path::PathBuf,
+    time::{Duration, Instant},
+};
 
 use crate::{
     app::{actions::Action, config::AppConfig},
@@ -17,11 +21,24 @@ const NORMAL_USAGE_COLOR: Color = tailwind::GREEN.c500;
 const MEDIUM_USAGE_COLOR: Color = tailwind::YELLOW.c500;
 const HIGH_USAGE_COLOR: Color = tailwind::RED.c500;
 
+/// Maximum length of the mount point and filesystem type values
+const MAX_FILESYSTEM_VALUE_LENGTH: usize = 16;
+
+/// Minimum time between two updates of the filesystem details, e.g. the free space
+const FILESYSTEM_REFRESH_INTERVAL: Duration = Duration::from_secs(5);
+
 /// Display the system details like OS version, memory usage etc...
 #[derive(Debug)]
 pub struct SystemOverview {
     /// Action sender that can send actions to all other components
     action_sender: Option<tokio::sync::mpsc::UnboundedSender<Action>>,
+    /// Action sender, that sends actions to the Explorer task, which reads the filesystem details
+    explorer_action_sender: Option<tokio::sync::mpsc::Sender<Action>>,
+    /// The current working directory of the Explorer, whose filesystem is shown
+    cwd: PathBuf,
+    /// The filesystem details are read by the Explorer task, only one request runs at a time
+    is_loading_filesystem: bool,
+    last_filesystem_refresh: Instant,
     /// Collection of system information, e.g. system resources, usage and OS specifics
     system_details: SystemDetails,
     /// Default App-Theme is `Dark`
@@ -33,6 +50,10 @@ impl Default for SystemOverview {
     fn default() -> Self {
         Self {
             action_sender: None,
+            explorer_action_sender: None,
+            cwd: PathBuf::new(),
+            is_loading_filesystem: false,
+            last_filesystem_refresh: Instant::now(),
             system_details: SystemDetails::default(),
             theme: Theme::default(),
             is_active: true,
@@ -53,6 +74,23 @@ impl SystemOverview {
         self.system_details.refresh()
     }
 
+    /// Requests the details of the filesystem, which contains the current directory, from the Explorer task.
+    /// A new request is only sent after the previous one was answered, so that a hung network mount
+    /// doesn't pile up requests.
+    async fn request_filesystem_info(&mut self) -> Result<()> {
+        if self.is_loading_filesystem {
+            return Ok(());
+        }
+        if let Some(sender) = &self.explorer_action_sender {
+            self.is_loading_filesystem = true;
+            self.last_filesystem_refresh = Instant::now();
+            sender
+                .send(Action::LoadFilesystemInfo(self.cwd.clone()))
+                .await?;
+        }
+        Ok(())
+    }
+
     fn get_sys_info_lines(&self) -> (Vec<Line<'_>>, Vec<Line<'_>>) {
         let theme_colors = self.theme.theme_colors();
 
@@ -111,8 +149,6 @@ impl SystemOverview {
         let resource_keys = vec![
             Line::from(Span::from("CPU-Cores   :").fg(theme_colors.alt_fg))
                 .alignment(Alignment::Left),
-            Line::from(Span::from("Total Space :").fg(theme_colors.alt_fg))
-                .alignment(Alignment::Left),
             Line::from(Span::from("Total Memory:").fg(theme_colors.alt_fg))
                 .alignment(Alignment::Left),
             Line::from(Span::from("Total Swap  :").fg(theme_colors.alt_fg))
@@ -126,14 +162,6 @@ impl SystemOverview {
                     .fg(theme_colors.alt_fg),
             )
             .alignment(Alignment::Left),
-            Line::from(
-                Span::default()
-                    .content(utils::convert_bytes_to_human_readable(
-                        self.system_details.total_space,
-                    ))
-                    .fg(theme_colors.alt_fg),
-            )
-            .alignment(Alignment::Left),
             Line::from(
                 Span::default()
                     .content(utils::convert_bytes_to_human_readable(
@@ -155,6 +183,58 @@ impl SystemOverview {
         (resource_keys, resource_values)
     }
 
+    fn get_filesystem_info_lines(&self) -> (Vec<Line<'_>>, Vec<Line<'_>>) {
+        let theme_colors = self.theme.theme_colors();
+
+        let filesystem_keys = [
+            "Mount-Point:",
+            "FS-Type    :",
+            "Total      :",
+            "Free       :",
+            "Inodes     :",
+        ]
+        .into_iter()
+        .map(|key| Line::from(Span::from(key).fg(theme_colors.alt_fg)).alignment(Alignment::Left))
+        .collect();
+
+        let values = match &self.system_details.filesystem {
+            Some(filesystem) => vec![
+                utils::reduce_string_and_fill_with_dots(
+                    &filesystem.mount_point.to_string_lossy(),
+                    MAX_FILESYSTEM_VALUE_LENGTH,
+                ),
+                utils::reduce_string_and_fill_with_dots(
+                    &filesystem.fs_type,
+                    MAX_FILESYSTEM_VALUE_LENGTH,
+                ),
+                utils::convert_bytes_to_human_readable(filesystem.total_space),
+                utils::convert_bytes_to_human_readable(filesystem.available_space),
+                filesystem
+                    .inodes
+                    .as_ref()
+                    .map_or("n/a".to_string(), |inodes| {
+                        format!(
+                            "{:.1}% used",
+                            utils::calculate_percentage_f64(
+                                inodes.total.saturating_sub(inodes.free) as f64,
+                                inodes.total as f64,
+                            )
+                        )
+                    }),
+            ],
+            None => vec!["Unknown".to_string()],
+        };
+
+        let filesystem_values = values
+            .into_iter()
+            .map(|value| {
+                Line::from(Span::from(value).fg(theme_colors.alt_fg)).alignment(Alignment::Left)
+            })
+            .collect();
+
+        (filesystem_keys, filesystem_values)
+    }
+
     fn draw_resource_info(&self, f: &mut ratatui::Frame<'_>, area: Rect) {
         let theme_colors = self.theme.theme_colors();
 
@@ -183,6 +263,32 @@ impl SystemOverview {
         f.render_widget(values, values_area);
     }
 
+    fn draw_filesystem_info(&self, f: &mut ratatui::Frame<'_>, area: Rect) {
+        let theme_colors = self.theme.theme_colors();
+
+        let info_block = Block::default()
+            .title(" Filesystem ")
+            .title_style(Style::new().fg(theme_colors.main_fg))
+            .borders(Borders::ALL)
+            .border_type(BorderType::Rounded)
+            .border_style(Style::new().fg(theme_colors.main_fg))
+            .title_alignment(Alignment::Left)
+            .padding(Padding::left(1))
+            .style(Style::new().bg(theme_colors.main_bg));
+
+        let inner_block_area = info_block.inner(area);
+
+        let [keys_area, values_area] =
+            Layout::horizontal([Constraint::Length(13), Constraint::Fill(1)])
+                .areas(inner_block_area);
+
+        let (keys, values) = self.get_filesystem_info_lines();
+
+        f.render_widget(info_block, area);
+        f.render_widget(Paragraph::new(keys), keys_area);
+        f.render_widget(Paragraph::new(values), values_area);
+    }
+
     fn draw_sys_info(&self, f: &mut ratatui::Frame<'_>, area: Rect) {
         let theme_colors = self.theme.theme_colors();
 
@@ -305,6 +411,14 @@ impl Component for SystemOverview {
         Ok(())
     }
 
+    fn register_explorer_action_sender(
+        &mut self,
+        tx: tokio::sync::mpsc::Sender<Action>,
+    ) -> Result<()> {
+        self.explorer_action_sender = Some(tx);
+        Ok(())
+    }
+
     fn register_config_handler(&mut self, config: AppConfig) -> Result<()> {
         self.theme = config.theme();
         Ok(())
@@ -339,9 +453,24 @@ impl Component for SystemOverview {
 
     async fn update(&mut self, action: &Action) -> Result<Option<Action>> {
         match action {
-            Action::Tick => {
-                if self.is_active {
-                    self.refresh_system_details()
+            Action::Tick if self.is_active => {
+                self.refresh_system_details();
+                if self.last_filesystem_refresh.elapsed() >= FILESYSTEM_REFRESH_INTERVAL {
+                    self.request_filesystem_info().await?;
+                }
+            }
+            // the filesystem details and the disk usage follow the current directory of the Explorer
+            Action::LoadDirStarted(explorer) if explorer.cwd() != &self.cwd => {
+                self.cwd = explorer.cwd().clone();
+                self.request_filesystem_info().await?;
+            }
+            Action::FilesystemInfoDone(path, filesystem) => {
+                self.is_loading_filesystem = false;
+                if path == &self.cwd {
+                    self.system_details.set_filesystem(filesystem.clone());
+                } else {
+                    // the directory was changed while the details were read
+                    self.request_filesystem_info().await?;
                 }
             }
             Action::ToggleTheme(theme) => {
@@ -363,14 +492,20 @@ impl Component for SystemOverview {
             let overview_layout = Layout::horizontal([
                 Constraint::Length(42),
                 Constraint::Length(30),
+                Constraint::Length(MAX_FILESYSTEM_VALUE_LENGTH as u16 + 17),
                 Constraint::Fill(1),
             ]);
 
-            let [sys_info_area, resource_info_area, usage_info_area] =
-                overview_layout.areas(draw_area);
+            let [
+                sys_info_area,
+                resource_info_area,
+                filesystem_info_area,
+                usage_info_area,
+            ] = overview_layout.areas(draw_area);
 
             self.draw_sys_info(f, sys_info_area);
             self.draw_resource_info(f, resource_info_area);
+            self.draw_filesystem_info(f, filesystem_info_area);
             self.draw_usage_info(f, usage_info_area);
         }
 
@@ -5,7 +5,13 @@ use crossterm::event::KeyModifiers;
 use ratatui::{prelude::*, widgets::*};
 
 use crate::{
-    app::{AppState, actions::Action},
+    app::{
+        AppState,
+        actions::Action,
+        config::{parse_dir_list, parse_exclude_patterns},
+        history::SearchHistory,
+    },
+    file_handling::{refine::Refinement, search::QueryMode, walker::ExcludeFilter},
     ui::centered_rect_fixed_height,
     utils,
 };
@@ -22,6 +28,8 @@ pub struct TextInput {
     character_index: usize,
     /// To control how many characters the input field can hold
     input_field_width: u16,
+    /// Spaces are only allowed, if the input can contain several words, e.g. a search query with filters
+    allow_spaces: bool,
 }
 
 impl TextInput {
@@ -67,16 +75,19 @@ impl TextInput {
     }
 
     fn enter_string(&mut self, v: &str) {
-        v.chars()
-            .filter(|c| !c.is_whitespace())
-            .for_each(|c| self.enter_char(c));
+        v.chars().for_each(|c| self.enter_char(c));
+    }
+
+    /// Whitespaces like tabs or line breaks are never accepted
+    fn accepts(&self, c: char) -> bool {
+        !c.is_whitespace() || (self.allow_spaces && c == ' ')
     }
 
     fn enter_char(&mut self, new_char: char) {
         // Only insert if we still have room in the visible input field
         if self.input_field_width > 2
             && self.character_index <= (self.input_field_width - 3) as usize
-            && !new_char.is_whitespace()
+            && self.accepts(new_char)
         {
             let byte_idx = self.byte_index();
             self.value.insert(byte_idx, new_char);
@@ -96,21 +107,17 @@ impl TextInput {
     fn delete_char(&mut self, key_code: crossterm::event::KeyCode) {
         match key_code {
             // DELETE  →  remove character to the right of the cursor
-            crossterm::event::KeyCode::Delete => {
-                if self.character_index < self.value.len() {
-                    let before = self.value.chars().take(self.character_index);
-                    let after = self.value.chars().skip(self.character_index + 1);
-                    self.value = before.chain(after).collect();
-                }
+            crossterm::event::KeyCode::Delete if self.character_index < self.value.len() => {
+                let before = self.value.chars().take(self.character_index);
+                let after = self.value.chars().skip(self.character_index + 1);
+                self.value = before.chain(after).collect();
             }
             // BACKSPACE  →  remove character to the left of the cursor
-            crossterm::event::KeyCode::Backspace => {
-                if self.character_index != 0 {
-                    let before = self.value.chars().take(self.character_index - 1);
-                    let after = self.value.chars().skip(self.character_index);
-                    self.value = before.chain(after).collect();
-                    self.move_cursor_left();
-                }
+            crossterm::event::KeyCode::Backspace if self.character_index != 0 => {
+                let before = self.value.chars().take(self.character_index - 1);
+                let after = self.value.chars().skip(self.character_index);
+                self.value = before.chain(after).collect();
+                self.move_cursor_left();
             }
             _ => {}
         }
@@ -129,24 +136,77 @@ impl TextInput {
     }
 }
 
+/// State of the reverse incremental search through the history (`Ctrl+R`), similar to the history search of a shell
 #[derive(Debug, Default)]
+struct ReverseSearch {
+    /// The text the history entries must contain
+    text: String,
+    /// Index of the currently matching history entry
+    match_index: Option<usize>,
+    /// No (older) entry contains the search text, the last match is still shown
+    is_failed: bool,
+    /// The input before the reverse search was started, restored if the search is cancelled
+    original_input: String,
+}
+
+#[derive(Debug)]
 pub struct SearchInput {
     /// History of the inputs
-    history: Vec<String>,
+    history: SearchHistory,
     /// Current index in the history
     history_index: Option<usize>,
+    /// File in which the history is persisted, the history is only kept in memory if not set
+    history_file: Option<PathBuf>,
+    /// Active reverse incremental search through the history
+    reverse_search: Option<ReverseSearch>,
     /// Handles the users input
     pub text_input: TextInput,
 }
 
+impl Default for SearchInput {
+    fn default() -> Self {
+        Self {
+            history: Default::default(),
+            history_index: Default::default(),
+            history_file: Default::default(),
+            reverse_search: Default::default(),
+            // the search query can be combined with filters, e.g. `report size:>1M`
+            text_input: TextInput {
+                allow_spaces: true,
+                ..Default::default()
+            },
+        }
+    }
+}
+
 impl SearchInput {
+    /// Loads the history of the previous sessions, submitted queries are saved into the same file
+    pub fn load_history(&mut self, path: PathBuf) {
+        self.history = SearchHistory::load(&path);
+        self.history_file = Some(path);
+        self.history_index = None;
+    }
+
+    /// Replaces the current input with the value, e.g. the query of a saved search
+    pub fn set_value(&mut self, value: &str) {
+        self.reverse_search = None;
+        self.history_index = None;
+        self.text_input.clear();
+        self.text_input.enter_string(value);
+    }
+
+    /// Replaces the current input with the history entry at the given index
+    fn show_history_entry(&mut self, index: usize) {
+        let entry = self.history.get(index).unwrap_or_default().to_string();
+        self.text_input.clear();
+        self.text_input.enter_string(&entry);
+    }
+
     /// Navigate backwards (older) through the history
     fn history_backward(&mut self) {
         if self.history.is_empty() {
             return;
         }
-        self.text_input.value.clear();
-        self.text_input.reset_cursor();
 
         self.history_index = Some(match self.history_index {
             Some(i) if i > 0 => i - 1,
@@ -154,8 +214,7 @@ impl SearchInput {
             _ => self.history.len() - 1,
         });
 
-        let entry = self.history[self.history_index.unwrap()].clone();
-        entry.chars().for_each(|c| self.text_input.enter_char(c));
+        self.show_history_entry(self.history_index.unwrap());
     }
 
     /// Navigate forwards (newer) through the history
@@ -163,8 +222,6 @@ impl SearchInput {
         if self.history.is_empty() {
             return;
         }
-        self.text_input.value.clear();
-        self.text_input.reset_cursor();
 
         self.history_index = Some(match self.history_index {
             Some(i) if i < self.history.len() - 1 => i + 1,
@@ -172,18 +229,105 @@ impl SearchInput {
             _ => 0,
         });
 
-        let entry = self.history[self.history_index.unwrap()].clone();
-        entry.chars().for_each(|c| self.text_input.enter_char(c));
+        self.show_history_entry(self.history_index.unwrap());
     }
 
-    /// Saves the current input into the history (if not already present)
+    /// Saves the current input as most recent entry into the history
     pub fn submit(&mut self) {
-        if !self.text_input.value.trim().is_empty()
-            && !self.history.contains(&self.text_input.value)
+        self.history.push(self.text_input.value());
+        self.history_index = None;
+
+        if let Some(path) = &self.history_file
+            && let Err(err) = self.history.save(path)
         {
-            self.history.push(self.text_input.value.clone());
+            log::warn!(
+                "Failed to save the search history [{}]: {err}",
+                path.display()
+            );
         }
-        self.history_index = None;
+    }
+
+    /// Returns `true` while the reverse incremental search through the history is active
+    pub fn is_reverse_searching(&self) -> bool {
+        self.reverse_search.is_some()
+    }
+
+    fn start_reverse_search(&mut self) {
+        self.reverse_search = Some(ReverseSearch {
+            original_input: self.text_input.value().to_string(),
+            ..Default::default()
+        });
+    }
+
+    /// Searches the history for the next entry containing the search text and shows it in the input.
+    ///
+    /// # Arguments
+    /// * `older` - Skip the current match and continue with older entries (repeated `Ctrl+R`),
+    ///   otherwise the current match is kept, as long as it still contains the search text
+    fn update_reverse_search(&mut self, older: bool) {
+        let Some(search) = &mut self.reverse_search else {
+            return;
+        };
+
+        let before = match search.match_index {
+            Some(i) if older => i,
+            Some(i) => i + 1,
+            None => self.history.len(),
+        };
+        // keep the last match, if there is no older one
+        let Some(index) = self.history.rfind(&search.text, before) else {
+            search.is_failed = true;
+            return;
+        };
+        search.is_failed = false;
+        search.match_index = Some(index);
+        self.show_history_entry(index);
+    }
+
+    /// Handles a key while the reverse search is active.
+    ///
+    /// # Returns
+    /// `true` if the key was consumed by the reverse search.<br>
+    /// Any other key accepts the current match and should be handled like a normal key,
+    /// e.g. `Enter` submits the matched query and the arrow keys edit it.
+    pub fn handle_reverse_search_key(&mut self, key: crossterm::event::KeyEvent) -> bool {
+        let Some(search) = &mut self.reverse_search else {
+            return false;
+        };
+
+        match key.code {
+            crossterm::event::KeyCode::Char(c)
+                if key.modifiers == KeyModifiers::CONTROL && c.eq_ignore_ascii_case(&'r') =>
+            {
+                self.update_reverse_search(true);
+            }
+            crossterm::event::KeyCode::Char(c)
+                if key.modifiers.is_empty() || key.modifiers == KeyModifiers::SHIFT =>
+            {
+                search.text.push(c);
+                self.update_reverse_search(false);
+            }
+            crossterm::event::KeyCode::Backspace => {
+                search.text.pop();
+                // with a shorter text the most recent entry may match again
+                search.match_index = None;
+                self.update_reverse_search(false);
+            }
+            crossterm::event::KeyCode::Esc => {
+                // cancel the search and restore the previous input
+                let original_input = std::mem::take(&mut search.original_input);
+                self.reverse_search = None;
+                self.text_input.clear();
+                self.text_input.enter_string(&original_input);
+            }
+            _ => {
+                self.reverse_search = None;
+                self.history_index = None;
+                return false;
+            }
+        }
+
+        true
     }
 
     pub async fn handle_key_events(
@@ -206,6 +350,11 @@ impl SearchInput {
                         }
                     }
 
+                    // Ctrl + R →  reverse search through the history
+                    KeyModifiers::CONTROL if c.eq_ignore_ascii_case(&'r') => {
+                        self.start_reverse_search();
+                    }
+
                     // Allow printable characters with NONE / SHIFT / ALT / CTRL+ALT
                     modifiers
                         if modifiers.contains(KeyModifiers::SHIFT)
@@ -213,9 +362,7 @@ impl SearchInput {
                             || modifiers.contains(KeyModifiers::CONTROL | KeyModifiers::ALT)
                             || modifiers.is_empty() =>
                     {
-                        if !c.is_whitespace() {
-                            self.text_input.enter_char(c);
-                        }
+                        self.text_input.enter_char(c);
                     }
 
                     // Ignore everything else
@@ -259,8 +406,28 @@ impl SearchInput {
         // Store the actual available width so enter_char can enforce the limit
         self.text_input.set_width(area.width); // <- derived from provided draw area
 
-        let paragraph =
-            Paragraph::new(self.text_input.value()).style(Style::new().bg(bg).fg(text_fg));
+        // the reverse search shows the search text in front of the matched query, like a shell
+        let (text, cursor_index) = match &self.reverse_search {
+            Some(search) => {
+                let prefix = if search.is_failed {
+                    "(failed reverse-i-search)"
+                } else {
+                    "(reverse-i-search)"
+                };
+                let label = format!("{prefix}`{}'", search.text);
+                let cursor_index = label.chars().count() - 1;
+                (
+                    format!("{label}: {}", self.text_input.value()),
+                    cursor_index,
+                )
+            }
+            None => (
+                self.text_input.value().to_string(),
+                self.text_input.character_index,
+            ),
+        };
+
+        let paragraph = Paragraph::new(text).style(Style::new().bg(bg).fg(text_fg));
 
         let text_area = Rect {
             x: area.x + 1,
@@ -271,20 +438,33 @@ impl SearchInput {
         f.render_widget(paragraph, text_area);
 
         if show_cursor {
-            f.set_cursor_position(Position::new(
-                area.x + self.text_input.character_index as u16 + 1,
-                area.y,
-            ));
+            f.set_cursor_position(Position::new(area.x + cursor_index as u16 + 1, area.y));
         }
     }
 }
 
+/// The kind of value, that is edited with a [`SettingsInput`]
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+enum SettingsInputKind {
+    #[default]
+    Directory,
+    /// Comma separated exclude patterns
+    PatternList,
+    /// Comma separated directory paths
+    DirList,
+    /// A name that must not be empty, e.g. of a saved search
+    Name,
+    /// A follow-up query, that refines the search results
+    Query,
+}
+
 #[derive(Debug, Default)]
 pub struct SettingsInput {
     title: String,
     text_input: TextInput,
-    /// Indicates whether the directory path is valid or not
-    is_valid_path: bool,
+    kind: SettingsInputKind,
+    /// Indicates whether the directory path or the patterns are valid or not
+    is_valid_value: bool,
 }
 
 impl SettingsInput {
@@ -292,10 +472,53 @@ impl SettingsInput {
         Self {
             title: title.to_string(),
             text_input: TextInput::default(),
-            is_valid_path: true,
+            kind: SettingsInputKind::Directory,
+            is_valid_value: true,
         }
     }
 
+    /// Sets the input value to the comma separated patterns, the patterns are validated instead of a directory path
+    pub fn with_patterns(mut self, patterns: &[String]) -> Self {
+        self.kind = SettingsInputKind::PatternList;
+        self.text_input.allow_spaces = true;
+        self.text_input.set_width(u16::MAX); // temporarily set to max to allow entering the full string without truncation
+        self.text_input.enter_string(&patterns.join(", "));
+        self
+    }
+
+    /// Sets the input value to the comma separated directories, each directory path is validated
+    pub fn with_dirs(mut self, dirs: &[PathBuf]) -> Self {
+        self.kind = SettingsInputKind::DirList;
+        self.text_input.allow_spaces = true;
+        self.text_input.set_width(u16::MAX); // temporarily set to max to allow entering the full string without truncation
+        self.text_input.enter_string(
+            &dirs
+                .iter()
+                .map(utils::format_path_for_display)
+                .collect::<Vec<String>>()
+                .join(", "),
+        );
+        self
+    }
+
+    /// Sets the input value to the name, the name is only validated to be not empty
+    pub fn with_name(mut self, name: &str) -> Self {
+        self.kind = SettingsInputKind::Name;
+        self.text_input.allow_spaces = true;
+        self.text_input.set_width(u16::MAX); // temporarily set to max to allow entering the full string without truncation
+        self.text_input.enter_string(name);
+        self
+    }
+
+    /// Sets the input value to the query, the query is validated to contain a name or valid filters
+    pub fn with_query(mut self, query: &str) -> Self {
+        self.kind = SettingsInputKind::Query;
+        self.text_input.allow_spaces = true;
+        self.text_input.set_width(u16::MAX); // temporarily set to max to allow entering the full string without truncation
+        self.text_input.enter_string(query);
+        self
+    }
+
     /// Sets the input value to the given path string.
     /// The path is expanded and resolved to an absolute path before being set as the input value.
     pub fn with_value<P: AsRef<Path>>(mut self, v: P) -> Self {
@@ -310,6 +533,9 @@ impl SettingsInput {
     }
 
     fn handle_auto_completion(&mut self) {
+        if self.kind != SettingsInputKind::Directory {
+            return;
+        }
         if let Some(path) = utils::autocomplete_path(self.text_input.value()) {
             self.text_input.clear();
             self.text_input.enter_string(&path);
@@ -325,7 +551,7 @@ impl SettingsInput {
                 match key.modifiers {
                     // Ctrl + V  | Ctrl + Shift + V →  paste from clipboard
                     KeyModifiers::CONTROL if c.eq_ignore_ascii_case(&'v') => {
-                        self.is_valid_path = true; // Reset the valid path state on new input, to hide error message while validating the new path
+                        self.is_valid_value = true; // Reset the valid path state on new input, to hide error message while validating the new path
                         let content_to_paste = self.text_input.handle_paste()?;
                         if content_to_paste.is_empty() {
                             log::warn!("Settings-Input - {CLIPBOARD_EMPTY_MSG}");
@@ -345,8 +571,8 @@ impl SettingsInput {
                             || modifiers.contains(KeyModifiers::CONTROL | KeyModifiers::ALT)
                             || modifiers.is_empty() =>
                     {
-                        if !c.is_whitespace() {
-                            self.is_valid_path = true; // Reset the valid path state on new input, to hide error message while validating the new path
+                        if self.text_input.accepts(c) {
+                            self.is_valid_value = true; // Reset the valid path state on new input, to hide error message while validating the new path
                             self.text_input.enter_char(c);
                         }
                     }
@@ -356,9 +582,9 @@ impl SettingsInput {
                 }
             }
             crossterm::event::KeyCode::Enter => {
-                self.is_valid_path = self.is_valid_input();
+                self.is_valid_value = self.is_valid_input();
 
-                if self.is_valid_path {
+                if self.is_valid_value {
                     return Ok(Some(Action::ApplySettingsInput));
                 }
             }
@@ -419,13 +645,20 @@ impl SettingsInput {
 
         f.render_widget(paragraph, input_area);
 
-        if self.is_valid_path {
+        if self.is_valid_value {
             f.render_widget(Line::from(" ").bg(Color::default()), spacer_area_bottom);
         } else {
+            let error_msg = match self.kind {
+                SettingsInputKind::Directory => " Invalid path - No such directory ",
+                SettingsInputKind::PatternList => " Invalid pattern - Check the glob syntax ",
+                SettingsInputKind::DirList => {
+                    " Invalid path - One of the directories does not exist "
+                }
+                SettingsInputKind::Name => " Invalid name - The name must not be empty ",
+                SettingsInputKind::Query => " Invalid query - Check the filters ",
+            };
             f.render_widget(
-                Line::from(" Invalid path - No such directory ")
-                    .fg(Color::Red)
-                    .bg(Color::default()),
+                Line::from(error_msg).fg(Color::Red).bg(Color::default()),
                 spacer_area_bottom,
             );
         }
@@ -440,10 +673,26 @@ impl SettingsInput {
 
     /// Validates the current input value as a directory path. If the path starts with '~',
     /// it is expanded to the user's home directory before validation.
-    /// Returns `true` if the expanded path is a valid directory, otherwise returns `false`.
+    /// Returns `true` if the expanded path is a valid directory, otherwise returns `false`.<br>
+    /// A pattern list is valid, if all patterns are valid globs or absolute paths, a name must not be empty.
+    /// A query is valid, if it contains a name or only valid filters.
     fn is_valid_input(&self) -> bool {
-        let expanded_path = utils::expand_and_resolve_path(self.text_input.value());
-        PathBuf::from(expanded_path).is_dir()
+        match self.kind {
+            SettingsInputKind::Directory => {
+                let expanded_path = utils::expand_and_resolve_path(self.text_input.value());
+                PathBuf::from(expanded_path).is_dir()
+            }
+            SettingsInputKind::PatternList => {
+                ExcludeFilter::new(&parse_exclude_patterns(self.text_input.value())).is_ok()
+            }
+            SettingsInputKind::DirList => parse_dir_list(self.text_input.value())
+                .iter()
+                .all(|dir| dir.is_dir()),
+            SettingsInputKind::Name => !self.text_input.is_empty(),
+            SettingsInputKind::Query => {
+                Refinement::new(self.text_input.value(), QueryMode::Plain, false).is_ok()
+            }
+        }
     }
 
     fn help_text() -> ratatui::prelude::Line<'static> {
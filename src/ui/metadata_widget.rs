@@ -1,14 +1,18 @@
 #![allow(dead_code)]
 use anyhow::Result;
 use async_trait::async_trait;
+use crossterm::event::KeyModifiers;
 use ratatui::{prelude::*, style::palette::tailwind, widgets::*};
+use std::path::PathBuf;
 
 use crate::{
     app::{AppState, actions::Action},
     component::Component,
+    file_handling::checksum::{Checksum, ChecksumAlgorithm},
     models::{Scrollable, StatefulTable},
     tui::Event,
     ui::{HIGHLIGHT_SYMBOL, PALETTES, Theme, centered_rect},
+    utils,
 };
 
 #[derive(Debug)]
@@ -40,6 +44,8 @@ impl TableColors {
 pub struct MetadataPage {
     /// Action sender that can send actions to all other components
     action_sender: Option<tokio::sync::mpsc::UnboundedSender<Action>>,
+    /// Associated Explorer operation sender, that can send actions to the [`crate::file_handling::Explorer`]
+    explorer_action_sender: Option<tokio::sync::mpsc::Sender<Action>>,
     theme: Theme,
     border_style: Style,
     border_type: BorderType,
@@ -49,6 +55,14 @@ pub struct MetadataPage {
     object_name: String,
     colors: TableColors,
     is_active: bool,
+    /// Path of the displayed file, checksums are only available for files
+    file_path: Option<PathBuf>,
+    checksum_algorithm: ChecksumAlgorithm,
+    checksum: Option<Checksum>,
+    /// Processed and total bytes, while a checksum is computed
+    checksum_progress: Option<(u64, u64)>,
+    /// Hash pasted from the clipboard, to verify the file against
+    pasted_hash: Option<String>,
 }
 
 impl MetadataPage {
@@ -70,6 +84,187 @@ impl MetadataPage {
         ])
     }
 
+    /// Helper function to send a [`Action`] to the [`crate::file_handling::Explorer`]
+    async fn send_explorer_action(&self, action: Action) -> Result<()> {
+        if let Some(sender) = &self.explorer_action_sender {
+            sender.send(action).await?;
+        }
+        Ok(())
+    }
+
+    fn reset_checksum(&mut self) {
+        self.checksum = None;
+        self.checksum_progress = None;
+        self.pasted_hash = None;
+    }
+
+    /// Cancels a running checksum computation, if any
+    async fn cancel_checksum(&mut self) -> Result<()> {
+        if self.checksum_progress.take().is_some() {
+            self.send_explorer_action(Action::CancelChecksum).await?;
+        }
+        Ok(())
+    }
+
+    /// Handles the checksum related keys, only available for files
+    async fn handle_checksum_key_events(
+        &mut self,
+        key: crossterm::event::KeyEvent,
+        file_path: PathBuf,
+    ) -> Result<Option<Action>> {
+        match key.code {
+            crossterm::event::KeyCode::Enter if self.checksum_progress.is_none() => {
+                self.checksum = None;
+                self.checksum_progress = Some((0, 0));
+                self.send_explorer_action(Action::ComputeChecksum(
+                    file_path,
+                    self.checksum_algorithm,
+                ))
+                .await?;
+                return Ok(Action::UpdateAppState(AppState::Working(format!(
+                    "Computing {} checksum...",
+                    self.checksum_algorithm
+                )))
+                .into());
+            }
+            crossterm::event::KeyCode::Tab => {
+                self.cancel_checksum().await?;
+                self.checksum = None;
+                self.checksum_algorithm = self.checksum_algorithm.next();
+                return Ok(Action::UpdateAppState(AppState::done_empty()).into());
+            }
+            crossterm::event::KeyCode::Char(c)
+                if key.modifiers == KeyModifiers::CONTROL && c.eq_ignore_ascii_case(&'c') =>
+            {
+                let Some(checksum) = &self.checksum else {
+                    return Ok(Action::UpdateAppState(AppState::Failure(
+                        "No checksum computed yet".to_string(),
+                    ))
+                    .into());
+                };
+                match utils::copy_to_clipboard(&checksum.digest) {
+                    Ok(_) => {
+                        return Ok(Action::UpdateAppState(AppState::Done(
+                            "Checksum copied".to_string(),
+                        ))
+                        .into());
+                    }
+                    Err(err) => {
+                        log::error!("{:?}", err);
+                        return Ok(Action::UpdateAppState(AppState::Failure(
+                            "Failed to copy checksum to clipboard".to_string(),
+                        ))
+                        .into());
+                    }
+                }
+            }
+            crossterm::event::KeyCode::Char(c)
+                if key.modifiers == KeyModifiers::CONTROL && c.eq_ignore_ascii_case(&'v') =>
+            {
+                let pasted = utils::paste_from_clipboard()?;
+                let pasted = pasted.trim();
+                if pasted.is_empty() {
+                    return Ok(Action::UpdateAppState(AppState::Failure(
+                        "Clipboard is empty, nothing to verify".to_string(),
+                    ))
+                    .into());
+                }
+                self.pasted_hash = Some(pasted.to_string());
+            }
+            _ => {}
+        }
+        Ok(None)
+    }
+
+    /// Builds the lines of the verification result, if there is anything to verify
+    fn verification_lines(&self, checksum: &Checksum) -> Vec<Line<'static>> {
+        let mut lines = vec![];
+        let result_span = |matches: bool| {
+            if matches {
+                Span::styled("✔ matches ", Style::new().fg(Color::LightGreen))
+            } else {
+                Span::styled("✘ does NOT match ", Style::new().fg(Color::LightRed))
+            }
+        };
+
+        if let Some(pasted_hash) = &self.pasted_hash {
+            lines.push(Line::from(vec![
+                result_span(checksum.matches(pasted_hash)),
+                Span::raw("the pasted hash"),
+            ]));
+        }
+        if let Some(sibling) = &checksum.sibling {
+            lines.push(Line::from(vec![
+                result_span(checksum.matches(&sibling.hash)),
+                Span::raw(
+                    sibling
+                        .path
+                        .file_name()
+                        .map(|name| name.to_string_lossy().to_string())
+                        .unwrap_or_default(),
+                ),
+            ]));
+        }
+        lines
+    }
+
+    fn render_checksum(&self, f: &mut ratatui::Frame<'_>, area: Rect) {
+        let block = Block::new()
+            .title(Line::from(vec![
+                Span::raw(format!(" Checksum [{}] | ", self.checksum_algorithm)),
+                Span::styled("<Enter> ", Style::default().fg(Color::Yellow)),
+                Span::raw("Compute "),
+                Span::styled(" <Tab> ", Style::default().fg(Color::Yellow)),
+                Span::raw("Algorithm "),
+                Span::styled(" <Ctrl+C> ", Style::default().fg(Color::Yellow)),
+                Span::raw("Copy "),
+                Span::styled(" <Ctrl+V> ", Style::default().fg(Color::Yellow)),
+                Span::raw("Verify pasted hash "),
+            ]))
+            .border_type(self.border_type)
+            .borders(Borders::ALL)
+            .border_style(self.border_style)
+            .padding(Padding::horizontal(1))
+            .bg(self.colors.buffer_bg);
+
+        let [digest_area, verification_area] =
+            Layout::vertical([Constraint::Length(1), Constraint::Fill(1)]).areas(block.inner(area));
+
+        f.render_widget(block, area);
+
+        if let Some((processed, total)) = self.checksum_progress {
+            let ratio = if total == 0 {
+                0.0
+            } else {
+                (processed as f64 / total as f64).clamp(0.0, 1.0)
+            };
+            let gauge = Gauge::default()
+                .gauge_style(Style::new().fg(self.colors.selected_style_fg))
+                .label(format!(
+                    "{} / {}",
+                    utils::convert_bytes_to_human_readable(processed),
+                    utils::convert_bytes_to_human_readable(total)
+                ))
+                .ratio(ratio);
+            f.render_widget(gauge, digest_area);
+        } else if let Some(checksum) = &self.checksum {
+            f.render_widget(
+                Paragraph::new(checksum.digest.as_str()).style(Style::new().fg(self.colors.row_fg)),
+                digest_area,
+            );
+            f.render_widget(
+                Paragraph::new(self.verification_lines(checksum)),
+                verification_area,
+            );
+        } else {
+            f.render_widget(
+                Paragraph::new("Press <Enter> to compute the checksum")
+                    .style(Style::new().fg(self.colors.row_fg)),
+                digest_area,
+            );
+        }
+    }
+
     fn block_title() -> ratatui::prelude::Line<'static> {
         Line::from(vec![
             Span::raw(" Metadata | "),
@@ -83,6 +278,7 @@ impl Default for MetadataPage {
     fn default() -> Self {
         Self {
             action_sender: Default::default(),
+            explorer_action_sender: Default::default(),
             theme: Default::default(),
             border_style: Style::new().bold().fg(Color::LightGreen),
             border_type: BorderType::Rounded,
@@ -92,6 +288,11 @@ impl Default for MetadataPage {
             object_name: Default::default(),
             colors: TableColors::new(&PALETTES[0]),
             is_active: Default::default(),
+            file_path: Default::default(),
+            checksum_algorithm: Default::default(),
+            checksum: Default::default(),
+            checksum_progress: Default::default(),
+            pasted_hash: Default::default(),
         }
     }
 }
@@ -106,6 +307,14 @@ impl Component for MetadataPage {
         Ok(())
     }
 
+    fn register_explorer_action_sender(
+        &mut self,
+        tx: tokio::sync::mpsc::Sender<Action>,
+    ) -> Result<()> {
+        self.explorer_action_sender = Some(tx);
+        Ok(())
+    }
+
     async fn handle_events(&mut self, event: Option<crate::tui::Event>) -> Result<Option<Action>> {
         if let Some(event) = event {
             match event {
@@ -141,10 +350,14 @@ impl Component for MetadataPage {
                 Ok(None)
             }
             crossterm::event::KeyCode::Esc => {
+                self.cancel_checksum().await?;
                 self.is_active = false;
                 Ok(Action::CloseMetadata.into())
             }
-            _ => Ok(None),
+            _ => match self.file_path.clone() {
+                Some(file_path) => self.handle_checksum_key_events(key, file_path).await,
+                None => Ok(None),
+            },
         }
     }
 
@@ -169,6 +382,8 @@ impl Component for MetadataPage {
                     .set_items(metadata.get_metadata_rows(file_path));
                 self.metadata.state.select(Some(0));
                 self.scrollbar_state = ScrollbarState::new(self.metadata.items.len()).position(0);
+                self.file_path = Some(file_path.clone());
+                self.reset_checksum();
                 self.is_active = true;
             }
             Action::ShowDirMetadata(metadata) => {
@@ -177,8 +392,23 @@ impl Component for MetadataPage {
                 self.metadata.state.select(Some(0));
                 self.object_name = metadata.dir_name.clone();
                 self.scrollbar_state = ScrollbarState::new(self.metadata.items.len()).position(0);
+                self.file_path = None;
+                self.reset_checksum();
                 self.is_active = true;
             }
+            // ignore late progress updates of a cancelled computation
+            Action::ChecksumProgress(processed, total) if self.checksum_progress.is_some() => {
+                self.checksum_progress = Some((*processed, *total));
+            }
+            Action::ChecksumDone(checksum) => {
+                self.checksum_progress = None;
+                self.checksum = checksum.clone();
+                self.send_app_action(Action::UpdateAppState(AppState::Done("Done".to_string())))?;
+            }
+            Action::ChecksumFailure(msg) => {
+                self.checksum_progress = None;
+                self.send_app_action(Action::UpdateAppState(AppState::Failure(msg.clone())))?;
+            }
             _ => {}
         }
         Ok(None)
@@ -186,7 +416,12 @@ impl Component for MetadataPage {
 
     fn render(&mut self, f: &mut ratatui::Frame<'_>, area: Rect) -> Result<()> {
         if self.should_render() {
-            let draw_area = centered_rect(60, 50, area);
+            let popup_area = centered_rect(60, 50, area);
+            let [draw_area, checksum_area] = Layout::vertical([
+                Constraint::Fill(1),
+                Constraint::Length(if self.file_path.is_some() { 5 } else { 0 }),
+            ])
+            .areas(popup_area);
             let block = Block::new()
                 .title_bottom(Span::styled(
                     format!(" {} ", self.object_name),
@@ -222,7 +457,7 @@ impl Component for MetadataPage {
             let table_widths = [Constraint::Percentage(35), Constraint::Percentage(70)];
 
             // clear/reset a certain area to allow overdrawing (e.g. for popups).
-            f.render_widget(Clear, draw_area);
+            f.render_widget(Clear, popup_area);
 
             if draw_area.height < rows_counter as u16 {
                 let metadata_page_table = Table::new(rows, table_widths)
@@ -259,6 +494,10 @@ impl Component for MetadataPage {
                 self.metadata.state.select(Some(0));
                 f.render_stateful_widget(metadata_page_table, draw_area, &mut self.metadata.state);
             }
+
+            if self.file_path.is_some() {
+                self.render_checksum(f, checksum_area);
+            }
         }
 
         Ok(())
@@ -1,14 +1,22 @@
 use ratatui::{prelude::*, style::palette::tailwind};
 use serde::{Deserialize, Serialize};
 
+use crate::file_handling::{git::GitStatus, search::NameMatcher};
+
 pub mod about_widget;
+pub mod bulk_menu;
+pub mod diff_widget;
+pub mod dir_compare_widget;
+pub mod disk_usage_widget;
 pub mod dropdown;
+pub mod error_report;
 pub mod explorer_widget;
 pub mod footer_widget;
 pub mod help_widget;
 pub mod info_widget;
 pub mod input;
 pub mod metadata_widget;
+pub mod preset_picker;
 pub mod result_widget;
 pub mod search_widget;
 pub mod settings_widget;
@@ -46,6 +54,13 @@ pub struct ThemeColor {
     pub done_state_color: Color,
     pub failure_state_color: Color,
     pub working_state_color: Color,
+    pub diff_insert_bg: Color,
+    pub diff_delete_bg: Color,
+    pub git_ignored_color: Color,
+    pub git_untracked_color: Color,
+    pub git_staged_color: Color,
+    pub git_modified_color: Color,
+    pub git_conflicted_color: Color,
 }
 
 #[derive(Debug, Default, Clone, Copy, Eq, PartialEq, Hash)]
@@ -182,6 +197,13 @@ impl Theme {
                 done_state_color: Color::Blue,
                 failure_state_color: tailwind::RED.c700,
                 working_state_color: Color::Blue,
+                diff_insert_bg: tailwind::GREEN.c200,
+                diff_delete_bg: tailwind::RED.c200,
+                git_ignored_color: tailwind::GRAY.c600,
+                git_untracked_color: tailwind::SKY.c800,
+                git_staged_color: tailwind::GREEN.c800,
+                git_modified_color: tailwind::AMBER.c800,
+                git_conflicted_color: tailwind::RED.c700,
             },
             Theme::Dark => ThemeColor {
                 main_bg: tailwind::SLATE.c800,
@@ -200,6 +222,13 @@ impl Theme {
                 done_state_color: Color::LightCyan,
                 failure_state_color: tailwind::RED.c500,
                 working_state_color: Color::LightCyan,
+                diff_insert_bg: tailwind::GREEN.c950,
+                diff_delete_bg: tailwind::RED.c950,
+                git_ignored_color: tailwind::GRAY.c500,
+                git_untracked_color: tailwind::SKY.c400,
+                git_staged_color: tailwind::GREEN.c500,
+                git_modified_color: tailwind::YELLOW.c400,
+                git_conflicted_color: tailwind::RED.c500,
             },
             Theme::Dracula => ThemeColor {
                 main_bg: tailwind::SLATE.c900,
@@ -218,6 +247,13 @@ impl Theme {
                 done_state_color: tailwind::CYAN.c300,
                 failure_state_color: tailwind::RED.c500,
                 working_state_color: tailwind::CYAN.c300,
+                diff_insert_bg: tailwind::EMERALD.c950,
+                diff_delete_bg: tailwind::ROSE.c950,
+                git_ignored_color: tailwind::GRAY.c500,
+                git_untracked_color: tailwind::SKY.c400,
+                git_staged_color: tailwind::GREEN.c500,
+                git_modified_color: tailwind::YELLOW.c400,
+                git_conflicted_color: tailwind::RED.c500,
             },
             Theme::Indigo => ThemeColor {
                 main_bg: tailwind::INDIGO.c600,
@@ -236,6 +272,13 @@ impl Theme {
                 done_state_color: tailwind::WHITE,
                 failure_state_color: tailwind::RED.c700,
                 working_state_color: tailwind::WHITE,
+                diff_insert_bg: tailwind::GREEN.c800,
+                diff_delete_bg: tailwind::RED.c800,
+                git_ignored_color: tailwind::GRAY.c400,
+                git_untracked_color: tailwind::SKY.c300,
+                git_staged_color: tailwind::GREEN.c400,
+                git_modified_color: tailwind::YELLOW.c300,
+                git_conflicted_color: tailwind::RED.c400,
             },
         }
     }
@@ -251,15 +294,15 @@ impl Theme {
     }
 }
 
-/// Highlights occurrences of a specified substring in a given text while ignoring case sensitivity.
+/// Highlights the parts of a given text that are matched by the search query.
 ///
-/// This function searches for all occurrences of `highlight` in `text`, applying a specified
-/// `highlight_color` to the background of the matches while keeping the rest of the text in `default_color`.
+/// This function applies a specified `highlight_color` to the background of all match spans
+/// of the `matcher`, while keeping the rest of the text in `default_color`.
 ///
 /// # Arguments
 ///
 /// * `text` - The input string where highlighting is applied.
-/// * `highlight` - The substring to search for, case-insensitively.
+/// * `matcher` - The compiled search query, if `None` nothing is highlighted. A fuzzy query highlights the individual matched characters.
 /// * `highlight_color` - The color applied to highlighted portions.
 /// * `default_color` - The color applied to non-highlighted portions.
 ///
@@ -268,39 +311,35 @@ impl Theme {
 /// A `Vec<Span>` containing the input text divided into segments, with highlighted portions styled accordingly.
 pub fn highlight_text_part(
     text: String,
-    highlight: &str,
+    matcher: Option<&NameMatcher>,
     highlight_color: Color,
     default_color: Color,
-) -> Vec<Span<'_>> {
-    let mut spans = Vec::new();
+) -> Vec<Span<'static>> {
+    let ranges = matcher
+        .map(|matcher| matcher.match_ranges(&text))
+        .unwrap_or_default();
 
-    // Prevent infinite loop by returning the full string unchanged if highlight is empty
-    if highlight.trim().is_empty() {
-        return vec![Span::from(text.clone()).fg(default_color)];
+    if ranges.is_empty() {
+        return vec![Span::from(text).fg(default_color)];
     }
 
-    let lower_text = text.to_lowercase();
-    let lower_highlight = highlight.to_lowercase();
+    let mut spans = Vec::new();
     let mut start = 0;
 
-    while let Some(pos) = lower_text[start..].find(&lower_highlight) {
-        let pos = start + pos;
-
+    for range in ranges {
         // Push the text before the highlight if it exists
-        if pos > start {
-            spans.push(Span::from(text[start..pos].to_string()).fg(default_color));
+        if range.start > start {
+            spans.push(Span::from(text[start..range.start].to_string()).fg(default_color));
         }
 
-        // Get the text that should be highlighted
-        let highlighted_part = &text[pos..pos + highlight.len()];
         spans.push(
-            Span::from(highlighted_part.to_string())
+            Span::from(text[range.clone()].to_string())
                 .fg(default_color)
                 .bg(highlight_color),
         );
 
         // Move to the remaining part after the highlighted section
-        start = pos + highlight.len();
+        start = range.end;
     }
 
     // Push the remaining text if there is any left
@@ -311,9 +350,28 @@ pub fn highlight_text_part(
     spans
 }
 
+/// Returns a span with the marker of the given git status, colored by its meaning in the current theme.<br>
+/// If there is no status, a blank span with the same width is returned to keep the entries aligned.
+pub fn git_status_span(status: Option<GitStatus>, theme_colors: &ThemeColor) -> Span<'static> {
+    match status {
+        Some(status) => {
+            let color = match status {
+                GitStatus::Ignored => theme_colors.git_ignored_color,
+                GitStatus::Untracked => theme_colors.git_untracked_color,
+                GitStatus::Staged => theme_colors.git_staged_color,
+                GitStatus::Modified => theme_colors.git_modified_color,
+                GitStatus::Conflicted => theme_colors.git_conflicted_color,
+            };
+            Span::from(format!("{} ", status.marker())).fg(color).bold()
+        }
+        None => Span::from("  "),
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::highlight_text_part;
+    use crate::file_handling::search::{NameMatcher, QueryMode};
     use ratatui::{
         style::{Color, Stylize},
         text::Span,
@@ -323,7 +381,9 @@ mod tests {
     fn test_basic_highlight_1() {
         let filename = "important_document.txt";
         let highlight = "important";
-        let result = highlight_text_part(filename.into(), highlight, Color::Cyan, Color::White);
+        let matcher = NameMatcher::new(highlight, QueryMode::Plain, false).unwrap();
+        let result =
+            highlight_text_part(filename.into(), Some(&matcher), Color::Cyan, Color::White);
         assert_eq!(
             result,
             vec![
@@ -337,7 +397,9 @@ mod tests {
     fn test_basic_highlight_2() {
         let filename = "test_my_doc.rs";
         let highlight = "my";
-        let result = highlight_text_part(filename.into(), highlight, Color::Cyan, Color::White);
+        let matcher = NameMatcher::new(highlight, QueryMode::Plain, false).unwrap();
+        let result =
+            highlight_text_part(filename.into(), Some(&matcher), Color::Cyan, Color::White);
         assert_eq!(
             result,
             vec![
@@ -352,7 +414,9 @@ mod tests {
     fn test_basic_highlight_3() {
         let filename = "test_rust_file.rs";
         let highlight = ".rs";
-        let result = highlight_text_part(filename.into(), highlight, Color::Cyan, Color::White);
+        let matcher = NameMatcher::new(highlight, QueryMode::Plain, false).unwrap();
+        let result =
+            highlight_text_part(filename.into(), Some(&matcher), Color::Cyan, Color::White);
         assert_eq!(
             result,
             vec![
@@ -366,7 +430,9 @@ mod tests {
     fn test_multiple_highlight() {
         let filename = "test_my_doc_test.rs";
         let highlight = "test";
-        let result = highlight_text_part(filename.into(), highlight, Color::Cyan, Color::White);
+        let matcher = NameMatcher::new(highlight, QueryMode::Plain, false).unwrap();
+        let result =
+            highlight_text_part(filename.into(), Some(&matcher), Color::Cyan, Color::White);
         assert_eq!(
             result,
             vec![
@@ -382,7 +448,9 @@ mod tests {
     fn test_empty_highlight_given_1() {
         let filename = "document_important.pdf";
         let highlight = "";
-        let result = highlight_text_part(filename.into(), highlight, Color::Cyan, Color::White);
+        let matcher = NameMatcher::new(highlight, QueryMode::Plain, false).unwrap();
+        let result =
+            highlight_text_part(filename.into(), Some(&matcher), Color::Cyan, Color::White);
         assert_eq!(result, vec![Span::from("document_important.pdf").white()])
     }
 
@@ -390,7 +458,9 @@ mod tests {
     fn test_empty_highlight_given_2() {
         let filename = "document_important.pdf";
         let highlight = "\t      ";
-        let result = highlight_text_part(filename.into(), highlight, Color::Cyan, Color::White);
+        let matcher = NameMatcher::new(highlight, QueryMode::Plain, false).unwrap();
+        let result =
+            highlight_text_part(filename.into(), Some(&matcher), Color::Cyan, Color::White);
         assert_eq!(result, vec![Span::from("document_important.pdf").white()])
     }
 
@@ -398,7 +468,9 @@ mod tests {
     fn test_highlight_uppercase() {
         let filename = "document_important.pdf";
         let highlight = "POrTanT";
-        let result = highlight_text_part(filename.into(), highlight, Color::Cyan, Color::White);
+        let matcher = NameMatcher::new(highlight, QueryMode::Plain, false).unwrap();
+        let result =
+            highlight_text_part(filename.into(), Some(&matcher), Color::Cyan, Color::White);
         assert_eq!(
             result,
             vec![
@@ -413,7 +485,9 @@ mod tests {
     fn test_highlight_lowercase() {
         let filename = "DOCUMENT.PDF";
         let highlight = ".pdf";
-        let result = highlight_text_part(filename.into(), highlight, Color::Cyan, Color::White);
+        let matcher = NameMatcher::new(highlight, QueryMode::Plain, false).unwrap();
+        let result =
+            highlight_text_part(filename.into(), Some(&matcher), Color::Cyan, Color::White);
         assert_eq!(
             result,
             vec![
@@ -422,4 +496,45 @@ mod tests {
             ]
         )
     }
+
+    #[test]
+    fn test_highlight_regex_match_span() {
+        let filename = "report_2024_final.pdf";
+        let matcher = NameMatcher::new(r"\d{4}_f", QueryMode::Regex, false).unwrap();
+        let result =
+            highlight_text_part(filename.into(), Some(&matcher), Color::Cyan, Color::White);
+        assert_eq!(
+            result,
+            vec![
+                Span::from("report_").white(),
+                Span::from("2024_f").white().on_cyan(),
+                Span::from("inal.pdf").white()
+            ]
+        )
+    }
+
+    #[test]
+    fn test_highlight_fuzzy_match_characters() {
+        let filename = "search_widget.rs";
+        let matcher = NameMatcher::new("swrs", QueryMode::Fuzzy, false).unwrap();
+        let result =
+            highlight_text_part(filename.into(), Some(&matcher), Color::Cyan, Color::White);
+        assert_eq!(
+            result,
+            vec![
+                Span::from("s").white().on_cyan(),
+                Span::from("earch_").white(),
+                Span::from("w").white().on_cyan(),
+                Span::from("idget.").white(),
+                Span::from("rs").white().on_cyan(),
+            ]
+        )
+    }
+
+    #[test]
+    fn test_highlight_without_matcher() {
+        let filename = "report.pdf";
+        let result = highlight_text_part(filename.into(), None, Color::Cyan, Color::White);
+        assert_eq!(result, vec![Span::from("report.pdf").white()])
+    }
 }
@@ -0,0 +1,111 @@
+use ratatui::{prelude::*, widgets::*};
+
+use crate::{
+    app::presets::SearchPreset,
+    ui::{HIGHLIGHT_SYMBOL, ThemeColor, centered_rect},
+};
+
+/// Popup on the Search page, that lists the saved searches to re-run one of them
+#[derive(Debug, Default)]
+pub struct PresetPicker {
+    presets: Vec<SearchPreset>,
+    list_state: ListState,
+    is_visible: bool,
+}
+
+impl PresetPicker {
+    /// Shows the picker with the given presets, the first preset is selected
+    pub fn open(&mut self, presets: Vec<SearchPreset>) {
+        self.list_state.select((!presets.is_empty()).then_some(0));
+        self.presets = presets;
+        self.is_visible = true;
+    }
+
+    pub fn close(&mut self) {
+        self.is_visible = false;
+    }
+
+    pub fn is_visible(&self) -> bool {
+        self.is_visible
+    }
+
+    pub fn selected(&self) -> Option<&SearchPreset> {
+        self.list_state
+            .selected()
+            .and_then(|index| self.presets.get(index))
+    }
+
+    pub fn next(&mut self) {
+        if !self.presets.is_empty() {
+            let next = self
+                .list_state
+                .selected()
+                .map_or(0, |i| (i + 1) % self.presets.len());
+            self.list_state.select(Some(next));
+        }
+    }
+
+    pub fn previous(&mut self) {
+        if !self.presets.is_empty() {
+            let previous = self
+                .list_state
+                .selected()
+                .map_or(0, |i| i.checked_sub(1).unwrap_or(self.presets.len() - 1));
+            self.list_state.select(Some(previous));
+        }
+    }
+
+    pub fn render(&mut self, f: &mut Frame, area: Rect, theme_colors: &ThemeColor) {
+        let block = Block::default()
+            .title_top(" Saved searches ")
+            .title_bottom(Line::from(vec![
+                " <Enter>".fg(theme_colors.main_text_fg),
+                " Run ".fg(theme_colors.main_fg),
+                "|".fg(theme_colors.main_fg),
+                " <Tab>".fg(theme_colors.main_text_fg),
+                " Edit ".fg(theme_colors.main_fg),
+                "|".fg(theme_colors.main_fg),
+                " <Esc>".fg(theme_colors.main_text_fg),
+                " Close ".fg(theme_colors.main_fg),
+            ]))
+            .title_alignment(Alignment::Center)
+            .borders(Borders::ALL)
+            .border_type(BorderType::Rounded)
+            .border_style(Style::new().bold().fg(theme_colors.alt_fg))
+            .style(Style::new().bg(theme_colors.alt_bg));
+
+        let items: Vec<ListItem> = self
+            .presets
+            .iter()
+            .map(|preset| {
+                let root = if preset.root.trim().is_empty() {
+                    "current directory"
+                } else {
+                    preset.root.trim()
+                };
+                ListItem::new(Line::from(vec![
+                    Span::styled(
+                        format!("{} ", preset.name),
+                        Style::new().fg(theme_colors.main_text_fg).bold(),
+                    ),
+                    Span::styled(
+                        format!(
+                            " {} [{}, {}] in {root}",
+                            preset.query, preset.search_type, preset.mode
+                        ),
+                        Style::new().fg(theme_colors.main_fg),
+                    ),
+                ]))
+            })
+            .collect();
+
+        let list = List::new(items)
+            .block(block)
+            .highlight_style(Style::new().fg(theme_colors.selected_color))
+            .highlight_symbol(HIGHLIGHT_SYMBOL);
+
+        let popup_area = centered_rect(70, 60, area);
+        f.render_widget(Clear, popup_area);
+        f.render_stateful_widget(list, popup_area, &mut self.list_state);
+    }
+}
@@ -1,3 +1,4 @@
+use std::collections::HashSet;
 use std::io::{BufWriter, Write};
 use std::path::PathBuf;
 
@@ -10,11 +11,22 @@ use tokio_util::sync::CancellationToken;
 use crate::{
     app::{AppContext, AppState, actions::Action, config::AppConfig, key_bindings},
     component::Component,
-    file_handling::SearchResult,
+    file_handling::{
+        SearchResult,
+        bulk::{BulkJob, BulkOperation, BulkReport},
+        duplicates::DuplicateResolution,
+        git::GitInfo,
+        index::format_age,
+        refine::ResultHistory,
+        result_view::{DirHeader, ResultOrder, ResultRow, SortKey},
+        search::{NameMatcher, SearchType},
+    },
     models::Scrollable,
     tui::Event,
     ui::{
-        HIGHLIGHT_SYMBOL, Theme, get_main_layout, highlight_text_part, search_widget::SearchMode,
+        HIGHLIGHT_SYMBOL, Theme, ThemeColor, bulk_menu::BulkMenu, centered_rect_fixed_height,
+        error_report::ErrorReport, get_main_layout, git_status_span, highlight_text_part,
+        input::SettingsInput, search_widget::SearchMode,
     },
     utils,
 };
@@ -179,6 +191,8 @@ pub struct ResultWidget {
     /// If the [`crate::ui::info_widget::SystemOverview`] is not visible, than use the whole draw area
     use_whole_draw_area: bool,
     search_result: SearchResult,
+    /// Compiled search query, used to highlight the matched parts of the names
+    name_matcher: Option<NameMatcher>,
     /// Terminal height used to control the number of items to display on the screen
     terminal_height: u16,
     /// Page height used to control the PageUp and PageDown operations
@@ -186,6 +200,8 @@ pub struct ResultWidget {
     /// Flag to control the receiving of the key events for the search widget
     /// If the widget is working, then incoming key events are ignored
     is_working: bool,
+    /// Flag that indicates that the metadata of the selected directory is calculated, which can be cancelled with `Esc`
+    is_calculating_metadata: bool,
     /// Indicates if the Metadata PopUp widget is showing
     is_metadata_pop_up: bool,
     theme: Theme,
@@ -196,6 +212,26 @@ pub struct ResultWidget {
     // Directory in which the search results should be exported
     export_dir: PathBuf,
     follow_sym_links: bool,
+    /// Git status of the work tree that contains the search directory, if any
+    git_info: Option<GitInfo>,
+    /// Resolution of the selected duplicate group, that is waiting for the user confirmation
+    pending_resolution: Option<DuplicateResolution>,
+    /// Input of the follow-up query, that refines the shown result
+    refine_input: Option<SettingsInput>,
+    /// The broader results left by refining and the narrower results left by going back
+    result_history: ResultHistory,
+    /// Entries marked for a bulk operation, kept while the result is refined
+    marked_paths: HashSet<PathBuf>,
+    /// Lists the bulk operations for the marked entries
+    bulk_menu: BulkMenu,
+    /// Input of the destination directory of a copy, move or archive
+    bulk_target_input: Option<(BulkOperation, SettingsInput)>,
+    /// Bulk operation, that is waiting for the user confirmation
+    pending_bulk_job: Option<BulkJob>,
+    /// Flag that indicates that a bulk operation is running, which can be cancelled with `Esc`
+    is_running_bulk_job: bool,
+    /// Lists the entries, the last bulk operation failed for
+    error_report: ErrorReport,
 }
 
 impl Default for ResultWidget {
@@ -207,9 +243,11 @@ impl Default for ResultWidget {
             explorer_action_sender: Default::default(),
             use_whole_draw_area: Default::default(),
             search_result: Default::default(),
+            name_matcher: Default::default(),
             terminal_height: Default::default(),
             page_height: Default::default(),
             is_working: Default::default(),
+            is_calculating_metadata: Default::default(),
             is_metadata_pop_up: Default::default(),
             theme: Default::default(),
             applied_search_mode: Default::default(),
@@ -218,6 +256,16 @@ impl Default for ResultWidget {
             export_task: Default::default(),
             export_dir: Default::default(),
             follow_sym_links: Default::default(),
+            git_info: Default::default(),
+            pending_resolution: Default::default(),
+            refine_input: Default::default(),
+            result_history: Default::default(),
+            marked_paths: Default::default(),
+            bulk_menu: Default::default(),
+            bulk_target_input: Default::default(),
+            pending_bulk_job: Default::default(),
+            is_running_bulk_job: Default::default(),
+            error_report: Default::default(),
         }
     }
 }
@@ -233,6 +281,14 @@ impl ResultWidget {
         Ok(())
     }
 
+    /// Cancels the running metadata calculation, the Explorer reports the cancellation in the footer
+    async fn cancel_dir_metadata(&mut self) -> Result<Option<Action>> {
+        self.send_explorer_action(Action::CancelDirMetadata).await?;
+        self.is_working = false;
+        self.is_calculating_metadata = false;
+        Ok(None)
+    }
+
     /// Helper function to send a [`Action`] to all components
     fn send_app_action(&self, action: Action) -> Result<()> {
         if let Some(handler) = &self.action_sender {
@@ -241,13 +297,512 @@ impl ResultWidget {
         Ok(())
     }
 
+    /// Returns the selected file to keep and the other files of its duplicate group
+    fn selected_duplicates(&self) -> Option<(PathBuf, Vec<PathBuf>)> {
+        let selected = self.search_result.selected_index()?;
+        let (_, group) = self.search_result.duplicate_group_of(selected)?;
+        let items = self.search_result.items();
+        let others = items[group.range()]
+            .iter()
+            .enumerate()
+            .filter(|(i, _)| group.start + i != selected)
+            .map(|(_, entry)| entry.path.clone())
+            .collect();
+
+        Some((items[selected].path.clone(), others))
+    }
+
+    /// Handles the key events of the confirmation dialog to resolve the selected duplicate group
+    async fn handle_resolution_confirmation(
+        &mut self,
+        key: crossterm::event::KeyEvent,
+    ) -> Result<Option<Action>> {
+        match key.code {
+            crossterm::event::KeyCode::Char('y') | crossterm::event::KeyCode::Char('Y') => {
+                if let Some(resolution) = self.pending_resolution.take()
+                    && let Some((keep, others)) = self.selected_duplicates()
+                {
+                    self.send_app_action(Action::UpdateAppState(AppState::Working(
+                        "Resolving duplicates...".into(),
+                    )))?;
+                    self.send_explorer_action(Action::ResolveDuplicates(keep, others, resolution))
+                        .await?;
+                }
+            }
+            crossterm::event::KeyCode::Char('n')
+            | crossterm::event::KeyCode::Char('N')
+            | crossterm::event::KeyCode::Esc => {
+                self.pending_resolution = None;
+            }
+            _ => {}
+        }
+        Ok(None)
+    }
+
+    /// Asks the user to confirm the resolution of the selected duplicate group
+    fn request_resolution(&mut self, resolution: DuplicateResolution) -> Option<Action> {
+        if self.search_result.duplicate_groups().is_empty() {
+            return Action::UpdateAppState(AppState::Failure(
+                "Only available for duplicate search results".to_string(),
+            ))
+            .into();
+        }
+        self.pending_resolution = Some(resolution);
+        None
+    }
+
+    fn render_resolution_confirmation(
+        &self,
+        f: &mut ratatui::Frame<'_>,
+        area: Rect,
+        resolution: DuplicateResolution,
+    ) {
+        let Some((keep, others)) = self.selected_duplicates() else {
+            return;
+        };
+        let reclaimable = self
+            .search_result
+            .selected_index()
+            .and_then(|index| self.search_result.duplicate_group_of(index))
+            .map(|(_, group)| group.reclaimable())
+            .unwrap_or_default();
+
+        let (title, action_desc) = match resolution {
+            DuplicateResolution::Trash => (" Move duplicates to trash ", "move to the trash"),
+            DuplicateResolution::HardLink => (
+                " Replace duplicates with hard links ",
+                "replace with hard links",
+            ),
+        };
+
+        let block = Block::default()
+            .title_top(title)
+            .title_bottom(Line::from(vec![
+                " <y>".fg(Color::Yellow),
+                " Confirm ".into(),
+                " <n|Esc>".fg(Color::Yellow),
+                " Cancel ".into(),
+            ]))
+            .title_alignment(Alignment::Center)
+            .borders(Borders::ALL)
+            .border_type(BorderType::Rounded)
+            .border_style(Style::new().bold().fg(Color::LightRed))
+            .style(Style::new().bg(Color::default()));
+
+        let centered_area = centered_rect_fixed_height(65, 6, area);
+
+        let [_, keep_area, text_area, _] = Layout::vertical([
+            Constraint::Length(1),
+            Constraint::Length(1),
+            Constraint::Length(1),
+            Constraint::Length(1),
+        ])
+        .areas(block.inner(centered_area));
+
+        let copies_str = if others.len() == 1 { "copy" } else { "copies" };
+        let keep_text = Paragraph::new(format!("Keep '{}'", utils::format_path_for_display(&keep)))
+            .style(Style::new().fg(Color::White))
+            .alignment(Alignment::Center);
+        let text = Paragraph::new(format!(
+            "and {} the {} other {copies_str} ({})?",
+            action_desc,
+            others.len(),
+            utils::convert_bytes_to_human_readable(reclaimable)
+        ))
+        .style(Style::new().fg(Color::White))
+        .alignment(Alignment::Center);
+
+        f.render_widget(Clear, centered_area);
+        f.render_widget(block, centered_area);
+        f.render_widget(keep_text, keep_area);
+        f.render_widget(text, text_area);
+    }
+
+    /// Handles the key events of the refine input, the input actions are only meant for this page
+    async fn handle_refine_key_events(
+        &mut self,
+        key: crossterm::event::KeyEvent,
+    ) -> Result<Option<Action>> {
+        let Some(input) = &mut self.refine_input else {
+            return Ok(None);
+        };
+
+        match input.handle_key_events(key).await? {
+            Some(Action::ApplySettingsInput) => {
+                let query = input.value().to_string();
+                self.refine_input = None;
+                Ok(self.refine_result(&query).into())
+            }
+            Some(Action::SettingsInputCanceled) => {
+                self.refine_input = None;
+                Ok(None)
+            }
+            action => Ok(action),
+        }
+    }
+
+    /// Replaces the shown result with the entries, that match the follow-up query.<br>
+    /// The broader result is kept in the history, an empty refinement keeps the shown result.
+    fn refine_result(&mut self, query: &str) -> Action {
+        match self.search_result.refine(query) {
+            Ok(Some(refined)) => {
+                let msg = format!(
+                    "{} of {} results match",
+                    refined.items().len(),
+                    self.search_result.items().len()
+                );
+                self.result_history
+                    .push(std::mem::replace(&mut self.search_result, refined));
+                self.table_state
+                    .select(self.search_result.selected().into());
+                self.build_selected_hint();
+                Action::UpdateAppState(AppState::Done(msg))
+            }
+            Ok(None) => Action::UpdateAppState(AppState::Failure(format!(
+                "No results match '{}'",
+                query.trim()
+            ))),
+            Err(err) => Action::UpdateAppState(AppState::Failure(err.to_string())),
+        }
+    }
+
+    /// Marks or unmarks the selected entry and moves to the next entry
+    fn toggle_mark(&mut self) {
+        if let Some(entry) = self.search_result.selected_item() {
+            let path = entry.path.clone();
+            if !self.marked_paths.remove(&path) {
+                self.marked_paths.insert(path);
+            }
+            self.search_result.scroll_down();
+            self.table_state
+                .select(self.search_result.selected().into());
+            self.build_selected_hint();
+        }
+    }
+
+    /// Marks all shown entries, or unmarks them if all of them are already marked
+    fn toggle_mark_all(&mut self) {
+        let items = self.search_result.items();
+        let all_marked = items
+            .iter()
+            .all(|entry| self.marked_paths.contains(&entry.path));
+
+        for entry in items {
+            if all_marked {
+                self.marked_paths.remove(&entry.path);
+            } else {
+                self.marked_paths.insert(entry.path.clone());
+            }
+        }
+    }
+
+    /// Returns the marked entries of the shown result, or the selected entry if nothing is marked.<br>
+    /// Marked entries, that were removed by a refinement, are not part of the operation.
+    fn bulk_paths(&self) -> Vec<PathBuf> {
+        let marked: Vec<PathBuf> = self
+            .search_result
+            .items()
+            .iter()
+            .filter(|entry| self.marked_paths.contains(&entry.path))
+            .map(|entry| entry.path.clone())
+            .collect();
+
+        if marked.is_empty() {
+            self.search_result
+                .selected_item()
+                .map(|entry| vec![entry.path.clone()])
+                .unwrap_or_default()
+        } else {
+            marked
+        }
+    }
+
+    /// Opens the menu of the bulk operations for the marked entries
+    fn open_bulk_menu(&mut self) -> Option<Action> {
+        if self.search_result.is_in_progress() {
+            return Action::UpdateAppState(AppState::Failure(
+                "Wait until the search is done or stop it with Esc".to_string(),
+            ))
+            .into();
+        }
+        let count = self.bulk_paths().len();
+        if count == 0 {
+            return None;
+        }
+        self.bulk_menu.open(count);
+        None
+    }
+
+    /// Handles the key events of the bulk menu, the chosen operation asks for a destination or a confirmation
+    fn handle_bulk_menu_key_events(&mut self, key: crossterm::event::KeyEvent) {
+        match key.code {
+            crossterm::event::KeyCode::Up => self.bulk_menu.previous(),
+            crossterm::event::KeyCode::Down => self.bulk_menu.next(),
+            crossterm::event::KeyCode::Enter => {
+                let Some(operation) = self.bulk_menu.selected() else {
+                    return;
+                };
+                self.bulk_menu.close();
+
+                if operation.requires_target_dir() {
+                    let (title, default_dir) = match operation {
+                        BulkOperation::Archive => {
+                            ("Create the archive in directory", self.export_dir.clone())
+                        }
+                        _ => (
+                            "Destination directory",
+                            self.search_result.search_root().to_path_buf(),
+                        ),
+                    };
+                    self.bulk_target_input =
+                        Some((operation, SettingsInput::new(title).with_value(default_dir)));
+                } else {
+                    self.pending_bulk_job = Some(self.build_bulk_job(operation, None));
+                }
+            }
+            crossterm::event::KeyCode::Esc => self.bulk_menu.close(),
+            _ => {}
+        }
+    }
+
+    /// Handles the key events of the destination input, the input actions are only meant for this page
+    async fn handle_bulk_target_key_events(
+        &mut self,
+        key: crossterm::event::KeyEvent,
+    ) -> Result<Option<Action>> {
+        let Some((operation, input)) = &mut self.bulk_target_input else {
+            return Ok(None);
+        };
+
+        match input.handle_key_events(key).await? {
+            Some(Action::ApplySettingsInput) => {
+                let operation = *operation;
+                let target_dir = PathBuf::from(utils::expand_and_resolve_path(input.value()));
+                self.bulk_target_input = None;
+
+                let target = match operation {
+                    BulkOperation::Archive => target_dir.join(format!(
+                        "{}-search-results_{}.zip",
+                        utils::app_name(),
+                        chrono::Local::now().format("%Y-%m-%dT%H_%M_%S")
+                    )),
+                    _ => target_dir,
+                };
+                let job = self.build_bulk_job(operation, Some(target));
+                self.run_bulk_job(job).await?;
+                Ok(None)
+            }
+            Some(Action::SettingsInputCanceled) => {
+                self.bulk_target_input = None;
+                Ok(None)
+            }
+            action => Ok(action),
+        }
+    }
+
+    fn build_bulk_job(&self, operation: BulkOperation, target: Option<PathBuf>) -> BulkJob {
+        BulkJob::new(
+            operation,
+            self.bulk_paths(),
+            target,
+            self.search_result.search_root().to_path_buf(),
+        )
+    }
+
+    /// Handles the key events of the confirmation dialog of a trash or delete operation
+    async fn handle_bulk_confirmation(
+        &mut self,
+        key: crossterm::event::KeyEvent,
+    ) -> Result<Option<Action>> {
+        match key.code {
+            crossterm::event::KeyCode::Char('y') | crossterm::event::KeyCode::Char('Y') => {
+                if let Some(job) = self.pending_bulk_job.take() {
+                    self.run_bulk_job(job).await?;
+                }
+            }
+            crossterm::event::KeyCode::Char('n')
+            | crossterm::event::KeyCode::Char('N')
+            | crossterm::event::KeyCode::Esc => {
+                self.pending_bulk_job = None;
+            }
+            _ => {}
+        }
+        Ok(None)
+    }
+
+    async fn run_bulk_job(&mut self, job: BulkJob) -> Result<()> {
+        self.send_app_action(Action::UpdateAppState(AppState::Working(format!(
+            "{}... press Esc to cancel",
+            job.operation
+        ))))?;
+        self.is_running_bulk_job = true;
+        self.send_explorer_action(Action::RunBulkJob(job)).await
+    }
+
+    /// Removes the moved and deleted entries from the result and shows the errors of the failed entries
+    fn complete_bulk_job(&mut self, report: &BulkReport) -> Action {
+        self.is_working = false;
+        self.is_running_bulk_job = false;
+
+        if report.operation.removes_entries() {
+            self.search_result.remove_paths(&report.done);
+            self.result_history.remove_paths(&report.done);
+            self.table_state
+                .select(self.search_result.selected().into());
+            self.build_selected_hint();
+        }
+        for path in &report.done {
+            self.marked_paths.remove(path);
+        }
+
+        let entries_str = if report.done.len() == 1 {
+            "entry"
+        } else {
+            "entries"
+        };
+        let mut msg = format!(
+            "{} {entries_str} {}",
+            report.done.len(),
+            report.operation.done_desc()
+        );
+        if report.is_cancelled {
+            msg.push_str(", cancelled");
+        }
+        if report.errors.is_empty() {
+            return Action::UpdateAppState(AppState::Done(msg));
+        }
+
+        msg = format!("{msg}, {} failed", report.errors.len());
+        self.error_report.open(msg.clone(), report.errors.clone());
+        Action::UpdateAppState(AppState::Failure(msg))
+    }
+
+    fn render_bulk_confirmation(&self, f: &mut ratatui::Frame<'_>, area: Rect, job: &BulkJob) {
+        let entries_str = if job.paths.len() == 1 {
+            "entry"
+        } else {
+            "entries"
+        };
+        let (title, text) = match job.operation {
+            BulkOperation::Delete => (
+                " Delete permanently ",
+                format!(
+                    "Permanently delete {} {entries_str}? This cannot be undone.",
+                    job.paths.len()
+                ),
+            ),
+            _ => (
+                " Move to trash ",
+                format!("Move {} {entries_str} to the trash?", job.paths.len()),
+            ),
+        };
+
+        let block = Block::default()
+            .title_top(title)
+            .title_bottom(Line::from(vec![
+                " <y>".fg(Color::Yellow),
+                " Confirm ".into(),
+                " <n|Esc>".fg(Color::Yellow),
+                " Cancel ".into(),
+            ]))
+            .title_alignment(Alignment::Center)
+            .borders(Borders::ALL)
+            .border_type(BorderType::Rounded)
+            .border_style(Style::new().bold().fg(Color::LightRed))
+            .style(Style::new().bg(Color::default()));
+
+        let centered_area = centered_rect_fixed_height(65, 5, area);
+        let [_, text_area, _] = Layout::vertical([
+            Constraint::Length(1),
+            Constraint::Length(1),
+            Constraint::Length(1),
+        ])
+        .areas(block.inner(centered_area));
+
+        let text = Paragraph::new(text)
+            .style(Style::new().fg(Color::White))
+            .alignment(Alignment::Center);
+
+        f.render_widget(Clear, centered_area);
+        f.render_widget(block, centered_area);
+        f.render_widget(text, text_area);
+    }
+
     fn build_selected_hint(&mut self) {
+        let rows_len = self.search_result.row_count();
         self.selected_hint = format!(
             " {}/{} ",
-            self.search_result.selected() + 1,
-            self.search_result.items().len()
+            (self.search_result.selected() + 1).min(rows_len),
+            rows_len
         );
     }
+
+    /// Sorts or groups the shown rows, not available while the rows are still streamed or grouped by content
+    fn set_result_order(&mut self, order: ResultOrder) -> Option<Action> {
+        if self.search_result.is_in_progress() {
+            return Action::UpdateAppState(AppState::Failure(
+                "Wait until the search is done or stop it with Esc".to_string(),
+            ))
+            .into();
+        }
+        if !self.search_result.duplicate_groups().is_empty() {
+            return Action::UpdateAppState(AppState::Failure(
+                "Duplicates are grouped by content and cannot be sorted".to_string(),
+            ))
+            .into();
+        }
+
+        self.search_result.set_order(order);
+        self.table_state
+            .select(self.search_result.selected().into());
+        self.build_selected_hint();
+        None
+    }
+
+    /// Collapses or expands the directory of the selected row, if the result is grouped by directory
+    fn set_dir_collapsed(&mut self, is_collapsed: bool) {
+        self.search_result.set_selected_dir_collapsed(is_collapsed);
+        self.table_state
+            .select(self.search_result.selected().into());
+        self.build_selected_hint();
+    }
+
+    /// The row of a directory header, if the result is grouped by directory
+    fn dir_header_row(
+        header: &DirHeader,
+        column_count: usize,
+        theme_colors: &ThemeColor,
+    ) -> Row<'static> {
+        let symbol = if header.is_collapsed { "▸ " } else { "▾ " };
+        let entries_str = if header.len == 1 { "entry" } else { "entries" };
+
+        let mut cells = vec![
+            Cell::from(Text::from(vec![
+                Line::from(" "),
+                Line::from(vec![
+                    Span::from(symbol).fg(theme_colors.selected_color),
+                    Span::from(utils::format_path_for_display(&header.path))
+                        .fg(theme_colors.dir_color)
+                        .bold(),
+                    Span::from(format!("  ({} {entries_str})", header.len)).fg(theme_colors.alt_fg),
+                ]),
+            ])),
+            Cell::from(Text::from(vec![
+                Line::from(" "),
+                Line::from("Dir").fg(theme_colors.alt_fg),
+            ])),
+            Cell::from(Text::from(vec![
+                Line::from(" "),
+                Line::from(utils::convert_bytes_to_human_readable(header.size))
+                    .fg(theme_colors.alt_fg),
+            ])),
+        ];
+        cells.resize(column_count, Cell::from(""));
+
+        Row::new(cells)
+            .height(2)
+            .style(Style::new().bg(theme_colors.alt_bg))
+    }
 }
 
 #[async_trait(?Send)]
@@ -298,6 +853,22 @@ impl Component for ResultWidget {
         if let Some(event) = event {
             match event {
                 Event::Key(key_event) => {
+                    if self.is_calculating_metadata
+                        && self.app_context == AppContext::Results
+                        && key_event.code == crossterm::event::KeyCode::Esc
+                    {
+                        return self.cancel_dir_metadata().await;
+                    }
+                    // the Explorer reports the entries processed until the cancellation
+                    if self.is_running_bulk_job
+                        && self.app_context == AppContext::Results
+                        && key_event.code == crossterm::event::KeyCode::Esc
+                    {
+                        if let Some(sender) = &self.explorer_action_sender {
+                            sender.send(Action::CancelBulkJob).await?;
+                        }
+                        return Ok(None);
+                    }
                     if self.should_handle_events() {
                         let cmd_desc =
                             key_bindings::get_command_description(&key_event, &self.app_context)
@@ -319,6 +890,34 @@ impl Component for ResultWidget {
         &mut self,
         key: crossterm::event::KeyEvent,
     ) -> Result<Option<Action>> {
+        if self.pending_resolution.is_some() {
+            return self.handle_resolution_confirmation(key).await;
+        }
+        if self.error_report.is_visible() {
+            match key.code {
+                crossterm::event::KeyCode::Up => self.error_report.previous(),
+                crossterm::event::KeyCode::Down => self.error_report.next(),
+                crossterm::event::KeyCode::Enter | crossterm::event::KeyCode::Esc => {
+                    self.error_report.close()
+                }
+                _ => {}
+            }
+            return Ok(None);
+        }
+        if self.pending_bulk_job.is_some() {
+            return self.handle_bulk_confirmation(key).await;
+        }
+        if self.bulk_menu.is_visible() {
+            self.handle_bulk_menu_key_events(key);
+            return Ok(None);
+        }
+        if self.bulk_target_input.is_some() {
+            return self.handle_bulk_target_key_events(key).await;
+        }
+        if self.refine_input.is_some() {
+            return self.handle_refine_key_events(key).await;
+        }
+
         match key.code {
             // Up arrow key -> move one file or folder up -> we cycle back to the end when we reach the beginning
             crossterm::event::KeyCode::Up
@@ -357,8 +956,7 @@ impl Component for ResultWidget {
                 if key.modifiers == crossterm::event::KeyModifiers::NONE =>
             {
                 self.send_app_action(Action::UpdateAppState(AppState::done_empty()))?;
-                if self.search_result.selected()
-                    >= self.search_result.items().len().saturating_sub(1)
+                if self.search_result.selected() >= self.search_result.row_count().saturating_sub(1)
                 {
                     return Ok(Action::UpdateAppState(AppState::Done(
                         "Last item reached".to_string(),
@@ -370,11 +968,96 @@ impl Component for ResultWidget {
                     .select(self.search_result.selected().into());
                 self.build_selected_hint();
             }
+            // Enter -> Open the selected file in the editor, at the matching line for a content search
+            crossterm::event::KeyCode::Enter
+                if key.modifiers == crossterm::event::KeyModifiers::NONE =>
+            {
+                if let Some(header) = self.search_result.selected_dir_header() {
+                    let is_collapsed = !header.is_collapsed;
+                    self.set_dir_collapsed(is_collapsed);
+                    return Ok(None);
+                }
+                let Some(index) = self.search_result.selected_index() else {
+                    return Ok(None);
+                };
+                let Some(selected_entry) = self.search_result.selected_item() else {
+                    return Ok(None);
+                };
+
+                if selected_entry.path.is_file() {
+                    let line = self
+                        .search_result
+                        .line_match_of(index)
+                        .map(|line_match| line_match.line_number);
+                    return Ok(Action::OpenInEditor(selected_entry.path.clone(), line).into());
+                } else {
+                    return Ok(Action::UpdateAppState(AppState::Failure(
+                        "Only files can be opened in the editor".to_string(),
+                    ))
+                    .into());
+                }
+            }
+            // Ctrl + e -> Mark the selected file or directory for comparison or compare it with the marked one
+            crossterm::event::KeyCode::Char('e')
+                if key.modifiers == crossterm::event::KeyModifiers::CONTROL =>
+            {
+                let Some(selected_entry) = self.search_result.selected_item() else {
+                    return Ok(None);
+                };
+
+                if selected_entry.path.is_file() {
+                    return Ok(Action::SelectDiffFile(
+                        selected_entry.path.clone(),
+                        AppContext::Results,
+                    )
+                    .into());
+                } else if selected_entry.path.is_dir() {
+                    return Ok(Action::SelectCompareDir(
+                        selected_entry.path.clone(),
+                        AppContext::Results,
+                    )
+                    .into());
+                } else {
+                    return Ok(Action::UpdateAppState(AppState::Failure(
+                        "The selected path no longer exists".to_string(),
+                    ))
+                    .into());
+                }
+            }
+            // Ctrl + r -> Open the parent directory of the selected entry in the Explorer and select the entry
+            crossterm::event::KeyCode::Char('r')
+                if key.modifiers == crossterm::event::KeyModifiers::CONTROL =>
+            {
+                if self.search_result.is_in_progress() {
+                    return Ok(Action::UpdateAppState(AppState::Failure(
+                        "Wait until the search is done or stop it with Esc".to_string(),
+                    ))
+                    .into());
+                }
+                let Some(selected_entry) = self.search_result.selected_item() else {
+                    return Ok(None);
+                };
+
+                if !selected_entry.path.exists() && !selected_entry.path.is_symlink() {
+                    return Ok(Action::UpdateAppState(AppState::Failure(
+                        "The selected path no longer exists".to_string(),
+                    ))
+                    .into());
+                }
+
+                let path = selected_entry.path.clone();
+                // the result is kept, so that the Explorer can return to it to reveal the next entry
+                self.app_context = AppContext::NotActive;
+                self.send_app_action(Action::SwitchAppContext(AppContext::Explorer))?;
+                return Ok(Action::RevealInExplorer(path).into());
+            }
             // Ctrl + a -> Display metadata for the selected object, if any
             crossterm::event::KeyCode::Char('a')
                 if key.modifiers == crossterm::event::KeyModifiers::CONTROL =>
             {
-                let selected_entry = &self.search_result.items()[self.search_result.selected()];
+                let Some(selected_entry) = self.search_result.selected_item() else {
+                    return Ok(None);
+                };
 
                 if !selected_entry.path.exists() {
                     return Ok(Some(Action::UpdateAppState(AppState::Failure(
@@ -404,13 +1087,16 @@ impl Component for ResultWidget {
                         self.follow_sym_links,
                     ))
                     .await?;
+                    self.is_calculating_metadata = true;
                 }
             }
             // Ctrl + c -> Copy absolute path to clipboard
             crossterm::event::KeyCode::Char('c')
                 if key.modifiers == crossterm::event::KeyModifiers::CONTROL =>
             {
-                let selected_entry = &self.search_result.items()[self.search_result.selected()];
+                let Some(selected_entry) = self.search_result.selected_item() else {
+                    return Ok(None);
+                };
 
                 let path_to_copy = utils::absolute_path_as_string(&selected_entry.path);
                 match utils::copy_to_clipboard(&path_to_copy) {
@@ -427,6 +1113,118 @@ impl Component for ResultWidget {
                     }
                 }
             }
+            // Delete -> keep the selected duplicate and move the other copies to the trash
+            crossterm::event::KeyCode::Delete
+                if key.modifiers == crossterm::event::KeyModifiers::NONE =>
+            {
+                return Ok(self.request_resolution(DuplicateResolution::Trash));
+            }
+            // Ctrl + l -> keep the selected duplicate and replace the other copies with hard links
+            crossterm::event::KeyCode::Char('l')
+                if key.modifiers == crossterm::event::KeyModifiers::CONTROL =>
+            {
+                return Ok(self.request_resolution(DuplicateResolution::HardLink));
+            }
+            // Ctrl + f -> Refine the shown result with a follow-up query
+            crossterm::event::KeyCode::Char('f')
+                if key.modifiers == crossterm::event::KeyModifiers::CONTROL =>
+            {
+                if self.search_result.is_in_progress() {
+                    return Ok(Action::UpdateAppState(AppState::Failure(
+                        "Wait until the search is done or stop it with Esc".to_string(),
+                    ))
+                    .into());
+                }
+                self.refine_input = Some(SettingsInput::new("Refine results").with_query(""));
+            }
+            // Space -> Mark or unmark the selected entry for a bulk operation
+            crossterm::event::KeyCode::Char(' ')
+                if key.modifiers == crossterm::event::KeyModifiers::NONE =>
+            {
+                self.toggle_mark();
+            }
+            // a -> Mark or unmark all shown entries
+            crossterm::event::KeyCode::Char('a')
+                if key.modifiers == crossterm::event::KeyModifiers::NONE =>
+            {
+                self.toggle_mark_all();
+            }
+            // s -> Sort by the next column
+            crossterm::event::KeyCode::Char('s')
+                if key.modifiers == crossterm::event::KeyModifiers::NONE =>
+            {
+                let order = self.search_result.order();
+                return Ok(self.set_result_order(ResultOrder {
+                    key: order.key.next(),
+                    ..order
+                }));
+            }
+            // r -> Reverse the sort order
+            crossterm::event::KeyCode::Char('r')
+                if key.modifiers == crossterm::event::KeyModifiers::NONE =>
+            {
+                let order = self.search_result.order();
+                return Ok(self.set_result_order(ResultOrder {
+                    descending: !order.descending,
+                    ..order
+                }));
+            }
+            // g -> Group the entries by their directory or show them as one list again
+            crossterm::event::KeyCode::Char('g')
+                if key.modifiers == crossterm::event::KeyModifiers::NONE =>
+            {
+                let order = self.search_result.order();
+                return Ok(self.set_result_order(ResultOrder {
+                    group_by_dir: !order.group_by_dir,
+                    ..order
+                }));
+            }
+            // Left -> Collapse the directory of the selected entry
+            crossterm::event::KeyCode::Left
+                if key.modifiers == crossterm::event::KeyModifiers::NONE =>
+            {
+                self.set_dir_collapsed(true);
+            }
+            // Right -> Expand the selected directory
+            crossterm::event::KeyCode::Right
+                if key.modifiers == crossterm::event::KeyModifiers::NONE =>
+            {
+                self.set_dir_collapsed(false);
+            }
+            // Ctrl + b -> Apply a bulk operation to the marked entries
+            crossterm::event::KeyCode::Char('b')
+                if key.modifiers == crossterm::event::KeyModifiers::CONTROL =>
+            {
+                return Ok(self.open_bulk_menu());
+            }
+            // Alt + Left -> Show the previous (broader) result
+            crossterm::event::KeyCode::Left
+                if key.modifiers == crossterm::event::KeyModifiers::ALT =>
+            {
+                if !self.result_history.back(&mut self.search_result) {
+                    return Ok(Action::UpdateAppState(AppState::Done(
+                        "No previous results".to_string(),
+                    ))
+                    .into());
+                }
+                self.table_state
+                    .select(self.search_result.selected().into());
+                self.build_selected_hint();
+            }
+            // Alt + Right -> Show the next (refined) result again
+            crossterm::event::KeyCode::Right
+                if key.modifiers == crossterm::event::KeyModifiers::ALT =>
+            {
+                if !self.result_history.forward(&mut self.search_result) {
+                    return Ok(Action::UpdateAppState(AppState::Done(
+                        "No next results".to_string(),
+                    ))
+                    .into());
+                }
+                self.table_state
+                    .select(self.search_result.selected().into());
+                self.build_selected_hint();
+            }
             crossterm::event::KeyCode::Char('o')
                 if key.modifiers == crossterm::event::KeyModifiers::CONTROL =>
             {
@@ -493,9 +1291,21 @@ impl Component for ResultWidget {
                 // Close the channel to indicate that no more values will be sent
                 drop(tx);
             }
+            // Esc -> Stop a running search and keep the partial results
+            crossterm::event::KeyCode::Esc if self.search_result.is_in_progress() => {
+                // the Explorer reports the cancellation in the footer
+                self.send_explorer_action(Action::CancelSearch).await?;
+                self.is_working = false;
+                self.search_result.stop();
+                self.table_state
+                    .select(self.search_result.selected().into());
+                self.build_selected_hint();
+            }
             crossterm::event::KeyCode::Esc => {
                 self.app_context = AppContext::NotActive;
                 self.search_result = SearchResult::default();
+                self.result_history.clear();
+                self.marked_paths.clear();
                 self.table_state
                     .select(self.search_result.selected().into());
                 return Ok(Action::SwitchAppContext(self.previous_context).into());
@@ -514,15 +1324,58 @@ impl Component for ResultWidget {
             Action::ShowResultsPage(result, mode) => {
                 self.applied_search_mode = *mode;
                 self.search_result = result.clone();
+                self.result_history.clear();
+                self.marked_paths.clear();
+                self.refine_input = None;
+                self.name_matcher = self.search_result.name_matcher();
                 self.search_result.set_terminal_height(self.terminal_height);
                 self.table_state
                     .select(self.search_result.selected().into());
                 self.build_selected_hint();
 
+                if self.search_result.is_in_progress() {
+                    return Ok(Action::UpdateAppState(AppState::Working(
+                        "Search in progress...".into(),
+                    ))
+                    .into());
+                }
                 return Ok(Action::UpdateAppState(AppState::Done("Done".into())).into());
             }
+            // the matches of a stopped search may arrive after the cancellation
+            Action::SearchChunk(items, line_matches) if self.search_result.is_in_progress() => {
+                self.search_result.append_matches(items, line_matches);
+                self.build_selected_hint();
+            }
+            Action::SearchSnapshot(items) if self.search_result.is_in_progress() => {
+                self.search_result.replace_matches(items);
+                self.table_state
+                    .select(self.search_result.selected().into());
+                self.build_selected_hint();
+            }
+            Action::SearchDone(search_result) if self.search_result.is_in_progress() => {
+                match search_result {
+                    Some(result) => {
+                        self.search_result.complete(result);
+                        self.table_state
+                            .select(self.search_result.selected().into());
+                        self.build_selected_hint();
+                        return Ok(Action::UpdateAppState(AppState::Done("Done".into())).into());
+                    }
+                    None => {
+                        self.search_result = SearchResult::default();
+                        self.send_app_action(Action::UpdateAppState(AppState::Failure(
+                            "No matches found".to_string(),
+                        )))?;
+                        if self.app_context == AppContext::Results {
+                            self.app_context = AppContext::NotActive;
+                            return Ok(Action::SwitchAppContext(self.previous_context).into());
+                        }
+                    }
+                }
+            }
             Action::LoadDirMetadataDone(metadata) => {
                 self.is_working = false;
+                self.is_calculating_metadata = false;
                 match metadata {
                     Some(metadata) => {
                         self.is_metadata_pop_up = true;
@@ -545,6 +1398,30 @@ impl Component for ResultWidget {
                 self.is_working = false;
                 return Ok(Action::UpdateAppState(AppState::Failure(msg.clone())).into());
             }
+            Action::ResolveDuplicatesDone(resolved, errors) => {
+                self.is_working = false;
+                self.search_result.remove_resolved_duplicates(resolved);
+                self.result_history.remove_paths(resolved);
+                self.table_state
+                    .select(self.search_result.selected().into());
+                self.build_selected_hint();
+
+                let files_str = if resolved.len() == 1 { "file" } else { "files" };
+                let msg = format!("{} duplicate {files_str} resolved", resolved.len());
+                if errors.is_empty() {
+                    return Ok(Action::UpdateAppState(AppState::Done(msg)).into());
+                } else {
+                    return Ok(Action::UpdateAppState(AppState::Failure(format!(
+                        "{msg}, {} failed: {}",
+                        errors.len(),
+                        errors.join(", ")
+                    )))
+                    .into());
+                }
+            }
+            Action::BulkJobDone(report) if self.is_running_bulk_job => {
+                return Ok(self.complete_bulk_job(report).into());
+            }
             Action::CloseMetadata => self.is_metadata_pop_up = false,
             Action::Resize(_, h) => {
                 // update the terminal height
@@ -571,6 +1448,9 @@ impl Component for ResultWidget {
                 self.export_dir = c.export_dir();
                 self.follow_sym_links = c.follow_sym_links();
             }
+            Action::GitStatusDone(git_info) => {
+                self.git_info = git_info.clone();
+            }
             Action::Quit => self.export_task.stop(),
             _ => {}
         }
@@ -603,43 +1483,193 @@ impl Component for ResultWidget {
                 "matches"
             };
 
-            let inner_block_title = format!(
-                " Summary → [ Applied Mode: {}, {} {matches_str} ]  ",
-                self.applied_search_mode,
-                self.search_result.items().len()
-            );
+            let duplicate_groups = self.search_result.duplicate_groups();
+            let show_duplicates = !duplicate_groups.is_empty();
+            // a content search shows the matching line instead of the type and size
+            let show_lines = self.search_result.search_type() == SearchType::Content;
+            // a format search shows the detected format instead of the type
+            let show_formats = self.search_result.search_type() == SearchType::Format;
+
+            // the follow-up queries of a refined result, e.g. `Refined: type:file › *.rs, `
+            let refined_str = match self.search_result.refinements() {
+                [] => String::new(),
+                refinements => format!("Refined: {}, ", refinements.join(" › ")),
+            };
+
+            // the number of marked entries of the shown result, e.g. `3 marked, `
+            let marked_count = self
+                .search_result
+                .items()
+                .iter()
+                .filter(|entry| self.marked_paths.contains(&entry.path))
+                .count();
+            let marked_str = match marked_count {
+                0 => String::new(),
+                n => format!("{n} marked, "),
+            };
+
+            // the applied order, e.g. `Sorted: Size ↓, grouped by directory, `
+            let order = self.search_result.order();
+            let mut order_str = String::new();
+            if order.key != SortKey::Search || order.descending {
+                let direction = if order.descending { "↓" } else { "↑" };
+                order_str.push_str(&format!("Sorted: {} {direction}, ", order.key));
+            }
+            if order.group_by_dir {
+                order_str.push_str("grouped by directory, ");
+            }
+
+            // the age of the index, that answered the search, e.g. `From index updated 2h ago, `
+            let index_str = match self.search_result.indexed_at() {
+                Some(indexed_at) => format!("From index updated {}, ", format_age(indexed_at)),
+                None => String::new(),
+            };
 
-            let help_msg = vec![
+            let inner_block_title = if show_duplicates {
+                format!(
+                    " Summary → [ Applied Mode: {}, {refined_str}{marked_str}{} duplicates in {} groups, {} reclaimable ]  ",
+                    self.applied_search_mode,
+                    self.search_result.items().len(),
+                    duplicate_groups.len(),
+                    utils::convert_bytes_to_human_readable(self.search_result.reclaimable_space())
+                )
+            } else {
+                let searching_str = if self.search_result.is_in_progress() {
+                    "searching… "
+                } else {
+                    ""
+                };
+                format!(
+                    " Summary → [ Applied Mode: {}, Type: {}, {index_str}{refined_str}{order_str}{marked_str}{searching_str}{} {matches_str} ]  ",
+                    self.applied_search_mode,
+                    self.search_result.search_type(),
+                    self.search_result.items().len()
+                )
+            };
+
+            let esc_desc = if self.search_result.is_in_progress() {
+                " Stop search "
+            } else {
+                " Back to search "
+            };
+            let mut help_msg = vec![
                 " <Esc>".fg(theme_colors.main_text_fg),
-                " Back to search ".fg(theme_colors.main_fg),
+                esc_desc.fg(theme_colors.main_fg),
                 " <F12>".fg(theme_colors.main_text_fg),
                 " Export Results (JSON) ".fg(theme_colors.main_fg),
+                " <Enter>".fg(theme_colors.main_text_fg),
+                " Open in editor ".fg(theme_colors.main_fg),
+                " <Ctrl+F>".fg(theme_colors.main_text_fg),
+                " Refine ".fg(theme_colors.main_fg),
+                " <Ctrl+R>".fg(theme_colors.main_text_fg),
+                " Reveal in Explorer ".fg(theme_colors.main_fg),
+                " <Space>".fg(theme_colors.main_text_fg),
+                " Mark ".fg(theme_colors.main_fg),
+                " <Ctrl+B>".fg(theme_colors.main_text_fg),
+                " Bulk operations ".fg(theme_colors.main_fg),
             ];
+            if !show_duplicates {
+                help_msg.extend([
+                    " <s|r>".fg(theme_colors.main_text_fg),
+                    " Sort ".fg(theme_colors.main_fg),
+                    " <g>".fg(theme_colors.main_text_fg),
+                    " Group by directory ".fg(theme_colors.main_fg),
+                ]);
+            }
+            if self.result_history.can_go_back() {
+                help_msg.extend([
+                    " <Alt+←>".fg(theme_colors.main_text_fg),
+                    " Back ".fg(theme_colors.main_fg),
+                ]);
+            }
+            if self.result_history.can_go_forward() {
+                help_msg.extend([
+                    " <Alt+→>".fg(theme_colors.main_text_fg),
+                    " Forward ".fg(theme_colors.main_fg),
+                ]);
+            }
+            if show_duplicates {
+                help_msg.extend([
+                    " <Delete>".fg(theme_colors.main_text_fg),
+                    " Trash other copies ".fg(theme_colors.main_fg),
+                    " <Ctrl+L>".fg(theme_colors.main_text_fg),
+                    " Hard-link other copies ".fg(theme_colors.main_fg),
+                ]);
+            }
 
             let header_style = Style::default()
                 .fg(self.theme.theme_colors().header_fg)
                 .bg(self.theme.theme_colors().header_bg);
 
-            let header = ["Path", "Type", "Size"]
+            // the git column is only visible, if the search directory is inside a git work tree
+            let show_git_status = self.git_info.is_some();
+
+            let mut header_cells = if show_lines {
+                vec!["Path", "Line", "Match"]
+            } else if show_formats {
+                vec!["Path", "Format", "Size"]
+            } else {
+                vec!["Path", "Type", "Size"]
+            };
+            if show_git_status {
+                header_cells.push("Git");
+            }
+            if show_duplicates {
+                header_cells.push("Group");
+            }
+            let header = header_cells
                 .into_iter()
                 .map(Cell::from)
                 .collect::<Row>()
                 .style(header_style)
                 .height(1);
 
-            let table_widths = [
-                Constraint::Fill(1),
-                Constraint::Length(7),
-                Constraint::Length(12),
-            ];
+            let mut table_widths = if show_lines {
+                vec![
+                    Constraint::Fill(1),
+                    Constraint::Length(8),
+                    Constraint::Fill(2),
+                ]
+            } else if show_formats {
+                vec![
+                    Constraint::Fill(1),
+                    Constraint::Length(24),
+                    Constraint::Length(12),
+                ]
+            } else {
+                vec![
+                    Constraint::Fill(1),
+                    Constraint::Length(7),
+                    Constraint::Length(12),
+                ]
+            };
+            if show_git_status {
+                table_widths.push(Constraint::Length(4));
+            }
+            if show_duplicates {
+                table_widths.push(Constraint::Length(20));
+            }
+
+            let column_count = table_widths.len();
 
             let rows = self
                 .search_result
                 .get_content_to_draw()
                 .iter()
                 .enumerate()
-                .map(|(i, entry)| {
-                    let color = match i % 2 {
+                .map(|(i, row)| {
+                    let index = match row {
+                        ResultRow::Entry(index) => *index,
+                        ResultRow::Dir(header) => {
+                            return Self::dir_header_row(header, column_count, &theme_colors);
+                        }
+                    };
+                    let entry = &self.search_result.items()[index];
+                    let duplicate_group = self.search_result.duplicate_group_of(index);
+
+                    // duplicate groups are distinguished by the row color, instead of the single rows
+                    let color_index = duplicate_group.map(|(n, _)| n).unwrap_or(i);
+                    let color = match color_index % 2 {
                         0 => self.theme.theme_colors().alt_row_color,
                         _ => self.theme.theme_colors().normal_row_color,
                     };
@@ -650,13 +1680,29 @@ impl Component for ResultWidget {
                     // SECOND: extract the containing file/dir name from the shorten path
                     let extract = utils::extract_part(&shorten_path, &entry.name);
 
-                    // THIRD: highlight the search query
+                    // THIRD: highlight the search query, a content search highlights the matching line instead
                     let path_spans = match extract {
+                        _ if show_lines => {
+                            vec![Span::from(shorten_path).fg(self.theme.theme_colors().alt_fg)]
+                        }
+                        // a fuzzy query may only match the path, then the matched characters of the path are highlighted
+                        Some(name)
+                            if self.name_matcher.as_ref().is_some_and(|matcher| {
+                                matcher.is_fuzzy() && !matcher.is_match(&name)
+                            }) =>
+                        {
+                            highlight_text_part(
+                                shorten_path,
+                                self.name_matcher.as_ref(),
+                                self.theme.theme_colors().search_highlight_color,
+                                self.theme.theme_colors().alt_fg,
+                            )
+                        }
                         Some(name) => {
                             let p = shorten_path.replace(&name, "");
                             let mut highlighted = highlight_text_part(
                                 name,
-                                self.search_result.search_query(),
+                                self.name_matcher.as_ref(),
                                 self.theme.theme_colors().search_highlight_color,
                                 self.theme.theme_colors().alt_fg,
                             );
@@ -666,44 +1712,86 @@ impl Component for ResultWidget {
                         }
                         None => highlight_text_part(
                             shorten_path,
-                            self.search_result.search_query(),
+                            self.name_matcher.as_ref(),
                             self.theme.theme_colors().search_highlight_color,
                             self.theme.theme_colors().alt_fg,
                         ),
                     };
 
-                    let object_type = if entry.is_dir() {
-                        "Dir".to_string()
-                    } else {
-                        "File".to_string()
-                    };
-                    let size = if let Some(metadata) = &entry.file_metadata {
-                        utils::convert_bytes_to_human_readable(metadata.size)
-                    } else {
-                        " - ".to_string()
+                    let (object_type, size) = match self.search_result.line_match_of(index) {
+                        Some(line_match) => (
+                            Line::from(line_match.line_number.to_string()),
+                            Line::from(highlight_text_part(
+                                line_match.line.clone(),
+                                self.name_matcher.as_ref(),
+                                self.theme.theme_colors().search_highlight_color,
+                                self.theme.theme_colors().alt_fg,
+                            )),
+                        ),
+                        None => {
+                            let object_type = match self.search_result.detected_format_of(index) {
+                                Some(format) => format,
+                                None if entry.is_dir() => "Dir",
+                                None => "File",
+                            };
+                            let size = if let Some(metadata) = &entry.file_metadata {
+                                utils::convert_bytes_to_human_readable(metadata.size)
+                            } else {
+                                " - ".to_string()
+                            };
+                            (Line::from(object_type), Line::from(size))
+                        }
                     };
 
-                    let path_line = Line::from(path_spans);
+                    let mut path_line = Line::from(path_spans);
+                    if self.marked_paths.contains(&entry.path) {
+                        path_line.spans.insert(
+                            0,
+                            Span::from("✔ ").fg(self.theme.theme_colors().selected_color),
+                        );
+                    }
 
                     let path_cell = Cell::from(Text::from(vec![Line::from(" "), path_line]));
                     let object_type_cell = Cell::from(Text::from(vec![
                         Line::from(" "),
-                        Line::from(Span::styled(
-                            object_type,
-                            Style::new().fg(self.theme.theme_colors().alt_fg),
-                        )),
+                        object_type.fg(self.theme.theme_colors().alt_fg),
                     ]));
                     let size_cell = Cell::from(Text::from(vec![
                         Line::from(" "),
-                        Line::from(Span::styled(
-                            size,
-                            Style::new().fg(self.theme.theme_colors().alt_fg),
-                        )),
+                        size.fg(self.theme.theme_colors().alt_fg),
                     ]));
 
-                    Row::new(vec![path_cell, object_type_cell, size_cell])
-                        .height(2)
-                        .style(Style::new().bg(color))
+                    let mut cells = vec![path_cell, object_type_cell, size_cell];
+                    if let Some(git_info) = &self.git_info {
+                        cells.push(Cell::from(Text::from(vec![
+                            Line::from(" "),
+                            Line::from(git_status_span(
+                                git_info.status_of(&entry.path),
+                                &self.theme.theme_colors(),
+                            )),
+                        ])));
+                    }
+                    if let Some((n, group)) = duplicate_group {
+                        // the group summary is only shown in the first row of each group
+                        let group_desc = if group.start == index {
+                            format!(
+                                "#{} {} free",
+                                n + 1,
+                                utils::convert_bytes_to_human_readable(group.reclaimable())
+                            )
+                        } else {
+                            format!("#{}", n + 1)
+                        };
+                        cells.push(Cell::from(Text::from(vec![
+                            Line::from(" "),
+                            Line::from(Span::styled(
+                                group_desc,
+                                Style::new().fg(self.theme.theme_colors().alt_fg),
+                            )),
+                        ])));
+                    }
+
+                    Row::new(cells).height(2).style(Style::new().bg(color))
                 })
                 .collect::<Vec<Row>>();
 
@@ -757,6 +1845,25 @@ impl Component for ResultWidget {
             f.render_widget(first_block, draw_area);
             f.render_widget(second_block, second_block_area);
             f.render_stateful_widget(results_table, table_area, &mut self.table_state);
+
+            if let Some(resolution) = self.pending_resolution {
+                self.render_resolution_confirmation(f, area, resolution);
+            }
+            if let Some(input) = &mut self.refine_input {
+                input.render(f, second_block_area, true);
+            }
+            if self.bulk_menu.is_visible() {
+                self.bulk_menu.render(f, area, &theme_colors);
+            }
+            if let Some((_, input)) = &mut self.bulk_target_input {
+                input.render(f, second_block_area, true);
+            }
+            if let Some(job) = &self.pending_bulk_job {
+                self.render_bulk_confirmation(f, area, job);
+            }
+            if self.error_report.is_visible() {
+                self.error_report.render(f, area, &theme_colors);
+            }
         }
         Ok(())
     }
@@ -6,10 +6,27 @@ use serde::{Deserialize, Serialize};
 use std::path::PathBuf;
 
 use crate::{
-    app::{AppContext, AppState, actions::Action, config::AppConfig, key_bindings},
+    app::{
+        AppContext, AppState,
+        actions::Action,
+        config::AppConfig,
+        key_bindings,
+        presets::{SearchPreset, load_presets},
+    },
     component::Component,
+    file_handling::{
+        SearchResult,
+        filters::parse_query,
+        index::{IndexedRoot, format_age},
+        reports::ReportKind,
+        search::{QueryMode, SearchQuery, SearchType},
+    },
     tui::Event,
-    ui::{Theme, centered_rect_fixed_height, get_main_layout, input::SearchInput},
+    ui::{
+        Theme, centered_rect_fixed_height, get_main_layout,
+        input::{SearchInput, SettingsInput},
+        preset_picker::PresetPicker,
+    },
     utils,
 };
 
@@ -30,10 +47,12 @@ impl std::fmt::Display for SearchMode {
 }
 
 impl SearchMode {
-    fn depth(&self) -> usize {
+    /// Returns the depth of the directory walk, the Deep search is limited by the configured depth (0 = unlimited)
+    fn depth(&self, deep_search_depth: u8) -> usize {
         match self {
             SearchMode::Flat => 1,
-            SearchMode::Deep => usize::MAX,
+            SearchMode::Deep if deep_search_depth == 0 => usize::MAX,
+            SearchMode::Deep => deep_search_depth as usize,
         }
     }
 }
@@ -56,13 +75,32 @@ pub struct SearchWidget {
     // The shortened CWD -> used as Block title
     cwd_display_name: String,
     /// Flag to control the receiving of the key events for the search widget
-    /// If the widget is working, then incoming key events are ignored
+    /// If the widget is working, then incoming key events are ignored, except `Esc` to cancel the search
     is_working: bool,
     theme: Theme,
     mode: SearchMode,
+    search_type: SearchType,
+    query_mode: QueryMode,
+    case_sensitive: bool,
+    /// Error of the current query (e.g. an invalid regex), shown below the input before the search is started
+    query_error: Option<String>,
     follow_sym_links: bool,
+    /// Maximum depth of the Deep search, 0 = unlimited
+    deep_search_depth: u8,
+    /// Depth of a loaded saved search, replaces the configured depth until the page is opened again
+    depth_override: Option<u8>,
+    exclude_patterns: Vec<String>,
+    cross_filesystems: bool,
+    /// The configured directories of the filesystem index
+    indexed_dirs: Vec<PathBuf>,
+    /// The completely indexed directories, reported by the Explorer after each index update
+    indexed_roots: Vec<IndexedRoot>,
     /// Handles all text input logic
     search_input: SearchInput,
+    /// Lists the saved searches
+    preset_picker: PresetPicker,
+    /// Input for the name, under which the current search is saved
+    preset_name_input: Option<SettingsInput>,
 }
 
 impl Default for SearchWidget {
@@ -78,25 +116,95 @@ impl Default for SearchWidget {
             is_working: Default::default(),
             theme: Default::default(),
             mode: Default::default(),
+            search_type: Default::default(),
+            query_mode: Default::default(),
+            case_sensitive: Default::default(),
+            query_error: Default::default(),
             follow_sym_links: Default::default(),
+            deep_search_depth: Default::default(),
+            depth_override: Default::default(),
+            exclude_patterns: Default::default(),
+            cross_filesystems: true,
+            indexed_dirs: Default::default(),
+            indexed_roots: Default::default(),
             search_input: SearchInput::default(),
+            preset_picker: PresetPicker::default(),
+            preset_name_input: Default::default(),
         }
     }
 }
 
 impl SearchWidget {
-    async fn submit_search(&mut self) -> Result<()> {
+    /// Builds the search query from the current input and settings.<br>
+    /// The filters are split from the entered text, an invalid filter is returned as error
+    fn build_query(&self) -> Result<SearchQuery> {
+        let (pattern, filters) = parse_query(self.search_input.text_input.value())?;
+
+        Ok(SearchQuery {
+            cwd: self.cwd.clone(),
+            pattern,
+            filters,
+            search_type: self.search_type,
+            query_mode: self.query_mode,
+            case_sensitive: self.case_sensitive,
+            depth: self.mode.depth(self.deep_search_depth()),
+            follow_sym_links: self.follow_sym_links,
+            exclude_patterns: self.exclude_patterns.clone(),
+            same_file_system: !self.cross_filesystems,
+        })
+    }
+
+    /// Validates the current query, so that errors can be shown while typing
+    fn validate_query(&mut self) {
+        self.query_error = self
+            .build_query()
+            .and_then(|query| query.validate())
+            .err()
+            .map(|err| err.to_string());
+    }
+
+    /// The depth of the Deep search, 0 = unlimited
+    fn deep_search_depth(&self) -> u8 {
+        self.depth_override.unwrap_or(self.deep_search_depth)
+    }
+
+    async fn submit_search(&mut self) -> Result<Option<Action>> {
+        if self.search_input.text_input.is_empty() && self.search_type.requires_query() {
+            return Ok(Action::UpdateAppState(AppState::Failure(
+                "Search query must not be empty".to_string(),
+            ))
+            .into());
+        }
+
+        let query = match self
+            .build_query()
+            .and_then(|query| query.validate().map(|_| query))
+        {
+            Ok(query) => query,
+            Err(err) => {
+                return Ok(Action::UpdateAppState(AppState::Failure(err.to_string())).into());
+            }
+        };
+
         // Saves the current query into the history (if not already present)
         self.search_input.submit();
 
-        self.send_explorer_action(Action::StartSearch(
-            self.cwd.clone(),
-            self.search_input.text_input.value().to_string(),
-            self.mode.depth(),
-            self.follow_sym_links,
-        ))
-        .await?;
-        Ok(())
+        if query.search_type.is_streamed() {
+            // the Results page is opened before the search starts, so that it receives all streamed matches
+            self.send_app_action(Action::ShowResultsPage(
+                SearchResult::in_progress(&query),
+                self.mode,
+            ))?;
+            self.send_explorer_action(Action::StartSearch(query))
+                .await?;
+            // the Results page handles the progress and the end of the search
+            self.is_working = false;
+            return Ok(Action::SwitchAppContext(AppContext::Results).into());
+        }
+
+        self.send_explorer_action(Action::StartSearch(query))
+            .await?;
+        Ok(None)
     }
 
     /// Helper function to send a [`Action`] to the [`crate::file_handling::Explorer`]
@@ -117,6 +225,135 @@ impl SearchWidget {
         Ok(())
     }
 
+    fn apply_search_settings(&mut self, config: &AppConfig) {
+        self.follow_sym_links = config.follow_sym_links();
+        self.deep_search_depth = config.deep_search_depth();
+        self.exclude_patterns = config.exclude_patterns();
+        self.cross_filesystems = config.cross_filesystems();
+        self.indexed_dirs = config.indexed_dirs();
+    }
+
+    /// Describes the state of the index, if a name search in the current directory is answered from it,
+    /// e.g. ` [Index updated 2h ago]`
+    fn index_desc(&self) -> String {
+        if self.search_type != SearchType::Name
+            || self.follow_sym_links
+            || !self
+                .indexed_dirs
+                .iter()
+                .any(|dir| self.cwd.starts_with(dir))
+        {
+            return String::new();
+        }
+
+        match self
+            .indexed_roots
+            .iter()
+            .find(|root| self.cwd.starts_with(&root.path))
+        {
+            Some(root) => format!(" [Index updated {}]", format_age(root.updated_at)),
+            None => " [Index not built yet]".to_string(),
+        }
+    }
+
+    /// Saves the current search under the given name into the directory of the saved searches
+    fn save_preset(&self, name: &str) -> Action {
+        let preset = SearchPreset {
+            name: name.trim().to_string(),
+            query: self.search_input.text_input.value().to_string(),
+            search_type: self.search_type,
+            query_mode: self.query_mode,
+            case_sensitive: self.case_sensitive,
+            mode: self.mode,
+            depth: self.deep_search_depth(),
+            root: utils::format_path_for_display(&self.cwd),
+        };
+
+        let state = match preset.save(utils::presets_dir()) {
+            Ok(path) => AppState::Done(format!(
+                "Saved search '{}' [{}]",
+                preset.name,
+                utils::format_path_for_display(path)
+            )),
+            Err(err) => AppState::Failure(err.to_string()),
+        };
+        Action::UpdateAppState(state)
+    }
+
+    /// Loads the saved search into the Search page, so that it can be submitted or edited
+    fn load_preset(&mut self, preset: &SearchPreset) -> Result<()> {
+        if let Some(root) = preset.root_dir() {
+            if !root.is_dir() {
+                anyhow::bail!(
+                    "The directory of the saved search '{}' does not exist [{}]",
+                    preset.name,
+                    preset.root
+                );
+            }
+            self.cwd_display_name = utils::format_path_for_display(&root);
+            self.cwd = root;
+        }
+
+        self.search_type = preset.search_type;
+        self.query_mode = preset.query_mode;
+        self.case_sensitive = preset.case_sensitive;
+        self.mode = preset.mode;
+        self.depth_override = Some(preset.depth);
+        self.search_input.set_value(&preset.query);
+        self.validate_query();
+        Ok(())
+    }
+
+    async fn handle_preset_picker_key_events(
+        &mut self,
+        key: crossterm::event::KeyEvent,
+    ) -> Result<Option<Action>> {
+        match key.code {
+            crossterm::event::KeyCode::Up => self.preset_picker.previous(),
+            crossterm::event::KeyCode::Down => self.preset_picker.next(),
+            crossterm::event::KeyCode::Esc => self.preset_picker.close(),
+            // Enter re-runs the saved search, Tab only loads it for editing
+            crossterm::event::KeyCode::Enter | crossterm::event::KeyCode::Tab => {
+                let Some(preset) = self.preset_picker.selected().cloned() else {
+                    return Ok(None);
+                };
+                self.preset_picker.close();
+                if let Err(err) = self.load_preset(&preset) {
+                    return Ok(Action::UpdateAppState(AppState::Failure(err.to_string())).into());
+                }
+                if key.code == crossterm::event::KeyCode::Enter {
+                    return self.submit_search().await;
+                }
+            }
+            _ => {}
+        }
+        Ok(None)
+    }
+
+    async fn handle_preset_name_key_events(
+        &mut self,
+        key: crossterm::event::KeyEvent,
+    ) -> Result<Option<Action>> {
+        let Some(input) = &mut self.preset_name_input else {
+            return Ok(None);
+        };
+
+        // the input actions are only meant for this page, not for the Settings page
+        match input.handle_key_events(key).await? {
+            Some(Action::ApplySettingsInput) => {
+                let name = input.value().to_string();
+                let action = self.save_preset(&name);
+                self.preset_name_input = None;
+                Ok(action.into())
+            }
+            Some(Action::SettingsInputCanceled) => {
+                self.preset_name_input = None;
+                Ok(None)
+            }
+            action => Ok(action),
+        }
+    }
+
     fn switch_search_mode(&mut self) {
         match self.mode {
             SearchMode::Flat => self.mode = SearchMode::Deep,
@@ -145,7 +382,8 @@ impl Component for SearchWidget {
 
     fn register_config_handler(&mut self, config: AppConfig) -> Result<()> {
         self.theme = config.theme();
-        self.follow_sym_links = config.follow_sym_links();
+        self.apply_search_settings(&config);
+        self.search_input.load_history(utils::search_history_file());
         Ok(())
     }
 
@@ -174,17 +412,58 @@ impl Component for SearchWidget {
         &mut self,
         key: crossterm::event::KeyEvent,
     ) -> Result<Option<Action>> {
+        if self.is_working {
+            if key.code == crossterm::event::KeyCode::Esc {
+                // the Explorer reports the cancellation in the footer
+                self.send_explorer_action(Action::CancelSearch).await?;
+                self.is_working = false;
+                return Ok(None);
+            }
+            return Ok(None);
+        }
+
+        if self.preset_name_input.is_some() {
+            return self.handle_preset_name_key_events(key).await;
+        }
+        if self.preset_picker.is_visible() {
+            return self.handle_preset_picker_key_events(key).await;
+        }
+
+        // keys not consumed by the reverse search are handled with the accepted query
+        if self.search_input.is_reverse_searching()
+            && self.search_input.handle_reverse_search_key(key)
+        {
+            self.validate_query();
+            return Ok(None);
+        }
+
         match key.code {
             // Submit search
             crossterm::event::KeyCode::Enter => {
-                if !self.search_input.text_input.is_empty() {
-                    self.submit_search().await?;
-                } else {
+                return self.submit_search().await;
+            }
+            crossterm::event::KeyCode::Char('p') if key.modifiers == KeyModifiers::CONTROL => {
+                let presets = load_presets(utils::presets_dir());
+                if presets.is_empty() {
+                    return Ok(Action::UpdateAppState(AppState::Failure(
+                        "No saved searches yet - Save the current search with Ctrl+N".to_string(),
+                    ))
+                    .into());
+                }
+                self.preset_picker.open(presets);
+            }
+            crossterm::event::KeyCode::Char('n') if key.modifiers == KeyModifiers::CONTROL => {
+                if self.search_input.text_input.is_empty() && self.search_type.requires_query() {
                     return Ok(Action::UpdateAppState(AppState::Failure(
                         "Search query must not be empty".to_string(),
                     ))
                     .into());
                 }
+                if let Some(err) = &self.query_error {
+                    return Ok(Action::UpdateAppState(AppState::Failure(err.clone())).into());
+                }
+                self.preset_name_input =
+                    Some(SettingsInput::new("Save current search as").with_name(""));
             }
             crossterm::event::KeyCode::Char('o') if key.modifiers == KeyModifiers::CONTROL => {
                 return Ok(Action::HideOrShowSystemOverview.into());
@@ -193,9 +472,34 @@ impl Component for SearchWidget {
                 self.theme = self.theme.toggle_theme();
                 return Ok(Action::ToggleTheme(self.theme).into());
             }
+            crossterm::event::KeyCode::Char('g') if key.modifiers == KeyModifiers::CONTROL => {
+                self.query_mode = self.query_mode.next();
+                self.validate_query();
+            }
+            crossterm::event::KeyCode::Char('s') if key.modifiers == KeyModifiers::CONTROL => {
+                self.case_sensitive = !self.case_sensitive;
+            }
+            crossterm::event::KeyCode::F(5) if key.modifiers == KeyModifiers::NONE => {
+                if self.indexed_dirs.is_empty() {
+                    return Ok(Action::UpdateAppState(AppState::Failure(
+                        "No indexed directories - Add them on the Settings page".to_string(),
+                    ))
+                    .into());
+                }
+                // the index is updated in the background, the Search page stays usable
+                if let Some(sender) = &self.explorer_action_sender {
+                    sender
+                        .send(Action::UpdateIndex(self.indexed_dirs.clone()))
+                        .await?;
+                }
+            }
             crossterm::event::KeyCode::Tab if key.modifiers == KeyModifiers::NONE => {
                 self.switch_search_mode();
             }
+            crossterm::event::KeyCode::BackTab => {
+                self.search_type = self.search_type.next();
+                self.validate_query();
+            }
             crossterm::event::KeyCode::F(1) if key.modifiers == KeyModifiers::NONE => {
                 self.app_context = AppContext::NotActive;
                 return Ok(Action::ShowHelp(AppContext::Search).into());
@@ -221,8 +525,11 @@ impl Component for SearchWidget {
                 // - Backspace / Delete
                 // - Left / Right cursor movement
                 // - Up / Down history navigation
+                // - Ctrl+R reverse search through the history
                 // - Ctrl+V clipboard paste
-                return self.search_input.handle_key_events(key).await;
+                let action = self.search_input.handle_key_events(key).await;
+                self.validate_query();
+                return action;
             }
         }
 
@@ -230,7 +537,7 @@ impl Component for SearchWidget {
     }
 
     fn should_handle_events(&self) -> bool {
-        self.app_context == AppContext::Search && !self.is_working
+        self.app_context == AppContext::Search
     }
 
     fn should_render(&self) -> bool {
@@ -245,7 +552,12 @@ impl Component for SearchWidget {
             Action::ShowSearchPage(cwd) => {
                 self.cwd = cwd.to_path_buf();
                 self.cwd_display_name = utils::format_path_for_display(&self.cwd);
+                self.depth_override = None;
+                self.preset_picker.close();
+                self.preset_name_input = None;
             }
+            // a cancelled search may finish before the cancellation is received
+            Action::SearchDone(_) if !self.is_working => {}
             Action::SearchDone(search_result) => {
                 self.is_working = false;
                 if let Some(result) = search_result {
@@ -258,6 +570,9 @@ impl Component for SearchWidget {
                     .into());
                 }
             }
+            Action::IndexStatus(roots) => {
+                self.indexed_roots = roots.clone();
+            }
             Action::ToggleTheme(theme) => {
                 self.theme = *theme;
             }
@@ -265,7 +580,8 @@ impl Component for SearchWidget {
                 self.use_whole_draw_area = !self.use_whole_draw_area;
             }
             Action::ApplyAppSettings(c) => {
-                self.follow_sym_links = c.follow_sym_links();
+                self.apply_search_settings(c);
+                self.validate_query();
             }
             _ => {}
         }
@@ -288,24 +604,99 @@ impl Component for SearchWidget {
             let theme_colors = self.theme.theme_colors();
 
             let main_block_title = format!(" Cwd: [{}] ", self.cwd_display_name);
-            let inner_block_title = match self.mode {
-                SearchMode::Flat => " Search for file/directory names in the current directory ",
-                SearchMode::Deep => {
-                    " Search for file/directory names in the current directory and all subdirectories "
+            let search_desc = match self.search_type {
+                SearchType::Name => "Search for file/directory names",
+                SearchType::Content => "Search for lines in file contents",
+                SearchType::Format => "Search for files by their detected format",
+                SearchType::Duplicates => "Search for duplicate files",
+                SearchType::Report(ReportKind::LargestFiles) => "List the largest files",
+                SearchType::Report(ReportKind::RecentlyModified) => "List recently modified files",
+                SearchType::Report(ReportKind::Empty) => "List empty files and directories",
+                SearchType::Report(ReportKind::BrokenSymlinks) => "List broken symlinks",
+            };
+            let scope_desc = match self.mode {
+                SearchMode::Flat => "in the current directory".to_string(),
+                SearchMode::Deep if self.deep_search_depth() == 0 => {
+                    "in the current directory and all subdirectories".to_string()
+                }
+                SearchMode::Deep => format!(
+                    "in the current directory and subdirectories up to depth {}",
+                    self.deep_search_depth()
+                ),
+            };
+            let query_desc = match self.search_type {
+                SearchType::Name | SearchType::Content => " (with filters like ext:rs size:>1M)",
+                SearchType::Format => {
+                    " (categories like image, video, audio, archive, document, executable or formats like pdf)"
+                }
+                SearchType::Duplicates => " (optional name filter)",
+                SearchType::Report(ReportKind::LargestFiles) => " (number of files, default 100)",
+                SearchType::Report(ReportKind::RecentlyModified) => {
+                    " (time span like 30m, 12h or 7d, default 24h)"
                 }
+                SearchType::Report(_) => " (no query required)",
+            };
+            let inner_block_title = format!(
+                " {search_desc} {scope_desc}{query_desc}{} ",
+                self.index_desc()
+            );
+            let case_desc = if self.case_sensitive {
+                "Sensitive"
+            } else {
+                "Insensitive"
+            };
+            let input_block_title = format!(
+                " Type search query [Mode: {}, Type: {}, Query: {}, Case: {}] ",
+                self.mode, self.search_type, self.query_mode, case_desc
+            );
+
+            let help_msg = if self.search_input.is_reverse_searching() {
+                vec![
+                    " <Esc>".fg(theme_colors.main_text_fg),
+                    " Cancel history search ".fg(theme_colors.main_fg),
+                    "|".fg(theme_colors.main_fg),
+                    " <Enter>".fg(theme_colors.main_text_fg),
+                    " Submit match ".fg(theme_colors.main_fg),
+                    "|".fg(theme_colors.main_fg),
+                    " <Ctrl+R>".fg(theme_colors.main_text_fg),
+                    " Older match ".fg(theme_colors.main_fg),
+                    "|".fg(theme_colors.main_fg),
+                    " <←/→>".fg(theme_colors.main_text_fg),
+                    " Edit match ".fg(theme_colors.main_fg),
+                ]
+            } else {
+                vec![
+                    " <Esc>".fg(theme_colors.main_text_fg),
+                    " Back to Explorer ".fg(theme_colors.main_fg),
+                    "|".fg(theme_colors.main_fg),
+                    " <Enter>".fg(theme_colors.main_text_fg),
+                    " Submit search ".fg(theme_colors.main_fg),
+                    "|".fg(theme_colors.main_fg),
+                    " <Tab>".fg(theme_colors.main_text_fg),
+                    " Switch search mode ".fg(theme_colors.main_fg),
+                    "|".fg(theme_colors.main_fg),
+                    " <Shift+Tab>".fg(theme_colors.main_text_fg),
+                    " Switch search type ".fg(theme_colors.main_fg),
+                    "|".fg(theme_colors.main_fg),
+                    " <Ctrl+G>".fg(theme_colors.main_text_fg),
+                    " Switch query mode ".fg(theme_colors.main_fg),
+                    "|".fg(theme_colors.main_fg),
+                    " <Ctrl+S>".fg(theme_colors.main_text_fg),
+                    " Toggle case ".fg(theme_colors.main_fg),
+                    "|".fg(theme_colors.main_fg),
+                    " <Ctrl+R>".fg(theme_colors.main_text_fg),
+                    " Search history ".fg(theme_colors.main_fg),
+                    "|".fg(theme_colors.main_fg),
+                    " <Ctrl+P>".fg(theme_colors.main_text_fg),
+                    " Saved searches ".fg(theme_colors.main_fg),
+                    "|".fg(theme_colors.main_fg),
+                    " <Ctrl+N>".fg(theme_colors.main_text_fg),
+                    " Save search ".fg(theme_colors.main_fg),
+                    "|".fg(theme_colors.main_fg),
+                    " <F5>".fg(theme_colors.main_text_fg),
+                    " Reindex ".fg(theme_colors.main_fg),
+                ]
             };
-            let input_block_title = format!(" Type search query [Mode: {}] ", self.mode);
-
-            let help_msg = vec![
-                " <Esc>".fg(theme_colors.main_text_fg),
-                " Back to Explorer ".fg(theme_colors.main_fg),
-                "|".fg(theme_colors.main_fg),
-                " <Enter>".fg(theme_colors.main_text_fg),
-                " Submit search ".fg(theme_colors.main_fg),
-                "|".fg(theme_colors.main_fg),
-                " <Tab>".fg(theme_colors.main_text_fg),
-                " Switch search mode ".fg(theme_colors.main_fg),
-            ];
 
             // CWD block
             let first_block = Block::default()
@@ -351,7 +742,7 @@ impl Component for SearchWidget {
                 SearchMode::Deep => theme_colors.alt_fg,
             };
 
-            let input_block = Block::default()
+            let mut input_block = Block::default()
                 .title_top(Line::from(input_block_title))
                 .title_alignment(Alignment::Left)
                 .borders(Borders::ALL)
@@ -359,6 +750,14 @@ impl Component for SearchWidget {
                 .border_style(Style::new().fg(border_fg))
                 .style(Style::new().bg(theme_colors.alt_bg));
 
+            if let Some(err) = &self.query_error {
+                input_block = input_block.title_bottom(
+                    Line::from(format!(" {err} "))
+                        .fg(theme_colors.failure_state_color)
+                        .left_aligned(),
+                );
+            }
+
             let [spacer_line_area, input_block_area] =
                 Layout::vertical([Constraint::Length(1), Constraint::Length(1)])
                     .areas(input_block.inner(third_block_area));
@@ -373,8 +772,19 @@ impl Component for SearchWidget {
                 input_centered_area,
                 theme_colors.alt_bg,
                 theme_colors.main_text_fg,
-                !self.is_working, // show_cursor only when no search is running
+                // show_cursor only when no search is running and no popup is shown
+                !self.is_working
+                    && !self.preset_picker.is_visible()
+                    && self.preset_name_input.is_none(),
             );
+
+            if self.preset_picker.is_visible() {
+                self.preset_picker
+                    .render(f, second_block_area, &theme_colors);
+            }
+            if let Some(input) = &mut self.preset_name_input {
+                input.render(f, second_block_area, true);
+            }
         }
 
         Ok(())
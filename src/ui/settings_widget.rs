@@ -1,11 +1,15 @@
-use std::path::Path;
+use std::path::{Path, PathBuf};
 
 use anyhow::Result;
 use async_trait::async_trait;
 use ratatui::{prelude::*, style::palette::tailwind, widgets::*};
 
 use crate::{
-    app::{AppContext, actions::Action, config::AppConfig},
+    app::{
+        AppContext,
+        actions::Action,
+        config::{AppConfig, parse_dir_list, parse_exclude_patterns},
+    },
     component::Component,
     models::{Scrollable, StatefulTable},
     tui::Event,
@@ -13,6 +17,9 @@ use crate::{
     utils,
 };
 
+/// Dropdown entry of the Deep search depth, that is stored as 0
+const DEPTH_UNLIMITED: &str = "Unlimited";
+
 /// Track the current selected settings type
 #[derive(Debug)]
 enum SettingsTypes {
@@ -20,6 +27,10 @@ enum SettingsTypes {
     StartDirectory,
     ExportDirectory,
     FollowSymLinks,
+    DeepSearchDepth,
+    ExcludePatterns,
+    CrossFilesystems,
+    IndexedDirs,
     Fps,
     SystemUpdateRate,
 }
@@ -29,6 +40,8 @@ enum SettingsTypes {
 enum DropDownTypes {
     Theme(Dropdown<Theme>),
     SymLinks(Dropdown<String>),
+    DeepSearchDepth(Dropdown<String>),
+    CrossFilesystems(Dropdown<String>),
     Fps(Dropdown<u8>),
     SystemUpdateRate(Dropdown<u8>),
     #[default]
@@ -50,6 +63,23 @@ impl DropDownTypes {
         ))
     }
 
+    fn deep_search_depth(current: &u8) -> Self {
+        let mut depths = vec![DEPTH_UNLIMITED.to_string()];
+        depths.extend((2_u8..=32_u8).map(|depth| depth.to_string()));
+        let current = match current {
+            0 => DEPTH_UNLIMITED.to_string(),
+            depth => depth.to_string(),
+        };
+        Self::DeepSearchDepth(Dropdown::new(depths, &current).with_max_visible(8))
+    }
+
+    fn cross_filesystems(current: &str) -> Self {
+        Self::CrossFilesystems(Dropdown::new(
+            vec!["Yes".into(), "No".into()],
+            &current.to_string(),
+        ))
+    }
+
     fn fps(current: &u8) -> Self {
         Self::Fps(Dropdown::new((30_u8..=60_u8).collect(), current).with_max_visible(8))
     }
@@ -65,6 +95,8 @@ impl DropDownTypes {
         match self {
             Self::Theme(d) => d.handle_key_events(key).await,
             Self::SymLinks(d) => d.handle_key_events(key).await,
+            Self::DeepSearchDepth(d) => d.handle_key_events(key).await,
+            Self::CrossFilesystems(d) => d.handle_key_events(key).await,
             Self::Fps(d) => d.handle_key_events(key).await,
             Self::SystemUpdateRate(d) => d.handle_key_events(key).await,
             _ => Ok(None),
@@ -75,6 +107,8 @@ impl DropDownTypes {
         match self {
             Self::Theme(d) => d.render(f, area, "Theme"),
             Self::SymLinks(d) => d.render(f, area, "Follow symbolic links"),
+            Self::DeepSearchDepth(d) => d.render(f, area, "Deep search depth"),
+            Self::CrossFilesystems(d) => d.render(f, area, "Cross filesystems"),
             Self::Fps(d) => d.render(f, area, "Frames per second"),
             Self::SystemUpdateRate(d) => d.render(f, area, "System update rate per second"),
             _ => {}
@@ -87,6 +121,8 @@ impl DropDownTypes {
 enum InputTypes {
     StartDir(SettingsInput),
     ExportDir(SettingsInput),
+    ExcludePatterns(SettingsInput),
+    IndexedDirs(SettingsInput),
     #[default]
     Undefined,
 }
@@ -100,6 +136,18 @@ impl InputTypes {
         Self::ExportDir(SettingsInput::new("Edit Export-Directory").with_value(current))
     }
 
+    fn exclude_patterns(current: &[String]) -> Self {
+        Self::ExcludePatterns(
+            SettingsInput::new("Edit Exclude-Patterns (comma separated)").with_patterns(current),
+        )
+    }
+
+    fn indexed_dirs(current: &[PathBuf]) -> Self {
+        Self::IndexedDirs(
+            SettingsInput::new("Edit Indexed-Directories (comma separated)").with_dirs(current),
+        )
+    }
+
     async fn handle_key_events(
         &mut self,
         key: crossterm::event::KeyEvent,
@@ -107,6 +155,8 @@ impl InputTypes {
         match self {
             Self::StartDir(d) => d.handle_key_events(key).await,
             Self::ExportDir(d) => d.handle_key_events(key).await,
+            Self::ExcludePatterns(d) => d.handle_key_events(key).await,
+            Self::IndexedDirs(d) => d.handle_key_events(key).await,
             _ => Ok(None),
         }
     }
@@ -115,6 +165,8 @@ impl InputTypes {
         match self {
             Self::StartDir(d) => d.render(f, area, true),
             Self::ExportDir(d) => d.render(f, area, true),
+            Self::ExcludePatterns(d) => d.render(f, area, true),
+            Self::IndexedDirs(d) => d.render(f, area, true),
             _ => {}
         }
     }
@@ -193,6 +245,29 @@ impl SettingsPage {
                     self.dropdown = DropDownTypes::sym_links(current);
                     action = Some(Action::DropDownShowing);
                 }
+                SettingsTypes::DeepSearchDepth => {
+                    self.dropdown =
+                        DropDownTypes::deep_search_depth(&self.config.deep_search_depth());
+                    action = Some(Action::DropDownShowing);
+                }
+                SettingsTypes::ExcludePatterns => {
+                    self.settings_input =
+                        InputTypes::exclude_patterns(&self.config.exclude_patterns());
+                    action = Some(Action::SettingsInputShowing);
+                }
+                SettingsTypes::IndexedDirs => {
+                    self.settings_input = InputTypes::indexed_dirs(&self.config.indexed_dirs());
+                    action = Some(Action::SettingsInputShowing);
+                }
+                SettingsTypes::CrossFilesystems => {
+                    let current = if self.config.cross_filesystems() {
+                        "Yes"
+                    } else {
+                        "No"
+                    };
+                    self.dropdown = DropDownTypes::cross_filesystems(current);
+                    action = Some(Action::DropDownShowing);
+                }
                 SettingsTypes::Fps => {
                     self.dropdown = DropDownTypes::fps(&self.config.fps());
                     action = Some(Action::DropDownShowing);
@@ -339,6 +414,10 @@ impl Component for SettingsPage {
                     SettingsTypes::StartDirectory,
                     SettingsTypes::ExportDirectory,
                     SettingsTypes::FollowSymLinks,
+                    SettingsTypes::DeepSearchDepth,
+                    SettingsTypes::ExcludePatterns,
+                    SettingsTypes::CrossFilesystems,
+                    SettingsTypes::IndexedDirs,
                     SettingsTypes::Fps,
                     SettingsTypes::SystemUpdateRate,
                 ]);
@@ -372,6 +451,19 @@ impl Component for SettingsPage {
                         };
                         self.config.set_follow_sym_links(follow_sym_links);
                     }
+                    DropDownTypes::DeepSearchDepth(d) => {
+                        // "Unlimited" is stored as 0
+                        self.config
+                            .set_deep_search_depth(d.selected().parse().unwrap_or_default());
+                    }
+                    DropDownTypes::CrossFilesystems(d) => {
+                        let cross_filesystems = match d.selected().as_str() {
+                            "Yes" => true,
+                            "No" => false,
+                            _ => self.config.cross_filesystems(), // fallback to current value if something unexpected happens
+                        };
+                        self.config.set_cross_filesystems(cross_filesystems);
+                    }
                     DropDownTypes::Fps(d) => {
                         self.config.set_fps(*d.selected());
                     }
@@ -395,6 +487,13 @@ impl Component for SettingsPage {
                         let new_export_dir = utils::expand_and_resolve_path(d.value());
                         self.config.set_export_dir(new_export_dir);
                     }
+                    InputTypes::ExcludePatterns(d) => {
+                        self.config
+                            .set_exclude_patterns(parse_exclude_patterns(d.value()));
+                    }
+                    InputTypes::IndexedDirs(d) => {
+                        self.config.set_indexed_dirs(parse_dir_list(d.value()));
+                    }
                     _ => {}
                 }
                 // also update the new settings on the settings page
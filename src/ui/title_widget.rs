@@ -19,6 +19,8 @@ pub struct TitleBar {
     render_frames: u32,
     render_fps: f64,
     is_system_overview_showing: bool,
+    /// Checked out git branch, if the current directory is inside a git work tree
+    git_branch: Option<String>,
 }
 
 impl Default for TitleBar {
@@ -49,6 +51,7 @@ impl TitleBar {
             render_frames: 0,
             render_fps: 0.0,
             is_system_overview_showing: true,
+            git_branch: None,
         }
     }
 
@@ -121,6 +124,11 @@ impl Component for TitleBar {
             Action::HideOrShowSystemOverview => {
                 self.is_system_overview_showing = !self.is_system_overview_showing;
             }
+            Action::GitStatusDone(git_info) => {
+                self.git_branch = git_info
+                    .as_ref()
+                    .and_then(|git_info| git_info.branch().map(str::to_string));
+            }
             _ => {}
         }
 
@@ -131,14 +139,26 @@ impl Component for TitleBar {
         if self.should_render() {
             let title_area = get_main_layout(area).title_area;
 
-            let [spacer_area, app_name_area, help_hint_area, meta_data_area] =
-                Layout::horizontal([
-                    Constraint::Length(1),
-                    Constraint::Length(20),
-                    Constraint::Length(20),
-                    Constraint::Fill(1),
-                ])
-                .areas(title_area);
+            let git_branch = self
+                .git_branch
+                .as_ref()
+                .map(|branch| format!("Git: {} ", branch))
+                .unwrap_or_default();
+
+            let [
+                spacer_area,
+                app_name_area,
+                help_hint_area,
+                git_branch_area,
+                meta_data_area,
+            ] = Layout::horizontal([
+                Constraint::Length(1),
+                Constraint::Length(20),
+                Constraint::Length(20),
+                Constraint::Length(git_branch.chars().count() as u16),
+                Constraint::Fill(1),
+            ])
+            .areas(title_area);
 
             // the app and render tick rate formatted with two decimal places
             let rate_meta_data = format!(
@@ -167,6 +187,15 @@ impl Component for TitleBar {
             f.render_widget(Span::from(" ").bg(self.bg_color), spacer_area);
             f.render_widget(help_hint, help_hint_area);
 
+            let git_branch = Paragraph::new(Span::styled(
+                git_branch,
+                Style::default()
+                    .fg(Color::Black)
+                    .add_modifier(Modifier::BOLD),
+            ))
+            .style(Style::default().bg(self.bg_color));
+            f.render_widget(git_branch, git_branch_area);
+
             let meta_data = Paragraph::new(Span::styled(
                 rate_meta_data,
                 Style::default()
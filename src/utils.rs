@@ -128,6 +128,12 @@ pub fn config_dir() -> PathBuf {
     }
 }
 
+/// Get the directory of the saved searches, every saved search is stored in its own file.
+/// **The directory may not exist yet,** caller must create it.
+pub fn presets_dir() -> PathBuf {
+    config_dir().join("presets")
+}
+
 /// Retrieves the data directory path for the project.
 ///
 /// This function uses the `simple_home_dir` crate to determine the user's home directory
@@ -235,6 +241,18 @@ pub fn log_file_old() -> PathBuf {
     data_dir().join(format!("{}.log.old", APP_NAME))
 }
 
+/// Get the path to the file, in which the search history is stored. **Parent direct may not exist yet,**
+/// caller must create it.
+pub fn search_history_file() -> PathBuf {
+    data_dir().join("search_history.json")
+}
+
+/// Get the path to the database of the filesystem index. **Parent direct may not exist yet,**
+/// caller must create it.
+pub fn index_file() -> PathBuf {
+    data_dir().join("index.db")
+}
+
 /// Creates the application's data directory.
 ///
 /// This function creates the necessary data directories,
@@ -300,7 +318,8 @@ pub fn convert_bytes_to_human_readable(bytes: u64) -> String {
 /// or the original string if it is within the allowed length.
 pub fn reduce_string_and_fill_with_dots(data: &str, max_length: usize) -> String {
     if data.chars().count() > max_length {
-        let reduce_string = &data[..max_length - 3];
+        // cut at a char boundary, a multi-byte character would otherwise cause a panic
+        let reduce_string: String = data.chars().take(max_length.saturating_sub(3)).collect();
         // Append the three dots
         format!("{}...", reduce_string)
     } else {
@@ -463,6 +482,76 @@ pub fn extract_part(text: &str, search: &str) -> Option<String> {
     }
 }
 
+/// Builds the command line to open the file in the editor of the user, optionally at the given line.<br>
+/// The editor is taken from `$VISUAL` or `$EDITOR` and may contain arguments, e.g. `code --wait`.
+pub fn editor_command(
+    editor: &str,
+    path: &Path,
+    line: Option<usize>,
+) -> Option<(String, Vec<String>)> {
+    let mut parts = editor.split_whitespace().map(str::to_string);
+    let program = parts.next()?;
+    let mut args: Vec<String> = parts.collect();
+
+    let program_name = Path::new(&program)
+        .file_stem()
+        .map(|name| name.to_string_lossy().to_lowercase())
+        .unwrap_or_default();
+    let path = path.to_string_lossy().to_string();
+
+    match line {
+        // VS Code and its forks expect the line appended to the path
+        Some(line) if matches!(program_name.as_str(), "code" | "codium" | "cursor") => {
+            args.push("--goto".into());
+            args.push(format!("{path}:{line}"));
+        }
+        Some(line) if matches!(program_name.as_str(), "hx" | "helix" | "subl" | "zed") => {
+            args.push(format!("{path}:{line}"));
+        }
+        // Notepad doesn't support to open a file at a specific line
+        Some(_) if program_name == "notepad" => args.push(path),
+        // vi, vim, nvim, nano, emacs, micro, kak, ...
+        Some(line) => {
+            args.push(format!("+{line}"));
+            args.push(path);
+        }
+        None => args.push(path),
+    }
+
+    Some((program, args))
+}
+
+/// Opens the file in the editor of the user and waits until the editor is closed.<br>
+/// The terminal must be restored before, because the editor takes over the terminal.
+pub fn open_in_editor(path: &Path, line: Option<usize>) -> Result<()> {
+    let editor = std::env::var("VISUAL")
+        .or_else(|_| std::env::var("EDITOR"))
+        .unwrap_or_else(|_| {
+            if cfg!(target_os = "windows") {
+                "notepad".to_string()
+            } else {
+                "vi".to_string()
+            }
+        });
+
+    let (program, args) = editor_command(&editor, path, line)
+        .with_context(|| format!("Invalid editor command: [{}]", editor))?;
+
+    let status = std::process::Command::new(&program)
+        .args(&args)
+        .status()
+        .with_context(|| format!("Failed to start the editor: [{}]", program))?;
+
+    if !status.success() {
+        return Err(anyhow::anyhow!(
+            "The editor [{}] exited with {}",
+            program,
+            status
+        ));
+    }
+    Ok(())
+}
+
 #[cfg(test)]
 mod tests_key_event_to_string {
     use super::*;
@@ -787,4 +876,30 @@ mod tests_common {
 
         assert_eq!(calculate_percentage_f64(numerator, denominator), expected);
     }
+
+    #[test]
+    fn test_editor_command() {
+        let path = Path::new("/tmp/main.rs");
+
+        assert_eq!(
+            editor_command("nvim", path, Some(12)),
+            Some(("nvim".into(), vec!["+12".into(), "/tmp/main.rs".into()]))
+        );
+        assert_eq!(
+            editor_command("code --wait", path, Some(3)),
+            Some((
+                "code".into(),
+                vec!["--wait".into(), "--goto".into(), "/tmp/main.rs:3".into()]
+            ))
+        );
+        assert_eq!(
+            editor_command("/usr/bin/hx", path, Some(7)),
+            Some(("/usr/bin/hx".into(), vec!["/tmp/main.rs:7".into()]))
+        );
+        assert_eq!(
+            editor_command("vi", path, None),
+            Some(("vi".into(), vec!["/tmp/main.rs".into()]))
+        );
+        assert_eq!(editor_command("  ", path, Some(1)), None);
+    }
 }